@@ -1,8 +1,9 @@
 //! Benchmarks for various APIs to make sure `CompactString` is at least no slower than `String`
 
+use std::fmt::Write;
 use std::time::Instant;
 
-use compact_str::CompactString;
+use compact_str::{CompactString, CompactStringExt};
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 
 static VERY_LONG_STR: &str = include_str!("../data/moby10b.txt");
@@ -39,6 +40,72 @@ fn compact_string_very_big_heap_length(c: &mut Criterion) {
     });
 }
 
+/// Strings that are inline, heap allocated, and `&'static str`s, in a pseudo-random order so the
+/// variant of the next string can't be predicted.
+fn mixed_variants() -> Vec<CompactString> {
+    let mut state = 0x2545_f491_u32;
+    (0..1024)
+        .map(|_| {
+            // xorshift32
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            match state % 3 {
+                0 => CompactString::new("i am short"),
+                1 => CompactString::new(
+                    "I am a very long string that will get allocated on the heap",
+                ),
+                _ => CompactString::const_new("I am a very long string that stays a &'static str"),
+            }
+        })
+        .collect()
+}
+
+fn compact_string_mixed_length(c: &mut Criterion) {
+    let strings = mixed_variants();
+    c.bench_function("mixed variants length", |b| {
+        b.iter(|| black_box(&strings).iter().map(|s| s.len()).sum::<usize>())
+    });
+}
+
+fn compact_string_mixed_capacity(c: &mut Criterion) {
+    let strings = mixed_variants();
+    c.bench_function("mixed variants capacity", |b| {
+        b.iter(|| {
+            black_box(&strings)
+                .iter()
+                .map(|s| s.capacity())
+                .sum::<usize>()
+        })
+    });
+}
+
+fn compact_string_mixed_eq_literal(c: &mut Criterion) {
+    // none of the variants have the same length as the literal, which is the common case when
+    // looking for a specific string
+    let strings = mixed_variants();
+    c.bench_function("mixed variants eq literal", |b| {
+        b.iter(|| {
+            black_box(&strings)
+                .iter()
+                .filter(|s| **s == black_box("needle"))
+                .count()
+        })
+    });
+}
+
+fn std_str_mixed_eq_literal(c: &mut Criterion) {
+    let strings: Vec<String> = mixed_variants().into_iter().map(String::from).collect();
+    c.bench_function("std mixed eq literal", |b| {
+        b.iter(|| {
+            black_box(&strings)
+                .iter()
+                .filter(|s| **s == black_box("needle"))
+                .count()
+        })
+    });
+}
+
 fn compact_string_reserve_small(c: &mut Criterion) {
     c.bench_function("reserve small", |b| {
         b.iter(|| {
@@ -178,6 +245,88 @@ fn compact_string_from_string_heap_long(c: &mut Criterion) {
     });
 }
 
+fn compact_string_from_str_very_long(c: &mut Criterion) {
+    c.bench_function("from str very long", |b| {
+        b.iter(|| CompactString::new(black_box(VERY_LONG_STR)))
+    });
+}
+
+fn compact_string_from_utf8_very_long(c: &mut Criterion) {
+    let bytes = VERY_LONG_STR.as_bytes();
+    c.bench_function("from utf8 unchecked very long", |b| {
+        b.iter(|| unsafe { CompactString::from_utf8_unchecked(black_box(bytes)) })
+    });
+}
+
+fn compact_string_clone_very_long(c: &mut Criterion) {
+    let compact = CompactString::new(VERY_LONG_STR);
+    c.bench_function("clone very long", |b| {
+        b.iter(|| black_box(&compact).clone())
+    });
+}
+
+fn compact_string_concat_many(c: &mut Criterion) {
+    let words: Vec<&str> = VERY_LONG_STR.split(' ').take(1024).collect();
+    c.bench_function("concat many", |b| {
+        b.iter(|| black_box(words.concat_compact()))
+    });
+}
+
+fn compact_string_join_many(c: &mut Criterion) {
+    let words: Vec<&str> = VERY_LONG_STR.split(' ').take(1024).collect();
+    c.bench_function("join many", |b| {
+        b.iter(|| black_box(words.join_compact(" ")))
+    });
+}
+
+fn compact_string_debug_inline(c: &mut Criterion) {
+    let compact_str = CompactString::new("i am short");
+    let mut buf = String::with_capacity(64);
+    c.bench_function("debug inline", |b| {
+        b.iter(|| {
+            buf.clear();
+            write!(buf, "{:?}", black_box(&compact_str)).unwrap();
+        })
+    });
+}
+
+fn compact_string_display_padded(c: &mut Criterion) {
+    let compact_str = CompactString::new("i am short");
+    let mut buf = String::with_capacity(64);
+    c.bench_function("display padded", |b| {
+        b.iter(|| {
+            buf.clear();
+            write!(buf, "{:>32}", black_box(&compact_str)).unwrap();
+        })
+    });
+}
+
+fn compact_string_write_fmt_padded(c: &mut Criterion) {
+    c.bench_function("write fmt padded", |b| {
+        b.iter(|| {
+            let mut compact = CompactString::default();
+            write!(
+                compact,
+                "{:>6}|{:>8.2}|{:<12}",
+                black_box(42),
+                black_box(1.5),
+                "abc"
+            )
+            .unwrap();
+            compact
+        })
+    });
+}
+
+fn compact_string_lines(c: &mut Criterion) {
+    c.bench_function("lines compact", |b| {
+        b.iter(|| {
+            let lines: Vec<CompactString> = CompactString::lines_compact(VERY_LONG_STR).collect();
+            black_box(lines)
+        })
+    });
+}
+
 fn std_string_short_length(c: &mut Criterion) {
     let word = "i am short";
     let string = String::from(word);
@@ -228,6 +377,71 @@ fn std_str_reserve_large(c: &mut Criterion) {
     });
 }
 
+fn std_str_from_str_very_long(c: &mut Criterion) {
+    c.bench_function("std str from str very long", |b| {
+        b.iter(|| String::from(black_box(VERY_LONG_STR)))
+    });
+}
+
+fn std_str_from_utf8_very_long(c: &mut Criterion) {
+    let bytes = VERY_LONG_STR.as_bytes();
+    c.bench_function("std str from utf8 unchecked very long", |b| {
+        b.iter(|| unsafe { String::from_utf8_unchecked(black_box(bytes).to_vec()) })
+    });
+}
+
+fn std_str_clone_very_long(c: &mut Criterion) {
+    let std_str = String::from(VERY_LONG_STR);
+    c.bench_function("std str clone very long", |b| {
+        b.iter(|| black_box(&std_str).clone())
+    });
+}
+
+fn std_str_concat_many(c: &mut Criterion) {
+    let words: Vec<&str> = VERY_LONG_STR.split(' ').take(1024).collect();
+    c.bench_function("std str concat many", |b| {
+        b.iter(|| black_box(words.concat()))
+    });
+}
+
+fn std_str_join_many(c: &mut Criterion) {
+    let words: Vec<&str> = VERY_LONG_STR.split(' ').take(1024).collect();
+    c.bench_function("std str join many", |b| {
+        b.iter(|| black_box(words.join(" ")))
+    });
+}
+
+fn std_str_debug_short(c: &mut Criterion) {
+    let std_str = String::from("i am short");
+    let mut buf = String::with_capacity(64);
+    c.bench_function("std str debug short", |b| {
+        b.iter(|| {
+            buf.clear();
+            write!(buf, "{:?}", black_box(&std_str)).unwrap();
+        })
+    });
+}
+
+fn std_str_display_padded(c: &mut Criterion) {
+    let std_str = String::from("i am short");
+    let mut buf = String::with_capacity(64);
+    c.bench_function("std str display padded", |b| {
+        b.iter(|| {
+            buf.clear();
+            write!(buf, "{:>32}", black_box(&std_str)).unwrap();
+        })
+    });
+}
+
+fn std_str_lines(c: &mut Criterion) {
+    c.bench_function("std str lines", |b| {
+        b.iter(|| {
+            let lines: Vec<String> = VERY_LONG_STR.lines().map(String::from).collect();
+            black_box(lines)
+        })
+    });
+}
+
 fn std_str_clone_small(c: &mut Criterion) {
     let std_str = String::from("i am short");
     c.bench_function("std str clone small", |b| b.iter(|| std_str.clone()));
@@ -277,6 +491,9 @@ criterion_group!(
     compact_string_inline_length,
     compact_string_heap_length,
     compact_string_very_big_heap_length,
+    compact_string_mixed_length,
+    compact_string_mixed_capacity,
+    compact_string_mixed_eq_literal,
     compact_string_reserve_small,
     compact_string_reserve_large,
     compact_string_clone_small,
@@ -287,7 +504,16 @@ criterion_group!(
     compact_string_extend_chars_heap_20,
     compact_string_from_string_inline,
     compact_string_from_string_heap,
-    compact_string_from_string_heap_long
+    compact_string_from_string_heap_long,
+    compact_string_from_str_very_long,
+    compact_string_from_utf8_very_long,
+    compact_string_clone_very_long,
+    compact_string_concat_many,
+    compact_string_join_many,
+    compact_string_debug_inline,
+    compact_string_display_padded,
+    compact_string_write_fmt_padded,
+    compact_string_lines,
 );
 criterion_group!(
     std_string,
@@ -298,9 +524,18 @@ criterion_group!(
     std_str_reserve_large,
     std_str_clone_small,
     std_str_clone_large_and_modify,
+    std_str_from_str_very_long,
+    std_str_from_utf8_very_long,
+    std_str_clone_very_long,
     std_str_extend_chars_empty,
     std_str_extend_chars_short,
     std_str_str_extend_chars_20,
+    std_str_concat_many,
+    std_str_join_many,
+    std_str_debug_short,
+    std_str_display_padded,
+    std_str_lines,
+    std_str_mixed_eq_literal,
 );
 
 criterion_main!(compact_str, std_string);
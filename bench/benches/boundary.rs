@@ -0,0 +1,207 @@
+//! Benchmarks that stress the inline/heap boundary of `CompactString`, i.e. strings of 23, 24,
+//! and 25 bytes on 64-bit platforms, so regressions from changes to `Repr` show up here first.
+//!
+//! Before any benchmarks run we also assert how many allocations each operation makes, using a
+//! counting global allocator, so an accidental heap allocation at the boundary fails loudly.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use compact_str::CompactString;
+use criterion::{black_box, criterion_group, BenchmarkId, Criterion};
+
+struct CountingAlloc;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+/// Returns the number of allocations (and reallocations) made while running `f`.
+fn count_allocations<R>(f: impl FnOnce() -> R) -> usize {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    black_box(f());
+    ALLOCATIONS.load(Ordering::Relaxed) - before
+}
+
+const MAX_INLINE: usize = std::mem::size_of::<String>();
+
+/// Strings one byte short of, exactly at, and one byte past the inline capacity.
+fn boundary_strings() -> [(usize, String); 3] {
+    [MAX_INLINE - 1, MAX_INLINE, MAX_INLINE + 1].map(|len| (len, "a".repeat(len)))
+}
+
+fn expected_allocations(len: usize) -> usize {
+    if len <= MAX_INLINE {
+        0
+    } else {
+        1
+    }
+}
+
+fn assert_allocation_counts() {
+    for (len, word) in boundary_strings() {
+        let expected = expected_allocations(len);
+
+        let allocs = count_allocations(|| CompactString::new(&word));
+        assert_eq!(allocs, expected, "CompactString::new, {len} bytes");
+
+        let compact = CompactString::new(&word);
+        let allocs = count_allocations(|| compact.clone());
+        assert_eq!(allocs, expected, "CompactString::clone, {len} bytes");
+
+        let other = compact.clone();
+        assert_eq!(count_allocations(|| compact == other), 0, "eq, {len} bytes");
+        assert_eq!(
+            count_allocations(|| {
+                let mut hasher = DefaultHasher::new();
+                compact.hash(&mut hasher);
+                hasher.finish()
+            }),
+            0,
+            "hash, {len} bytes",
+        );
+
+        // growing one byte at a time from empty, we should only allocate once we cross over
+        let allocs = count_allocations(|| {
+            let mut s = CompactString::default();
+            for _ in 0..len {
+                s.push_str("a");
+            }
+            s
+        });
+        assert_eq!(allocs, expected, "push_str, {len} bytes");
+    }
+}
+
+fn bench_new(c: &mut Criterion) {
+    let mut group = c.benchmark_group("boundary/new");
+    for (len, word) in boundary_strings() {
+        group.bench_with_input(BenchmarkId::from_parameter(len), word.as_str(), |b, word| {
+            b.iter(|| CompactString::new(word))
+        });
+    }
+    group.finish();
+}
+
+fn bench_clone(c: &mut Criterion) {
+    let mut group = c.benchmark_group("boundary/clone");
+    for (len, word) in boundary_strings() {
+        let compact = CompactString::new(word);
+        group.bench_with_input(BenchmarkId::from_parameter(len), &compact, |b, compact| {
+            b.iter(|| compact.clone())
+        });
+    }
+    group.finish();
+}
+
+fn bench_push_str(c: &mut Criterion) {
+    let mut group = c.benchmark_group("boundary/push_str");
+    for (len, _) in boundary_strings() {
+        // push a single byte onto a string that is one byte short of `len`
+        let base = CompactString::new("a".repeat(len - 1));
+        group.bench_with_input(BenchmarkId::from_parameter(len), &base, |b, base| {
+            b.iter(|| {
+                let mut s = base.clone();
+                s.push_str(black_box("a"));
+                s
+            })
+        });
+    }
+    group.finish();
+}
+
+fn bench_eq(c: &mut Criterion) {
+    let mut group = c.benchmark_group("boundary/eq");
+    for (len, word) in boundary_strings() {
+        let a = CompactString::new(&word);
+        let b = CompactString::new(&word);
+        group.bench_with_input(BenchmarkId::from_parameter(len), &(a, b), |bench, (a, b)| {
+            bench.iter(|| black_box(a) == black_box(b))
+        });
+    }
+    group.finish();
+}
+
+fn bench_hash(c: &mut Criterion) {
+    let mut group = c.benchmark_group("boundary/hash");
+    for (len, word) in boundary_strings() {
+        let compact = CompactString::new(word);
+        group.bench_with_input(BenchmarkId::from_parameter(len), &compact, |b, compact| {
+            b.iter(|| {
+                let mut hasher = DefaultHasher::new();
+                compact.hash(&mut hasher);
+                hasher.finish()
+            })
+        });
+    }
+    group.finish();
+}
+
+fn bench_heap_growth(c: &mut Criterion) {
+    let mut group = c.benchmark_group("boundary/heap_growth");
+    for total in [64, 1024, 16 * 1024] {
+        group.bench_with_input(
+            BenchmarkId::new("push_str 8 bytes", total),
+            &total,
+            |b, &total| {
+                b.iter(|| {
+                    let mut s = CompactString::default();
+                    while s.len() < total {
+                        s.push_str(black_box("abcdefgh"));
+                    }
+                    s
+                })
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("push_str 8 bytes, reserved", total),
+            &total,
+            |b, &total| {
+                b.iter(|| {
+                    let mut s = CompactString::with_capacity(total);
+                    while s.len() < total {
+                        s.push_str(black_box("abcdefgh"));
+                    }
+                    s
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(
+    boundary,
+    bench_new,
+    bench_clone,
+    bench_push_str,
+    bench_eq,
+    bench_hash,
+    bench_heap_growth,
+);
+
+fn main() {
+    assert_allocation_counts();
+
+    boundary();
+    Criterion::default().configure_from_args().final_summary();
+}
@@ -0,0 +1,387 @@
+//! A column of strings stored back to back in a single buffer, for holding very many short
+//! strings without paying for a 24 byte slot per string.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::iter::FusedIterator;
+use core::ops::Index;
+use core::{fmt, mem};
+
+use crate::{CompactStrRef, CompactString};
+
+/// A growable sequence of strings, stored contiguously in one buffer alongside their offsets.
+///
+/// Every string costs its length in bytes plus a 4 byte offset, compared to at least 24 bytes for
+/// a [`CompactString`], so this is well suited for storing many short strings, e.g. a column of a
+/// table. Strings are borrowed as `&str` on access and can be copied out into a
+/// [`CompactString`] with [`CompactStringColumn::to_compact_string`].
+///
+/// The total number of bytes stored in a column is limited to [`u32::MAX`].
+///
+/// # Examples
+/// ```
+/// use compact_str::CompactStringColumn;
+///
+/// let mut column = CompactStringColumn::new();
+/// column.push("apple");
+/// column.push("banana");
+/// column.push("");
+///
+/// assert_eq!(column.len(), 3);
+/// assert_eq!(&column[1], "banana");
+/// assert_eq!(column.iter().collect::<Vec<_>>(), ["apple", "banana", ""]);
+/// ```
+#[derive(Clone, Default, PartialEq, Eq, Hash)]
+pub struct CompactStringColumn {
+    /// The contents of every string, back to back.
+    bytes: String,
+    /// The offset into `bytes` where each string ends, the start is the end of the previous one.
+    ends: Vec<u32>,
+}
+
+impl CompactStringColumn {
+    /// Creates a new, empty column.
+    #[inline]
+    pub const fn new() -> Self {
+        CompactStringColumn {
+            bytes: String::new(),
+            ends: Vec::new(),
+        }
+    }
+
+    /// Creates a new, empty column with space for at least `strings` strings totalling `bytes`
+    /// bytes.
+    #[inline]
+    pub fn with_capacity(strings: usize, bytes: usize) -> Self {
+        CompactStringColumn {
+            bytes: String::with_capacity(bytes),
+            ends: Vec::with_capacity(strings),
+        }
+    }
+
+    /// Appends a string onto the end of the column.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the total length of the column would exceed [`u32::MAX`] bytes.
+    pub fn push(&mut self, s: &str) {
+        let end = self
+            .bytes
+            .len()
+            .checked_add(s.len())
+            .and_then(|end| u32::try_from(end).ok())
+            .expect("CompactStringColumn is too large");
+
+        self.bytes.push_str(s);
+        self.ends.push(end);
+    }
+
+    /// Returns the number of strings in the column.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.ends.len()
+    }
+
+    /// Returns `true` if the column contains no strings.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.ends.is_empty()
+    }
+
+    /// Returns the total length of all the strings in the column, in bytes.
+    #[inline]
+    pub fn bytes_len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// Returns the string at `index`, or `None` if it's out of bounds.
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<&str> {
+        let end = *self.ends.get(index)? as usize;
+        let start = match index.checked_sub(1) {
+            Some(prev) => self.ends[prev] as usize,
+            None => 0,
+        };
+        Some(&self.bytes[start..end])
+    }
+
+    /// Copies the string at `index` into a [`CompactString`], or returns `None` if it's out of
+    /// bounds. Short strings get inlined.
+    #[inline]
+    pub fn to_compact_string(&self, index: usize) -> Option<CompactString> {
+        self.get(index).map(CompactString::new)
+    }
+
+    /// Returns an iterator over the strings in the column.
+    #[inline]
+    pub fn iter(&self) -> CompactStringColumnIter<'_> {
+        CompactStringColumnIter {
+            bytes: &self.bytes,
+            ends: &self.ends,
+            start: 0,
+        }
+    }
+
+    /// Shortens the column, keeping the first `len` strings and dropping the rest.
+    ///
+    /// If `len` is greater than the current length of the column, this has no effect.
+    pub fn truncate(&mut self, len: usize) {
+        if len >= self.len() {
+            return;
+        }
+        let end = match len.checked_sub(1) {
+            Some(last) => self.ends[last] as usize,
+            None => 0,
+        };
+        self.ends.truncate(len);
+        self.bytes.truncate(end);
+    }
+
+    /// Removes all of the strings from the column, keeping the allocated memory.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.ends.clear();
+        self.bytes.clear();
+    }
+
+    /// Shrinks the memory used by the column as much as possible.
+    pub fn shrink_to_fit(&mut self) {
+        self.ends.shrink_to_fit();
+        self.bytes.shrink_to_fit();
+    }
+
+    /// Returns the number of bytes of heap memory used by the column, including unused capacity.
+    #[inline]
+    pub fn heap_size(&self) -> usize {
+        self.bytes.capacity() + self.ends.capacity() * mem::size_of::<u32>()
+    }
+
+    /// Encodes a snapshot of the column in the layout of a [`StrTable`](crate::StrTable), which
+    /// can be written out, e.g. to a file, and later viewed without copying.
+    ///
+    /// # Examples
+    /// ```
+    /// use compact_str::{CompactStringColumn, StrTable};
+    ///
+    /// let column: CompactStringColumn = ["apple", "banana"].into_iter().collect();
+    /// let snapshot = column.snapshot();
+    ///
+    /// let fruits: Vec<&str> = StrTable::new(&snapshot)
+    ///     .iter()
+    ///     .map(|s| s.unwrap().as_str())
+    ///     .collect();
+    /// assert_eq!(fruits, ["apple", "banana"]);
+    /// ```
+    pub fn snapshot(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(self.bytes.len() + self.len() * mem::size_of::<u32>());
+        for s in self {
+            CompactStrRef::write(s, &mut out);
+        }
+        out
+    }
+}
+
+impl Index<usize> for CompactStringColumn {
+    type Output = str;
+
+    #[inline]
+    #[track_caller]
+    fn index(&self, index: usize) -> &str {
+        match self.get(index) {
+            Some(s) => s,
+            None => panic!(
+                "index out of bounds: the len is {} but the index is {}",
+                self.len(),
+                index
+            ),
+        }
+    }
+}
+
+impl fmt::Debug for CompactStringColumn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<S: AsRef<str>> Extend<S> for CompactStringColumn {
+    fn extend<T: IntoIterator<Item = S>>(&mut self, iter: T) {
+        let iter = iter.into_iter();
+        self.ends.reserve(iter.size_hint().0);
+        for s in iter {
+            self.push(s.as_ref());
+        }
+    }
+}
+
+impl<S: AsRef<str>> FromIterator<S> for CompactStringColumn {
+    fn from_iter<T: IntoIterator<Item = S>>(iter: T) -> Self {
+        let mut column = CompactStringColumn::new();
+        column.extend(iter);
+        column
+    }
+}
+
+impl<'a> IntoIterator for &'a CompactStringColumn {
+    type Item = &'a str;
+    type IntoIter = CompactStringColumnIter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the strings in a [`CompactStringColumn`], created by
+/// [`CompactStringColumn::iter()`].
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct CompactStringColumnIter<'a> {
+    bytes: &'a str,
+    /// The end offsets of the strings we have yet to yield.
+    ends: &'a [u32],
+    /// The start offset of the next string from the front.
+    start: usize,
+}
+
+impl<'a> Iterator for CompactStringColumnIter<'a> {
+    type Item = &'a str;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a str> {
+        let (&end, rest) = self.ends.split_first()?;
+        let end = end as usize;
+        let s = &self.bytes[self.start..end];
+        self.ends = rest;
+        self.start = end;
+        Some(s)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.ends.len(), Some(self.ends.len()))
+    }
+}
+
+impl<'a> DoubleEndedIterator for CompactStringColumnIter<'a> {
+    #[inline]
+    fn next_back(&mut self) -> Option<&'a str> {
+        let (&end, rest) = self.ends.split_last()?;
+        let start = rest.last().map_or(self.start, |&prev| prev as usize);
+        self.ends = rest;
+        Some(&self.bytes[start..end as usize])
+    }
+}
+
+impl ExactSizeIterator for CompactStringColumnIter<'_> {}
+
+impl FusedIterator for CompactStringColumnIter<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::String;
+    use alloc::vec::Vec;
+
+    use test_strategy::proptest;
+
+    use super::CompactStringColumn;
+    use crate::StrTable;
+
+    #[test]
+    fn test_push_get() {
+        let long = "this is a long string that would be on the heap";
+        let mut column = CompactStringColumn::new();
+        assert!(column.is_empty());
+        assert_eq!(column.get(0), None);
+
+        column.push("hello");
+        column.push("");
+        column.push(long);
+        column.push("🦀");
+
+        assert_eq!(column.len(), 4);
+        assert_eq!(column.bytes_len(), 5 + long.len() + 4);
+        assert_eq!(column.get(0), Some("hello"));
+        assert_eq!(column.get(1), Some(""));
+        assert_eq!(&column[2], long);
+        assert_eq!(&column[3], "🦀");
+        assert_eq!(column.get(4), None);
+
+        let compact = column.to_compact_string(0).unwrap();
+        assert_eq!(compact, "hello");
+        assert!(!compact.is_heap_allocated());
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds")]
+    fn test_index_out_of_bounds() {
+        let column: CompactStringColumn = ["a"].into_iter().collect();
+        let _ = &column[1];
+    }
+
+    #[test]
+    fn test_iter() {
+        let column: CompactStringColumn = ["a", "bc", "", "def"].into_iter().collect();
+
+        let mut iter = column.iter();
+        assert_eq!(iter.len(), 4);
+        assert_eq!(iter.next(), Some("a"));
+        assert_eq!(iter.next_back(), Some("def"));
+        assert_eq!(iter.next_back(), Some(""));
+        assert_eq!(iter.len(), 1);
+        assert_eq!(iter.next(), Some("bc"));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+
+        let rev: Vec<&str> = column.iter().rev().collect();
+        assert_eq!(rev, ["def", "", "bc", "a"]);
+        assert_eq!(format!("{:?}", column), r#"["a", "bc", "", "def"]"#);
+    }
+
+    #[test]
+    fn test_truncate_clear() {
+        let mut column: CompactStringColumn = ["a", "bc", "def"].into_iter().collect();
+        column.truncate(5);
+        assert_eq!(column.len(), 3);
+
+        column.truncate(2);
+        assert_eq!(column.iter().collect::<Vec<_>>(), ["a", "bc"]);
+        assert_eq!(column.bytes_len(), 3);
+
+        column.push("g");
+        assert_eq!(column.iter().collect::<Vec<_>>(), ["a", "bc", "g"]);
+
+        column.truncate(0);
+        assert!(column.is_empty());
+        assert_eq!(column.bytes_len(), 0);
+
+        column.extend(["x", "y"]);
+        column.clear();
+        assert!(column.is_empty());
+        assert!(column.heap_size() > 0);
+        column.shrink_to_fit();
+        assert_eq!(column.heap_size(), 0);
+    }
+
+    #[test]
+    fn test_snapshot() {
+        let column: CompactStringColumn = ["apple", "", "cherry"].into_iter().collect();
+        let snapshot = column.snapshot();
+        assert_eq!(snapshot, StrTable::encode(["apple", "", "cherry"]));
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[proptest]
+    fn proptest_roundtrip(strings: Vec<String>) {
+        let column: CompactStringColumn = strings.iter().collect();
+        assert_eq!(column.len(), strings.len());
+
+        let collected: Vec<&str> = column.iter().collect();
+        assert_eq!(collected, strings);
+        let reversed: Vec<&str> = column.iter().rev().collect();
+        assert!(reversed.iter().eq(strings.iter().rev()));
+        for (i, s) in strings.iter().enumerate() {
+            assert_eq!(column.get(i), Some(s.as_str()));
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use arcstr::ArcStr;
+
+use crate::CompactString;
+
+/// Converts an [`ArcStr`] into a [`CompactString`].
+///
+/// Short strings get inlined, and literals created with [`arcstr::literal!`] are stored as a
+/// `&'static str`, neither of which allocates. Any other string gets copied into a new heap
+/// allocation, since the buffer is reference counted and can't be taken over.
+#[cfg_attr(docsrs, doc(cfg(feature = "arcstr")))]
+impl From<ArcStr> for CompactString {
+    #[inline]
+    fn from(s: ArcStr) -> Self {
+        CompactString::from(&s)
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "arcstr")))]
+impl From<&ArcStr> for CompactString {
+    #[inline]
+    fn from(s: &ArcStr) -> Self {
+        match ArcStr::as_static(s) {
+            Some(s) => CompactString::const_new(s),
+            None => CompactString::new(s.as_str()),
+        }
+    }
+}
+
+/// Converts a [`CompactString`] into an [`ArcStr`].
+///
+/// [`ArcStr`] stores its reference count inline with the string data, so this always copies into
+/// a new allocation, except for empty strings.
+#[cfg_attr(docsrs, doc(cfg(feature = "arcstr")))]
+impl From<CompactString> for ArcStr {
+    #[inline]
+    fn from(s: CompactString) -> Self {
+        ArcStr::from(s.as_str())
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "arcstr")))]
+impl From<&CompactString> for ArcStr {
+    #[inline]
+    fn from(s: &CompactString) -> Self {
+        ArcStr::from(s.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use arcstr::ArcStr;
+
+    use crate::CompactString;
+
+    #[test]
+    fn test_arcstr_roundtrip() {
+        let short = ArcStr::from("hello");
+        let compact = CompactString::from(&short);
+        assert_eq!(compact, "hello");
+        assert!(!compact.is_heap_allocated());
+        assert_eq!(ArcStr::from(compact), short);
+
+        let long = ArcStr::from("I am a very long string that will get allocated on the heap");
+        let compact = CompactString::from(long.clone());
+        assert_eq!(compact, long.as_str());
+        assert!(compact.is_heap_allocated());
+        assert_eq!(ArcStr::from(&compact), long);
+    }
+
+    #[test]
+    fn test_arcstr_literal_is_static() {
+        const TEXT: &str = "a string literal that is too long to be inlined";
+        let literal = arcstr::literal!("a string literal that is too long to be inlined");
+
+        let compact = CompactString::from(literal);
+        assert_eq!(compact.as_static_str(), Some(TEXT));
+        assert!(!compact.is_heap_allocated());
+    }
+}
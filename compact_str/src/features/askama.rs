@@ -0,0 +1,44 @@
+#[cfg(test)]
+use alloc::string::String;
+
+use askama::{FastWritable, Values};
+
+use crate::CompactString;
+
+#[cfg_attr(docsrs, doc(cfg(feature = "askama")))]
+impl FastWritable for CompactString {
+    #[inline]
+    fn write_into<W: core::fmt::Write + ?Sized>(
+        &self,
+        dest: &mut W,
+        values: &dyn Values,
+    ) -> askama::Result<()> {
+        self.as_str().write_into(dest, values)
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_askama() {
+    use askama::Template;
+
+    const TEXT: &str = "<script>alert('Hello, world!')</script>";
+
+    #[derive(Template)]
+    #[template(source = "<textarea>{{ msg }}</textarea>", ext = "html")]
+    struct Page<M: core::fmt::Display + FastWritable> {
+        msg: M,
+    }
+
+    let compact = Page {
+        msg: CompactString::from(TEXT),
+    };
+    let control = Page {
+        msg: String::from(TEXT),
+    };
+    assert_eq!(compact.render().unwrap(), control.render().unwrap());
+    assert_eq!(
+        compact.render().unwrap(),
+        "<textarea>&#60;script&#62;alert(&#39;Hello, world!&#39;)&#60;/script&#62;</textarea>",
+    );
+}
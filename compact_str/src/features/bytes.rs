@@ -58,8 +58,16 @@ impl CompactString {
     /// assert_eq!(compact_str, word);
     /// ```
     #[cfg_attr(docsrs, doc(cfg(feature = "bytes")))]
+    #[cfg_attr(all(feature = "debug-validate", debug_assertions), track_caller)]
     pub unsafe fn from_utf8_buf_unchecked<B: Buf>(buf: &mut B) -> Self {
         let repr = Repr::from_utf8_buf_unchecked(buf);
+
+        #[cfg(all(feature = "debug-validate", debug_assertions))]
+        crate::features::debug_validate::validate_utf8(
+            repr.as_slice(),
+            "after calling `from_utf8_buf_unchecked`",
+        );
+
         CompactString(repr)
     }
 }
@@ -0,0 +1,73 @@
+use alloc::string::String;
+
+use camino::{Utf8Path, Utf8PathBuf};
+
+use crate::CompactString;
+
+/// Converts a [`Utf8Path`] into a [`CompactString`], without going through an
+/// [`OsStr`](std::ffi::OsStr). Short paths get inlined.
+#[cfg_attr(docsrs, doc(cfg(feature = "camino")))]
+impl From<&Utf8Path> for CompactString {
+    #[inline]
+    fn from(path: &Utf8Path) -> Self {
+        CompactString::new(path.as_str())
+    }
+}
+
+/// Converts a [`Utf8PathBuf`] into a [`CompactString`]. Like `From<String>`, short paths get
+/// inlined and longer paths re-use the existing buffer without copying.
+#[cfg_attr(docsrs, doc(cfg(feature = "camino")))]
+impl From<Utf8PathBuf> for CompactString {
+    #[inline]
+    fn from(path: Utf8PathBuf) -> Self {
+        CompactString::from(path.into_string())
+    }
+}
+
+/// Converts a [`CompactString`] into a [`Utf8PathBuf`]. Like `From<CompactString> for String`,
+/// heap allocated strings hand over their buffer without copying.
+#[cfg_attr(docsrs, doc(cfg(feature = "camino")))]
+impl From<CompactString> for Utf8PathBuf {
+    #[inline]
+    fn from(s: CompactString) -> Self {
+        Utf8PathBuf::from(String::from(s))
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "camino")))]
+impl AsRef<Utf8Path> for CompactString {
+    #[inline]
+    fn as_ref(&self) -> &Utf8Path {
+        Utf8Path::new(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use camino::{Utf8Path, Utf8PathBuf};
+
+    use crate::CompactString;
+
+    #[test]
+    fn test_utf8_path_roundtrip() {
+        let short = Utf8Path::new("src/lib.rs");
+        let compact = CompactString::from(short);
+        assert_eq!(compact, "src/lib.rs");
+        assert!(!compact.is_heap_allocated());
+
+        let path: &Utf8Path = compact.as_ref();
+        assert_eq!(path.file_name(), Some("lib.rs"));
+        assert_eq!(Utf8PathBuf::from(compact), short);
+
+        let long = Utf8PathBuf::from("/home/user/projects/compact_str/src/features/camino.rs");
+        let ptr = long.as_str().as_ptr();
+        let compact = CompactString::from(long);
+        assert!(compact.is_heap_allocated());
+        assert_eq!(compact.as_ptr(), ptr);
+
+        // the buffer gets handed back, too
+        let path = Utf8PathBuf::from(compact);
+        assert_eq!(path.as_str().as_ptr(), ptr);
+        assert_eq!(path.extension(), Some("rs"));
+    }
+}
@@ -0,0 +1,123 @@
+//! Implements parsing [`CompactString`]s from command line arguments with [`clap`]
+
+use std::ffi::{OsStr, OsString};
+
+use clap::builder::{TypedValueParser, ValueParserFactory};
+use clap::error::ErrorKind;
+use clap::{Arg, Command, Error};
+
+use crate::CompactString;
+
+/// A [`TypedValueParser`] that parses UTF-8 arguments into [`CompactString`]s
+///
+/// This is the parser [`clap`] picks for a `CompactString` field by default, so
+/// `#[arg] name: CompactString` works in derive mode without a `value_parser` attribute. Short
+/// arguments are inlined, and owned arguments re-use their buffer, without an intermediate
+/// [`String`](std::string::String).
+///
+/// # Example
+/// ```
+/// use clap::Parser;
+/// use compact_str::CompactString;
+///
+/// #[derive(Parser)]
+/// struct Args {
+///     #[arg(long)]
+///     name: CompactString,
+///     #[arg(long)]
+///     tags: Vec<CompactString>,
+/// }
+///
+/// let args = Args::try_parse_from(["app", "--name", "ferris", "--tags", "a", "--tags", "b"]).unwrap();
+///
+/// assert_eq!(args.name, "ferris");
+/// assert!(!args.name.is_heap_allocated());
+/// assert_eq!(args.tags, ["a", "b"]);
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "clap")))]
+#[derive(Copy, Clone, Debug, Default)]
+#[non_exhaustive]
+pub struct CompactStringValueParser;
+
+impl CompactStringValueParser {
+    /// Creates a new [`CompactStringValueParser`]
+    pub fn new() -> Self {
+        CompactStringValueParser
+    }
+}
+
+impl TypedValueParser for CompactStringValueParser {
+    type Value = CompactString;
+
+    fn parse_ref(
+        &self,
+        cmd: &Command,
+        _arg: Option<&Arg>,
+        value: &OsStr,
+    ) -> Result<Self::Value, Error> {
+        CompactString::try_from(value).map_err(|_| invalid_utf8(cmd))
+    }
+
+    fn parse(
+        &self,
+        cmd: &Command,
+        _arg: Option<&Arg>,
+        value: OsString,
+    ) -> Result<Self::Value, Error> {
+        CompactString::try_from(value).map_err(|_| invalid_utf8(cmd))
+    }
+}
+
+#[cold]
+fn invalid_utf8(cmd: &Command) -> Error {
+    Error::new(ErrorKind::InvalidUtf8).with_cmd(cmd)
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "clap")))]
+impl ValueParserFactory for CompactString {
+    type Parser = CompactStringValueParser;
+
+    fn value_parser() -> Self::Parser {
+        CompactStringValueParser
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::error::ErrorKind;
+    use clap::{value_parser, Arg, Command};
+
+    use crate::CompactString;
+
+    fn command() -> Command {
+        Command::new("test").arg(
+            Arg::new("name")
+                .long("name")
+                .value_parser(value_parser!(CompactString)),
+        )
+    }
+
+    #[test]
+    fn test_value_parser() {
+        let long = "this is a long string that will be on the heap";
+        for name in ["", "ferris", long] {
+            let matches = command()
+                .try_get_matches_from(["test", "--name", name])
+                .unwrap();
+            assert_eq!(matches.get_one::<CompactString>("name").unwrap(), name);
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_value_parser_invalid_utf8() {
+        use std::ffi::OsString;
+        use std::os::unix::ffi::OsStringExt;
+
+        let invalid = OsString::from_vec(b"\xF0\x9F".to_vec());
+        let err = command()
+            .try_get_matches_from([OsString::from("test"), "--name".into(), invalid])
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidUtf8);
+    }
+}
@@ -0,0 +1,151 @@
+//! Tracks clones of heap allocated [`CompactString`](crate::CompactString)s, to find accidental
+//! deep copies of large strings during a performance audit. Only active in debug builds, in
+//! release builds cloning is unchanged and [`heap_clones`] always returns `0`.
+//!
+//! Every clone of a heap allocated string that is at least `COMPACT_STR_CLONE_THRESHOLD` bytes
+//! long (default `0`) gets counted. What else happens is picked with `COMPACT_STR_CLONE_ACTION`:
+//!
+//! * `count`, the default, only counts the clone.
+//! * `log` also prints the length of the string and where it was cloned to stderr.
+//! * `panic` panics, so the offending clone shows up with a backtrace.
+//!
+//! Both environment variables are read once, the first time a heap allocated string is cloned.
+//! Inline strings and `&'static str`s are copied without allocating, so they're never tracked.
+//!
+//! # Examples
+//! ```
+//! use compact_str::CompactString;
+//!
+//! let long = CompactString::new("a string that is too long to be stored inline");
+//! let before = compact_str::clone_tracking::heap_clones();
+//!
+//! let _copy = long.clone();
+//! # #[cfg(debug_assertions)]
+//! assert!(compact_str::clone_tracking::heap_clones() > before);
+//! ```
+
+use core::panic::Location;
+use core::sync::atomic::{AtomicU8, AtomicUsize, Ordering};
+use std::sync::Once;
+
+static HEAP_CLONES: AtomicUsize = AtomicUsize::new(0);
+
+static CONFIG: Once = Once::new();
+static THRESHOLD: AtomicUsize = AtomicUsize::new(0);
+static ACTION: AtomicU8 = AtomicU8::new(Action::Count as u8);
+
+/// Returns how many heap allocated strings at or above the threshold have been cloned so far.
+#[cfg_attr(docsrs, doc(cfg(feature = "clone-tracking")))]
+pub fn heap_clones() -> usize {
+    HEAP_CLONES.load(Ordering::Relaxed)
+}
+
+/// What to do when a heap allocated string at or above the threshold gets cloned
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+enum Action {
+    Count,
+    Log,
+    Panic,
+}
+
+impl Action {
+    fn parse(s: &str) -> Option<Action> {
+        match s.trim() {
+            "count" => Some(Action::Count),
+            "log" => Some(Action::Log),
+            "panic" => Some(Action::Panic),
+            _ => None,
+        }
+    }
+
+    fn from_u8(v: u8) -> Action {
+        match v {
+            1 => Action::Log,
+            2 => Action::Panic,
+            _ => Action::Count,
+        }
+    }
+}
+
+fn read_config() {
+    if let Some(threshold) = std::env::var("COMPACT_STR_CLONE_THRESHOLD")
+        .ok()
+        .and_then(|v| v.trim().parse().ok())
+    {
+        THRESHOLD.store(threshold, Ordering::Relaxed);
+    }
+    if let Some(action) = std::env::var("COMPACT_STR_CLONE_ACTION")
+        .ok()
+        .and_then(|v| Action::parse(&v))
+    {
+        ACTION.store(action as u8, Ordering::Relaxed);
+    }
+}
+
+/// Records that a heap allocated string of `len` bytes is being cloned.
+#[track_caller]
+pub(crate) fn track_heap_clone(len: usize) {
+    CONFIG.call_once(read_config);
+    let threshold = THRESHOLD.load(Ordering::Relaxed);
+    let action = Action::from_u8(ACTION.load(Ordering::Relaxed));
+    record(len, threshold, action);
+}
+
+#[track_caller]
+fn record(len: usize, threshold: usize, action: Action) {
+    if len < threshold {
+        return;
+    }
+    HEAP_CLONES.fetch_add(1, Ordering::Relaxed);
+
+    match action {
+        Action::Count => (),
+        Action::Log => std::eprintln!(
+            "compact_str: cloned a heap allocated CompactString of {} bytes at {}",
+            len,
+            Location::caller()
+        ),
+        Action::Panic => panic!(
+            "cloned a heap allocated CompactString of {} bytes at {}, which is at least the \
+             COMPACT_STR_CLONE_THRESHOLD of {} bytes",
+            len,
+            Location::caller(),
+            threshold
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{heap_clones, record, Action};
+
+    #[test]
+    fn test_parse_action() {
+        assert_eq!(Action::parse("count"), Some(Action::Count));
+        assert_eq!(Action::parse(" log\n"), Some(Action::Log));
+        assert_eq!(Action::parse("panic"), Some(Action::Panic));
+        assert_eq!(Action::parse("explode"), None);
+
+        for action in [Action::Count, Action::Log, Action::Panic] {
+            assert_eq!(Action::from_u8(action as u8), action);
+        }
+    }
+
+    #[test]
+    fn test_threshold() {
+        // other tests clone strings concurrently, so the count can only be checked for growth
+        let before = heap_clones();
+        record(64, 32, Action::Log);
+        assert!(heap_clones() > before);
+
+        // below the threshold nothing happens, not even a panic
+        record(16, 32, Action::Panic);
+    }
+
+    #[test]
+    #[should_panic(expected = "cloned a heap allocated CompactString of 64 bytes")]
+    fn test_panic() {
+        record(64, 32, Action::Panic);
+    }
+}
@@ -0,0 +1,159 @@
+//! Helpers for converting [`csv`] records into [`CompactString`]s.
+//!
+//! [`ByteRecord`] and [`StringRecord`] can already be built from an iterator of
+//! [`CompactString`]s, via their [`FromIterator`] impls. Going the other way, these helpers
+//! convert every field straight into a [`CompactString`], so short fields are inlined without an
+//! intermediate [`String`](alloc::string::String) per field.
+//!
+//! # Examples
+//! ```
+//! use compact_str::CompactString;
+//! use csv::ByteRecord;
+//!
+//! let record = ByteRecord::from(vec!["Boston", "United States", "4628910"]);
+//! let fields = compact_str::csv::from_byte_record(&record).unwrap();
+//! assert_eq!(fields, ["Boston", "United States", "4628910"]);
+//!
+//! // and back again
+//! let roundtrip: ByteRecord = fields.iter().collect();
+//! assert_eq!(roundtrip, record);
+//! ```
+
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::Utf8Error;
+
+use csv::{ByteRecord, StringRecord};
+
+use crate::CompactString;
+
+/// Converts every field of a [`ByteRecord`] into a [`CompactString`], validating that each one
+/// is UTF-8.
+///
+/// # Errors
+///
+/// Returns a [`FieldUtf8Error`] for the first field that is not valid UTF-8.
+pub fn from_byte_record(record: &ByteRecord) -> Result<Vec<CompactString>, FieldUtf8Error> {
+    record
+        .iter()
+        .enumerate()
+        .map(|(field, bytes)| {
+            core::str::from_utf8(bytes)
+                .map(CompactString::new)
+                .map_err(|error| FieldUtf8Error { field, error })
+        })
+        .collect()
+}
+
+/// Converts every field of a [`StringRecord`] into a [`CompactString`].
+pub fn from_string_record(record: &StringRecord) -> Vec<CompactString> {
+    record.iter().map(CompactString::new).collect()
+}
+
+/// The error returned by [`from_byte_record`] when a field is not valid UTF-8.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FieldUtf8Error {
+    field: usize,
+    error: Utf8Error,
+}
+
+impl FieldUtf8Error {
+    /// Returns the index of the field that failed to validate.
+    #[inline]
+    pub fn field(&self) -> usize {
+        self.field
+    }
+
+    /// Returns the underlying UTF-8 error, positions are relative to the start of the field.
+    #[inline]
+    pub fn utf8_error(&self) -> &Utf8Error {
+        &self.error
+    }
+}
+
+impl fmt::Display for FieldUtf8Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid UTF-8 in field {}: {}", self.field, self.error)
+    }
+}
+
+impl std::error::Error for FieldUtf8Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::{String, ToString};
+    use alloc::vec::Vec;
+
+    use csv::{ByteRecord, StringRecord};
+    use test_strategy::proptest;
+
+    use super::{from_byte_record, from_string_record};
+    use crate::CompactString;
+
+    #[test]
+    fn test_from_records() {
+        let long = "this is a long field that will be on the heap";
+        let record = StringRecord::from(vec!["a", "", long]);
+
+        let fields = from_string_record(&record);
+        assert_eq!(fields, ["a", "", long]);
+        assert!(!fields[0].is_heap_allocated());
+        assert!(fields[2].is_heap_allocated());
+
+        let fields = from_byte_record(record.as_byte_record()).unwrap();
+        assert_eq!(fields, ["a", "", long]);
+
+        let roundtrip: StringRecord = fields.iter().collect();
+        assert_eq!(roundtrip, record);
+    }
+
+    #[test]
+    fn test_invalid_utf8() {
+        let mut record = ByteRecord::new();
+        record.push_field(b"ok");
+        record.push_field(b"also ok");
+        record.push_field(b"bad \xFF");
+
+        let err = from_byte_record(&record).unwrap_err();
+        assert_eq!(err.field(), 2);
+        assert_eq!(err.utf8_error().valid_up_to(), 4);
+        assert_eq!(
+            err.to_string(),
+            "invalid UTF-8 in field 2: invalid utf-8 sequence of 1 bytes from index 4"
+        );
+    }
+
+    #[test]
+    fn test_reader() {
+        let data = "city,country\nBoston,United States\nParis,France\n";
+        let mut reader = csv::Reader::from_reader(data.as_bytes());
+
+        let mut record = ByteRecord::new();
+        let mut rows = Vec::new();
+        while reader.read_byte_record(&mut record).unwrap() {
+            rows.push(from_byte_record(&record).unwrap());
+        }
+        assert_eq!(rows, [["Boston", "United States"], ["Paris", "France"]]);
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[proptest]
+    fn proptest_roundtrip(fields: Vec<String>) {
+        let record = StringRecord::from(fields.clone());
+        let compact = from_byte_record(record.as_byte_record()).unwrap();
+        assert_eq!(compact, fields);
+        assert_eq!(from_string_record(&record), fields);
+        assert_eq!(compact.iter().collect::<StringRecord>(), record);
+    }
+
+    #[test]
+    fn test_compact_string_is_csv_field() {
+        let fields = [CompactString::new("a"), CompactString::new("b")];
+        let record: ByteRecord = fields.iter().collect();
+        assert_eq!(record, ByteRecord::from(vec!["a", "b"]));
+    }
+}
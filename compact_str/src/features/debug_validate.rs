@@ -0,0 +1,135 @@
+//! Checks that a [`CompactString`](crate::CompactString) still holds valid UTF-8 after its
+//! `unsafe` methods have been used, so a violation panics where it happened instead of surfacing
+//! somewhere far away. Only active in debug builds.
+
+use core::fmt;
+
+/// How many bytes on either side of the invalid byte to include in the hexdump
+const CONTEXT: usize = 8;
+
+/// Panics if `bytes` are not valid UTF-8, with a hexdump of the bytes around the first invalid
+/// one. `context` describes when the check ran, e.g. "after calling `set_len`".
+#[track_caller]
+pub(crate) fn validate_utf8(bytes: &[u8], context: &str) {
+    if let Err(err) = core::str::from_utf8(bytes) {
+        invalid_utf8(bytes, err.valid_up_to(), context)
+    }
+}
+
+#[cold]
+#[inline(never)]
+#[track_caller]
+fn invalid_utf8(bytes: &[u8], idx: usize, context: &str) -> ! {
+    let start = idx.saturating_sub(CONTEXT);
+    let end = core::cmp::min(idx + CONTEXT, bytes.len());
+    let hexdump = Hexdump {
+        bytes: &bytes[start..end],
+        mark: idx - start,
+    };
+
+    panic!(
+        "CompactString contains invalid UTF-8 at byte {} {}: {}",
+        idx, context, hexdump
+    );
+}
+
+/// Formats bytes as hex, with the byte at `mark` in brackets, e.g. `68 69 [ff] 21`
+struct Hexdump<'a> {
+    bytes: &'a [u8],
+    mark: usize,
+}
+
+impl fmt::Display for Hexdump<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, byte) in self.bytes.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            if i == self.mark {
+                write!(f, "[{:02x}]", byte)?;
+            } else {
+                write!(f, "{:02x}", byte)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::Hexdump;
+    use crate::CompactString;
+
+    #[test]
+    fn test_hexdump() {
+        let hexdump = Hexdump {
+            bytes: b"hi\xFF!",
+            mark: 2,
+        };
+        assert_eq!(hexdump.to_string(), "68 69 [ff] 21");
+    }
+
+    #[test]
+    fn test_valid_writes() {
+        let mut s = CompactString::new("hello");
+        unsafe {
+            s.as_mut_bytes()[5..8].copy_from_slice("!!!".as_bytes());
+            s.set_len(8);
+            s.as_mut_bytes()[0] = b'j';
+        }
+        assert_eq!(s, "jello!!!");
+        assert_eq!(unsafe { CompactString::from_utf8_unchecked("🦀") }, "🦀");
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(
+        expected = "invalid UTF-8 at byte 5 after calling `set_len`: 68 65 6c 6c 6f [ff]"
+    )]
+    fn test_set_len() {
+        let mut s = CompactString::new("hello");
+        unsafe {
+            s.as_mut_bytes()[5] = 0xFF;
+            s.set_len(6);
+        }
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(
+        expected = "invalid UTF-8 at byte 1 when calling `as_mut_bytes`, left by an \
+                               earlier write through its buffer: 61 [f0] 62"
+    )]
+    fn test_as_mut_bytes() {
+        let mut s = CompactString::new("a🦀");
+        unsafe {
+            // an invalid write that's never followed by `set_len`, gets caught the next time we
+            // hand out the buffer
+            s.as_mut_bytes()[2] = b'b';
+            s.as_mut_bytes();
+        }
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(
+        expected = "invalid UTF-8 at byte 0 after writing through `with_mut_bytes`: [ff] 69"
+    )]
+    fn test_with_mut_bytes() {
+        let mut s = CompactString::new("hi");
+        unsafe {
+            // caught as soon as the closure returns
+            s.with_mut_bytes(|buf| buf[0] = 0xFF);
+        }
+        unreachable!("the invalid write should have been caught");
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "after calling `from_utf8_unchecked`")]
+    fn test_from_utf8_unchecked() {
+        let _ = unsafe { CompactString::from_utf8_unchecked(b"abc\xC3") };
+    }
+}
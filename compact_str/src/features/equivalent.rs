@@ -0,0 +1,165 @@
+//! Implements [`Equivalent`] between [`CompactString`] and other owned string types, so maps
+//! keyed by one can be queried with the other without allocating, e.g. looking up a
+//! `hashbrown::HashMap<Arc<str>, V>` with a `&CompactString`.
+//!
+//! [`Equivalent`] is the trait used for lookups by [`hashbrown`] (with its `equivalent` feature,
+//! enabled by default) and [`indexmap`].
+//!
+//! Looking up a map keyed by [`CompactString`] with a `&str` doesn't need this feature at all,
+//! [`CompactString`] implements [`Borrow<str>`](core::borrow::Borrow) so the blanket
+//! [`Equivalent`] impl already covers it:
+//!
+//! ```
+//! use compact_str::CompactString;
+//! use indexmap::IndexMap;
+//!
+//! let mut map: IndexMap<CompactString, u32> = IndexMap::new();
+//! map.insert(CompactString::const_new("apples"), 3);
+//!
+//! // no `CompactString` gets created for any of these lookups
+//! assert_eq!(map.get("apples"), Some(&3));
+//! assert_eq!(map.get_index_of("apples"), Some(0));
+//! *map.get_mut("apples").unwrap() += 1;
+//! assert_eq!(map.swap_remove("apples"), Some(4));
+//!
+//! // `entry` takes the key by value, so look up first and only create a key when inserting
+//! let key = "pears";
+//! match map.get_mut(key) {
+//!     Some(count) => *count += 1,
+//!     None => {
+//!         map.insert(CompactString::new(key), 1);
+//!     }
+//! }
+//! assert_eq!(map["pears"], 1);
+//! ```
+//!
+//! With this feature enabled, the same works for maps keyed by other owned string types:
+//!
+//! ```
+//! use std::sync::Arc;
+//!
+//! use compact_str::CompactString;
+//! use indexmap::IndexMap;
+//!
+//! let mut map: IndexMap<Arc<str>, u32> = IndexMap::new();
+//! map.insert(Arc::from("apples"), 3);
+//!
+//! let key = CompactString::const_new("apples");
+//! assert_eq!(map.get(&key), Some(&3));
+//! ```
+//!
+//! [`hashbrown`]: https://docs.rs/hashbrown
+//! [`indexmap`]: https://docs.rs/indexmap
+
+use alloc::boxed::Box;
+use alloc::rc::Rc;
+use alloc::string::String;
+#[cfg(target_has_atomic = "ptr")]
+use alloc::sync::Arc;
+
+use equivalent::Equivalent;
+
+use crate::CompactString;
+
+macro_rules! impl_equivalent {
+    ($($(#[$attr:meta])* $ty:ty),* $(,)?) => {
+        $(
+            $(#[$attr])*
+            #[cfg_attr(docsrs, doc(cfg(feature = "equivalent")))]
+            impl Equivalent<$ty> for CompactString {
+                #[inline]
+                fn equivalent(&self, key: &$ty) -> bool {
+                    self.as_str() == &**key
+                }
+            }
+
+            $(#[$attr])*
+            #[cfg_attr(docsrs, doc(cfg(feature = "equivalent")))]
+            impl Equivalent<CompactString> for $ty {
+                #[inline]
+                fn equivalent(&self, key: &CompactString) -> bool {
+                    &**self == key.as_str()
+                }
+            }
+        )*
+    };
+}
+
+impl_equivalent!(
+    String,
+    Box<str>,
+    Rc<str>,
+    #[cfg(target_has_atomic = "ptr")]
+    Arc<str>,
+);
+
+#[cfg(test)]
+mod tests {
+    use alloc::boxed::Box;
+    use alloc::rc::Rc;
+    use alloc::string::String;
+    use alloc::sync::Arc;
+
+    use hashbrown::HashMap;
+    use indexmap::IndexMap;
+
+    use crate::CompactString;
+
+    #[test]
+    fn test_hashbrown_lookup() {
+        for key in ["hello", "this is a long string that will be on the heap"] {
+            let compact = CompactString::new(key);
+
+            let mut by_arc: HashMap<Arc<str>, usize> = HashMap::new();
+            by_arc.insert(Arc::from(key), 1);
+            assert_eq!(by_arc.get(&compact), Some(&1));
+            assert_eq!(by_arc.get(&CompactString::new("missing")), None);
+
+            let mut by_rc: HashMap<Rc<str>, usize> = HashMap::new();
+            by_rc.insert(Rc::from(key), 2);
+            assert_eq!(by_rc.get(&compact), Some(&2));
+
+            let mut by_string: HashMap<String, usize> = HashMap::new();
+            by_string.insert(String::from(key), 3);
+            assert_eq!(by_string.get(&compact), Some(&3));
+
+            let mut by_compact: HashMap<CompactString, usize> = HashMap::new();
+            by_compact.insert(compact.clone(), 4);
+            assert_eq!(by_compact.get(&Arc::<str>::from(key)), Some(&4));
+            assert_eq!(by_compact.get(&Rc::<str>::from(key)), Some(&4));
+            assert_eq!(by_compact.get(&Box::<str>::from(key)), Some(&4));
+            assert_eq!(by_compact.get(&String::from(key)), Some(&4));
+            assert_eq!(by_compact.get(key), Some(&4));
+        }
+    }
+
+    #[test]
+    fn test_indexmap_lookup() {
+        for key in ["hello", "this is a long string that will be on the heap"] {
+            let compact = CompactString::new(key);
+
+            let mut by_arc: IndexMap<Arc<str>, usize> = IndexMap::new();
+            by_arc.insert(Arc::from(key), 1);
+            assert_eq!(by_arc.get(&compact), Some(&1));
+            assert_eq!(by_arc.get_index_of(&compact), Some(0));
+            assert_eq!(by_arc.get(&CompactString::new("missing")), None);
+
+            let mut by_string: IndexMap<String, usize> = IndexMap::new();
+            by_string.insert(String::from(key), 2);
+            assert_eq!(by_string.get(&compact), Some(&2));
+
+            let mut by_compact: IndexMap<CompactString, usize> = IndexMap::new();
+            by_compact.insert(compact.clone(), 3);
+            assert_eq!(by_compact.get(key), Some(&3));
+            assert_eq!(by_compact.get(&Arc::<str>::from(key)), Some(&3));
+            assert_eq!(by_compact.get(&Rc::<str>::from(key)), Some(&3));
+            assert_eq!(by_compact.get(&Box::<str>::from(key)), Some(&3));
+            assert_eq!(
+                by_compact.get_full(&String::from(key)),
+                Some((0, &compact, &3))
+            );
+            assert_eq!(by_compact.shift_remove(key), Some(3));
+            assert!(by_compact.is_empty());
+        }
+    }
+}
@@ -0,0 +1,100 @@
+//! A thread-local scratch buffer for formatting into a [`CompactString`](crate::CompactString).
+//!
+//! Formatting directly into a [`CompactString`](crate::CompactString) grows it as it's written,
+//! which for long outputs means moving from inline to the heap and reallocating a few times.
+//! Instead we render once into a buffer that's reused across calls, and then copy the result into
+//! a [`CompactString`](crate::CompactString) of exactly the right size.
+
+use core::cell::RefCell;
+use std::string::String;
+
+/// Don't hold onto scratch buffers larger than this, so one huge format doesn't pin its memory
+/// for the rest of the thread's lifetime
+const MAX_RETAINED_CAPACITY: usize = 4 * 1024;
+
+std::thread_local! {
+    static SCRATCH: RefCell<String> = const { RefCell::new(String::new()) };
+}
+
+/// Calls `f` with an empty thread-local scratch buffer.
+///
+/// Returns `None` if the buffer is unavailable, i.e. when called re-entrantly from a `Display`
+/// impl that is itself being formatted, or while the thread is being torn down.
+pub(crate) fn with_scratch<R>(f: impl FnOnce(&mut String) -> R) -> Option<R> {
+    SCRATCH
+        .try_with(|scratch| {
+            let mut scratch = scratch.try_borrow_mut().ok()?;
+            scratch.clear();
+            let result = f(&mut scratch);
+
+            if scratch.capacity() > MAX_RETAINED_CAPACITY {
+                *scratch = String::new();
+            }
+            Some(result)
+        })
+        .ok()
+        .flatten()
+}
+
+#[cfg(test)]
+mod tests {
+    use core::fmt;
+
+    use super::{with_scratch, MAX_RETAINED_CAPACITY, SCRATCH};
+    use crate::{format_compact, ToCompactString};
+
+    #[test]
+    fn test_exact_capacity() {
+        let long = "a".repeat(100);
+        let s = format_compact!("{}{}", long, 42);
+
+        assert_eq!(s.len(), 102);
+        assert_eq!(s.capacity(), 102);
+
+        let s = format_compact!("{}", 42.5);
+        assert_eq!(s, "42.5");
+        assert!(!s.is_heap_allocated());
+    }
+
+    #[test]
+    fn test_reentrant() {
+        struct Nested;
+
+        impl fmt::Display for Nested {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                let inner = format_compact!("inner {}", "value");
+                write!(f, "[{}]", inner)
+            }
+        }
+
+        let s = format_compact!("outer {}", Nested);
+        assert_eq!(s, "outer [inner value]");
+        assert_eq!(Nested.to_compact_string(), "[inner value]");
+    }
+
+    #[test]
+    fn test_error_is_returned() {
+        struct Fails;
+
+        impl fmt::Display for Fails {
+            fn fmt(&self, _: &mut fmt::Formatter<'_>) -> fmt::Result {
+                Err(fmt::Error)
+            }
+        }
+
+        assert!(Fails.try_to_compact_string().is_err());
+
+        // the scratch buffer is still usable afterwards
+        assert_eq!(format_compact!("{}-{}", 1, 2), "1-2");
+    }
+
+    #[test]
+    fn test_large_buffers_are_released() {
+        let huge = "a".repeat(MAX_RETAINED_CAPACITY * 2);
+        assert_eq!(format_compact!("{}!", huge).len(), huge.len() + 1);
+
+        let capacity = SCRATCH.with(|scratch| scratch.borrow().capacity());
+        assert!(capacity <= MAX_RETAINED_CAPACITY);
+        assert_eq!(with_scratch(|scratch| scratch.len()), Some(0));
+    }
+}
@@ -0,0 +1,31 @@
+use alloc::string::String;
+
+use maud::Render;
+
+use crate::CompactString;
+
+#[cfg_attr(docsrs, doc(cfg(feature = "maud")))]
+impl Render for CompactString {
+    #[inline]
+    fn render_to(&self, buffer: &mut String) {
+        self.as_str().render_to(buffer)
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_maud() {
+    const TEXT: &str = "<script>alert(\"Hello, world!\")</script>";
+
+    fn page<M: Render>(msg: M) -> maud::Markup {
+        maud::html! { textarea { (msg) } }
+    }
+
+    let compact = page(CompactString::from(TEXT));
+    let control = page(String::from(TEXT));
+    assert_eq!(compact.0, control.0);
+    assert_eq!(
+        compact.into_string(),
+        "<textarea>&lt;script&gt;alert(&quot;Hello, world!&quot;)&lt;/script&gt;</textarea>",
+    );
+}
@@ -0,0 +1,108 @@
+#![cfg_attr(docsrs, doc(cfg(feature = "minicbor")))]
+
+use minicbor::decode::{self, Decoder};
+use minicbor::encode::{self, Encoder, Write};
+use minicbor::{Decode, Encode};
+
+use crate::CompactString;
+
+impl<C> Encode<C> for CompactString {
+    fn encode<W: Write>(
+        &self,
+        e: &mut Encoder<W>,
+        _ctx: &mut C,
+    ) -> Result<(), encode::Error<W::Error>> {
+        e.str(self.as_str())?.ok()
+    }
+}
+
+impl<'b, C> Decode<'b, C> for CompactString {
+    fn decode(d: &mut Decoder<'b>, _ctx: &mut C) -> Result<Self, decode::Error> {
+        // Definite-length text strings are yielded as a single chunk, whereas indefinite-length
+        // text strings get yielded chunk by chunk, which we append directly into our buffer
+        let mut s = CompactString::const_new("");
+        for chunk in d.str_iter()? {
+            s.push_str(chunk?);
+        }
+        Ok(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::String;
+
+    use minicbor::Encoder;
+    use test_strategy::proptest;
+
+    use crate::CompactString;
+
+    fn assert_roundtrip(s: &str) {
+        let bytes_compact = minicbor::to_vec(CompactString::from(s)).unwrap();
+        let bytes_control = minicbor::to_vec(String::from(s)).unwrap();
+        assert_eq!(&*bytes_compact, &*bytes_control);
+
+        let compact: CompactString = minicbor::decode(&bytes_compact).unwrap();
+        let control: String = minicbor::decode(&bytes_control).unwrap();
+        assert_eq!(compact, s);
+        assert_eq!(control, s);
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        assert_roundtrip("");
+        assert_roundtrip("Hello, 🌍!");
+        assert_roundtrip("this is a long string that will be on the heap");
+    }
+
+    #[test]
+    fn test_decode_indefinite() {
+        let mut e = Encoder::new(alloc::vec::Vec::new());
+        e.begin_str()
+            .unwrap()
+            .str("hello ")
+            .unwrap()
+            .str("")
+            .unwrap()
+            .str("world")
+            .unwrap()
+            .end()
+            .unwrap();
+        let bytes = e.into_writer();
+
+        let compact: CompactString = minicbor::decode(&bytes).unwrap();
+        assert_eq!(compact, "hello world");
+        assert!(!compact.is_heap_allocated());
+    }
+
+    #[test]
+    fn test_decode_invalid() {
+        // a byte string, not a text string
+        let bytes = [0x45, b'h', b'e', b'l', b'l', b'o'];
+        minicbor::decode::<CompactString>(&bytes).unwrap_err();
+
+        // invalid UTF-8
+        let bytes = [0x62, 0xF0, 0x9F];
+        minicbor::decode::<CompactString>(&bytes).unwrap_err();
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[proptest]
+    fn proptest_roundtrip(s: String) {
+        assert_roundtrip(&s);
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[proptest]
+    fn proptest_decode_indefinite(chunks: alloc::vec::Vec<String>) {
+        let mut e = Encoder::new(alloc::vec::Vec::new());
+        e.begin_str().unwrap();
+        for chunk in &chunks {
+            e.str(chunk).unwrap();
+        }
+        e.end().unwrap();
+
+        let compact: CompactString = minicbor::decode(e.writer()).unwrap();
+        assert_eq!(compact, chunks.concat());
+    }
+}
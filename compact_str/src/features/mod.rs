@@ -2,23 +2,63 @@
 
 #[cfg(feature = "arbitrary")]
 mod arbitrary;
+#[cfg(feature = "arcstr")]
+mod arcstr;
+#[cfg(feature = "askama")]
+mod askama;
 #[cfg(feature = "borsh")]
 mod borsh;
 #[cfg(feature = "bytes")]
 mod bytes;
+#[cfg(feature = "camino")]
+mod camino;
+#[cfg(feature = "clap")]
+pub(crate) mod clap;
+#[cfg(feature = "clone-tracking")]
+pub mod clone_tracking;
+#[cfg(feature = "csv")]
+pub mod csv;
+#[cfg(feature = "debug-validate")]
+pub(crate) mod debug_validate;
 #[cfg(feature = "diesel")]
 mod diesel;
+#[cfg(feature = "equivalent")]
+mod equivalent;
+#[cfg(feature = "format-scratch")]
+pub(crate) mod format_scratch;
 #[cfg(feature = "markup")]
 mod markup;
+#[cfg(feature = "maud")]
+mod maud;
+#[cfg(feature = "minicbor")]
+mod minicbor;
+#[cfg(feature = "percent-encoding")]
+mod percent_encoding;
 #[cfg(feature = "proptest")]
 mod proptest;
 #[cfg(feature = "quickcheck")]
 mod quickcheck;
+#[cfg(feature = "rand")]
+pub(crate) mod rand;
+#[cfg(feature = "rayon")]
+mod rayon;
+#[cfg(feature = "regex")]
+pub mod regex;
 #[cfg(feature = "rkyv")]
 mod rkyv;
 #[cfg(feature = "serde")]
-mod serde;
+pub mod serde;
+#[cfg(feature = "serde_json")]
+mod serde_json;
+#[cfg(feature = "smallstr-compat")]
+pub mod smallstr;
 #[cfg(feature = "smallvec")]
 mod smallvec;
 #[cfg(feature = "sqlx")]
 mod sqlx;
+#[cfg(feature = "time")]
+pub mod time;
+#[cfg(feature = "unicode-width")]
+mod unicode_width;
+#[cfg(feature = "uuid")]
+mod uuid;
@@ -0,0 +1,124 @@
+use core::str::Utf8Error;
+
+use percent_encoding::{percent_decode_str, PercentEncode};
+
+use crate::{CompactString, MAX_INLINE_SIZE};
+
+impl CompactString {
+    /// Percent-decodes `input` into a [`CompactString`], failing if the decoded bytes are not
+    /// valid UTF-8.
+    ///
+    /// The bytes are decoded straight into the buffer of the [`CompactString`], without going
+    /// through an intermediate [`Cow<str>`](alloc::borrow::Cow), so short values are never heap
+    /// allocated. Note that `+` is left as is, replace it with a space first if `input` is
+    /// `application/x-www-form-urlencoded`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_str::CompactString;
+    /// let s = CompactString::from_percent_encoded("caf%C3%A9%20au%20lait").unwrap();
+    ///
+    /// assert_eq!(s, "café au lait");
+    /// assert!(!s.is_heap_allocated());
+    ///
+    /// // `%FF` isn't valid UTF-8
+    /// assert!(CompactString::from_percent_encoded("%FF").is_err());
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "percent-encoding")))]
+    pub fn from_percent_encoded(input: &str) -> Result<Self, Utf8Error> {
+        // Decoding never makes the string longer
+        let mut s = CompactString::with_capacity(input.len());
+
+        // SAFETY: We validate the decoded bytes are UTF-8 before marking them as initialized
+        unsafe {
+            let buf = s.as_mut_bytes();
+            let mut len = 0;
+            for (slot, byte) in buf.iter_mut().zip(percent_decode_str(input)) {
+                *slot = byte;
+                len += 1;
+            }
+            core::str::from_utf8(&buf[..len])?;
+            s.set_len(len);
+        }
+
+        // The encoded string might not have fit inline, but the decoded one does
+        if s.is_heap_allocated() && s.len() <= MAX_INLINE_SIZE {
+            s.shrink_to_fit();
+        }
+        Ok(s)
+    }
+}
+
+/// Percent-encodes straight into a [`CompactString`], without an intermediate [`String`].
+///
+/// [`String`]: alloc::string::String
+#[cfg_attr(docsrs, doc(cfg(feature = "percent-encoding")))]
+impl From<PercentEncode<'_>> for CompactString {
+    fn from(encode: PercentEncode<'_>) -> Self {
+        let mut s = CompactString::default();
+        s.extend(encode);
+        s
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::{String, ToString};
+
+    use percent_encoding::{percent_decode_str, utf8_percent_encode, NON_ALPHANUMERIC};
+    use test_strategy::proptest;
+
+    use crate::CompactString;
+
+    #[test]
+    fn test_from_percent_encoded() {
+        let s = CompactString::from_percent_encoded("hello%20world").unwrap();
+        assert_eq!(s, "hello world");
+
+        // nothing to decode
+        let s = CompactString::from_percent_encoded("plain").unwrap();
+        assert_eq!(s, "plain");
+
+        // malformed escapes are kept as is
+        let s = CompactString::from_percent_encoded("100%%2").unwrap();
+        assert_eq!(s, "100%%2");
+
+        // the encoded string is too long to be inlined, but the decoded one isn't
+        let encoded = "%F0%9F%A6%80%F0%9F%A6%80%F0%9F%A6%80";
+        let s = CompactString::from_percent_encoded(encoded).unwrap();
+        assert_eq!(s, "🦀🦀🦀");
+        assert!(!s.is_heap_allocated());
+
+        let long = "this%20is%20a%20long%20string%20that%20will%20be%20on%20the%20heap";
+        let s = CompactString::from_percent_encoded(long).unwrap();
+        assert_eq!(s, "this is a long string that will be on the heap");
+
+        assert!(CompactString::from_percent_encoded("%C3%28").is_err());
+    }
+
+    #[test]
+    fn test_from_percent_encode() {
+        let s = CompactString::from(utf8_percent_encode("café au lait", NON_ALPHANUMERIC));
+        assert_eq!(s, "caf%C3%A9%20au%20lait");
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[proptest]
+    fn proptest_percent_roundtrip(s: String) {
+        let encoded = CompactString::from(utf8_percent_encode(&s, NON_ALPHANUMERIC));
+        assert_eq!(
+            encoded,
+            utf8_percent_encode(&s, NON_ALPHANUMERIC).to_string()
+        );
+
+        let decoded = CompactString::from_percent_encoded(&encoded).unwrap();
+        assert_eq!(decoded, s);
+        assert_eq!(
+            CompactString::from_percent_encoded(&s).ok(),
+            percent_decode_str(&s)
+                .decode_utf8()
+                .ok()
+                .map(CompactString::from)
+        );
+    }
+}
@@ -5,6 +5,7 @@ use alloc::vec::Vec;
 
 use quickcheck::{Arbitrary, Gen};
 
+use crate::repr::MAX_SIZE;
 use crate::CompactString;
 
 #[cfg_attr(docsrs, doc(cfg(feature = "quickcheck")))]
@@ -21,22 +22,61 @@ impl Arbitrary for CompactString {
     }
 
     fn shrink(&self) -> Box<dyn Iterator<Item = CompactString>> {
-        // Shrink a string by shrinking a vector of its characters.
+        let mut candidates = Vec::new();
+
+        // Bugs tend to lurk at the boundary between inline and heap allocated strings, so first
+        // try the longest prefixes that are just on either side of it
+        for max_len in [MAX_SIZE + 1, MAX_SIZE] {
+            if self.len() > max_len {
+                let prefix = CompactString::new(&self[..floor_char_boundary(self, max_len)]);
+                if candidates.last() != Some(&prefix) {
+                    candidates.push(prefix);
+                }
+            }
+        }
+
+        // Then try replacing all of the non-ASCII characters, keeping the length in bytes the same
+        // so we don't change whether or not the string is inlined
+        if !self.is_ascii() {
+            let mut ascii = CompactString::with_capacity(self.len());
+            for c in self.chars() {
+                if c.is_ascii() {
+                    ascii.push(c);
+                } else {
+                    (0..c.len_utf8()).for_each(|_| ascii.push('a'));
+                }
+            }
+            candidates.push(ascii);
+        }
+
+        // Finally shrink a string by shrinking a vector of its characters.
         let chars: Vec<char> = self.chars().collect();
         Box::new(
-            chars
-                .shrink()
-                .map(|x| x.into_iter().collect::<CompactString>()),
+            candidates.into_iter().chain(
+                chars
+                    .shrink()
+                    .map(|x| x.into_iter().collect::<CompactString>()),
+            ),
         )
     }
 }
 
+/// Returns the largest index `<= idx` that lies on a char boundary of `s`.
+fn floor_char_boundary(s: &str, mut idx: usize) -> usize {
+    while !s.is_char_boundary(idx) {
+        idx -= 1;
+    }
+    idx
+}
+
 #[cfg(test)]
 mod test {
     use alloc::string::String;
 
+    use quickcheck::Arbitrary;
     use quickcheck_macros::quickcheck;
 
+    use crate::repr::MAX_SIZE;
     use crate::CompactString;
 
     #[quickcheck]
@@ -46,6 +86,42 @@ mod test {
         assert_eq!(control, compact);
     }
 
+    #[quickcheck]
+    #[cfg_attr(miri, ignore)]
+    fn quickcheck_shrink_is_smaller(compact: CompactString) {
+        for shrunk in compact.shrink().take(100) {
+            assert_ne!(shrunk, compact);
+            assert!(shrunk.len() <= compact.len());
+        }
+    }
+
+    #[test]
+    fn test_shrink_toward_boundary() {
+        let long = CompactString::from("🦀".repeat(25));
+        let mut shrunk = long.shrink();
+        assert_eq!(shrunk.next().unwrap(), "🦀".repeat(6));
+        assert_eq!(shrunk.next().unwrap(), "a".repeat(100));
+
+        let long = CompactString::from("a".repeat(100));
+        let mut shrunk = long.shrink();
+        assert_eq!(shrunk.next().unwrap(), "a".repeat(MAX_SIZE + 1));
+        assert_eq!(shrunk.next().unwrap(), "a".repeat(MAX_SIZE));
+    }
+
+    #[test]
+    fn test_shrink_minimizes() {
+        // greedily shrink a failing input, the same way quickcheck does
+        let fails = |s: &CompactString| s.is_heap_allocated();
+
+        let mut curr = CompactString::from("🦀 this is a long string that will be on the heap 🌍");
+        while let Some(next) = curr.shrink().find(fails) {
+            curr = next;
+        }
+
+        assert_eq!(curr.len(), MAX_SIZE + 1);
+        assert!(curr.is_ascii());
+    }
+
     #[quickcheck]
     #[cfg_attr(miri, ignore)]
     fn quickcheck_inlines_strings(compact: CompactString) {
@@ -0,0 +1,140 @@
+//! Implements sampling random [`CompactString`]s from [`rand`] distributions
+
+use rand::distributions::{Alphanumeric, Distribution, Standard};
+use rand::Rng;
+
+use crate::CompactString;
+
+/// String sampling, like [`rand::distributions::DistString`], but for [`CompactString`]
+///
+/// Sampled characters are written directly into the [`CompactString`], so short strings never
+/// touch the heap and no intermediate [`String`](alloc::string::String) is created.
+///
+/// # Example
+/// ```
+/// use compact_str::DistCompactString;
+/// use rand::distributions::Alphanumeric;
+/// use rand::rngs::SmallRng;
+/// use rand::SeedableRng;
+///
+/// let mut rng = SmallRng::seed_from_u64(42);
+/// let s = Alphanumeric.sample_compact_string(&mut rng, 12);
+///
+/// assert_eq!(s.len(), 12);
+/// assert!(s.chars().all(|c| c.is_ascii_alphanumeric()));
+/// assert!(!s.is_heap_allocated());
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "rand")))]
+pub trait DistCompactString {
+    /// Append `len` random chars to `string`
+    fn append_compact_string<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+        string: &mut CompactString,
+        len: usize,
+    );
+
+    /// Generate a [`CompactString`] of `len` random chars
+    #[inline]
+    fn sample_compact_string<R: Rng + ?Sized>(&self, rng: &mut R, len: usize) -> CompactString {
+        let mut s = CompactString::const_new("");
+        self.append_compact_string(rng, &mut s, len);
+        s
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "rand")))]
+impl DistCompactString for Alphanumeric {
+    fn append_compact_string<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+        string: &mut CompactString,
+        len: usize,
+    ) {
+        string.reserve(len);
+
+        let start = string.len();
+        let end = start + len;
+
+        // SAFETY: `Alphanumeric` only ever produces ASCII bytes, which are valid UTF-8, and we
+        // reserved enough space for `len` more bytes above
+        unsafe {
+            let buf = &mut string.as_mut_bytes()[start..end];
+            for byte in buf {
+                *byte = self.sample(rng);
+            }
+            string.set_len(end);
+        }
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "rand")))]
+impl DistCompactString for Standard {
+    fn append_compact_string<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+        string: &mut CompactString,
+        len: usize,
+    ) {
+        string.reserve(len);
+        string.extend(Distribution::<char>::sample_iter(self, rng).take(len));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::distributions::{Alphanumeric, Standard};
+    use rand::rngs::SmallRng;
+    use rand::SeedableRng;
+    use test_case::test_case;
+
+    use super::DistCompactString;
+    use crate::CompactString;
+
+    #[test_case(0; "empty")]
+    #[test_case(8; "short")]
+    #[test_case(24; "max inline")]
+    #[test_case(25; "min heap")]
+    #[test_case(100; "long")]
+    fn test_alphanumeric(len: usize) {
+        let mut rng = SmallRng::seed_from_u64(len as u64);
+        let s = Alphanumeric.sample_compact_string(&mut rng, len);
+
+        assert_eq!(s.len(), len);
+        assert!(s.chars().all(|c| c.is_ascii_alphanumeric()));
+        assert_eq!(
+            s.is_heap_allocated(),
+            len > core::mem::size_of::<CompactString>()
+        );
+    }
+
+    #[test_case(0; "empty")]
+    #[test_case(8; "short")]
+    #[test_case(100; "long")]
+    fn test_standard(len: usize) {
+        let mut rng = SmallRng::seed_from_u64(len as u64);
+        let s = Standard.sample_compact_string(&mut rng, len);
+
+        assert_eq!(s.chars().count(), len);
+    }
+
+    #[test]
+    fn test_append() {
+        let mut rng = SmallRng::seed_from_u64(0);
+        let mut s = CompactString::from("prefix-");
+        Alphanumeric.append_compact_string(&mut rng, &mut s, 30);
+
+        assert_eq!(s.len(), 37);
+        assert!(s.starts_with("prefix-"));
+        assert!(s[7..].chars().all(|c| c.is_ascii_alphanumeric()));
+    }
+
+    #[test]
+    fn test_matches_dist_string() {
+        use rand::distributions::DistString;
+
+        let a = Alphanumeric.sample_compact_string(&mut SmallRng::seed_from_u64(7), 40);
+        let b = Alphanumeric.sample_string(&mut SmallRng::seed_from_u64(7), 40);
+        assert_eq!(a, b);
+    }
+}
@@ -0,0 +1,158 @@
+//! Implements [`FromParallelIterator`] and [`ParallelExtend`] for [`CompactString`]
+
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::collections::LinkedList;
+use alloc::string::String;
+use core::iter;
+
+use rayon::iter::{FromParallelIterator, IntoParallelIterator, ParallelExtend, ParallelIterator};
+
+use crate::CompactString;
+
+/// Each rayon job folds its items into its own [`CompactString`], the chunks are then collected
+/// into a list so we can reserve the exact amount of space we need before concatenating them
+fn collect_chunks<I, T>(par_iter: I) -> LinkedList<CompactString>
+where
+    I: IntoParallelIterator<Item = T>,
+    CompactString: Extend<T>,
+    T: Send,
+{
+    par_iter
+        .into_par_iter()
+        .fold(CompactString::default, |mut chunk, item| {
+            chunk.extend(iter::once(item));
+            chunk
+        })
+        .map(|chunk| {
+            let mut list = LinkedList::new();
+            list.push_back(chunk);
+            list
+        })
+        .reduce(LinkedList::new, |mut a, mut b| {
+            a.append(&mut b);
+            a
+        })
+}
+
+macro_rules! impl_rayon {
+    ($($item:ty),* $(,)?) => {
+        $(
+            #[cfg_attr(docsrs, doc(cfg(feature = "rayon")))]
+            impl<'a> ParallelExtend<$item> for CompactString {
+                fn par_extend<I>(&mut self, par_iter: I)
+                where
+                    I: IntoParallelIterator<Item = $item>,
+                {
+                    let chunks = collect_chunks(par_iter);
+
+                    self.reserve(chunks.iter().map(CompactString::len).sum());
+                    chunks.iter().for_each(|chunk| self.push_str(chunk));
+                }
+            }
+
+            #[cfg_attr(docsrs, doc(cfg(feature = "rayon")))]
+            impl<'a> FromParallelIterator<$item> for CompactString {
+                fn from_par_iter<I>(par_iter: I) -> Self
+                where
+                    I: IntoParallelIterator<Item = $item>,
+                {
+                    let mut s = CompactString::default();
+                    s.par_extend(par_iter);
+                    s
+                }
+            }
+        )*
+    };
+}
+
+impl_rayon!(
+    char,
+    &'a char,
+    &'a str,
+    Box<str>,
+    Cow<'a, str>,
+    String,
+    CompactString,
+);
+
+#[cfg_attr(docsrs, doc(cfg(feature = "rayon")))]
+impl ParallelExtend<CompactString> for String {
+    fn par_extend<I>(&mut self, par_iter: I)
+    where
+        I: IntoParallelIterator<Item = CompactString>,
+    {
+        let chunks = collect_chunks(par_iter);
+
+        self.reserve(chunks.iter().map(CompactString::len).sum());
+        chunks.iter().for_each(|chunk| self.push_str(chunk));
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "rayon")))]
+impl FromParallelIterator<CompactString> for String {
+    fn from_par_iter<I>(par_iter: I) -> Self
+    where
+        I: IntoParallelIterator<Item = CompactString>,
+    {
+        let mut s = String::new();
+        s.par_extend(par_iter);
+        s
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::{String, ToString};
+    use alloc::vec::Vec;
+
+    use rayon::prelude::*;
+    use test_strategy::proptest;
+
+    use crate::CompactString;
+
+    #[test]
+    fn test_collect_str() {
+        let words = ["hello", " ", "world", "!"];
+        let c: CompactString = words.par_iter().copied().collect();
+
+        assert_eq!(c, "hello world!");
+        assert!(!c.is_heap_allocated());
+    }
+
+    #[test]
+    fn test_par_extend() {
+        let mut c = CompactString::from("numbers:");
+        c.par_extend((0..1000).into_par_iter().map(|i| i.to_string()));
+
+        let expected: String = core::iter::once(String::from("numbers:"))
+            .chain((0..1000).map(|i| i.to_string()))
+            .collect();
+        assert_eq!(c, expected);
+    }
+
+    #[test]
+    fn test_collect_compact_strings_into_string() {
+        let items: Vec<CompactString> = (0..500)
+            .map(|i| CompactString::from(i.to_string()))
+            .collect();
+
+        let s: String = items.clone().into_par_iter().collect();
+        let expected: String = items.iter().map(CompactString::as_str).collect();
+        assert_eq!(s, expected);
+    }
+
+    #[proptest]
+    #[cfg_attr(miri, ignore)]
+    fn proptest_collect_chars(s: String) {
+        let c: CompactString = s.par_chars().collect();
+        assert_eq!(c, s);
+    }
+
+    #[proptest]
+    #[cfg_attr(miri, ignore)]
+    fn proptest_collect_strings(items: Vec<String>) {
+        let c: CompactString = items.par_iter().map(String::as_str).collect();
+        assert_eq!(c, items.concat());
+    }
+}
@@ -0,0 +1,217 @@
+//! Helpers for using [`regex`] with [`CompactString`]s.
+//!
+//! [`Replacer`] is implemented for [`CompactString`] and `&CompactString`, so they can be used as
+//! the replacement in [`Regex::replace_all`] without calling `.as_str()`. Like a [`String`]
+//! replacement, `$name` and `$1` are expanded to the matching capture group.
+//!
+//! The `*_compact` functions mirror [`Regex::replace`], [`Regex::replacen`] and
+//! [`Regex::replace_all`], but write the result straight into a [`CompactString`] instead of
+//! returning a [`Cow<str>`] that then has to be converted.
+//!
+//! # Examples
+//! ```
+//! use compact_str::CompactString;
+//! use regex::Regex;
+//!
+//! let re = Regex::new(r"(?<y>\d{4})-(?<m>\d{2})-(?<d>\d{2})").unwrap();
+//! let format = CompactString::new("$m/$d/$y");
+//!
+//! let s = compact_str::regex::replace_all_compact(&re, "2012-03-14, 2013-01-01", &format);
+//! assert_eq!(s, "03/14/2012, 01/01/2013");
+//! ```
+//!
+//! [`String`]: alloc::string::String
+
+use alloc::borrow::Cow;
+use alloc::string::String;
+
+use regex::{Captures, Regex, Replacer};
+
+use crate::CompactString;
+
+#[cfg_attr(docsrs, doc(cfg(feature = "regex")))]
+impl Replacer for CompactString {
+    #[inline]
+    fn replace_append(&mut self, caps: &Captures<'_>, dst: &mut String) {
+        caps.expand(self.as_str(), dst);
+    }
+
+    #[inline]
+    fn no_expansion(&mut self) -> Option<Cow<'_, str>> {
+        no_expansion(self)
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "regex")))]
+impl Replacer for &CompactString {
+    #[inline]
+    fn replace_append(&mut self, caps: &Captures<'_>, dst: &mut String) {
+        caps.expand(self.as_str(), dst);
+    }
+
+    #[inline]
+    fn no_expansion(&mut self) -> Option<Cow<'_, str>> {
+        no_expansion(self)
+    }
+}
+
+/// Returns the replacement as is, if it doesn't contain a `$` that would need to be expanded
+fn no_expansion(replacement: &CompactString) -> Option<Cow<'_, str>> {
+    match replacement.find_byte(b'$') {
+        Some(_) => None,
+        None => Some(Cow::Borrowed(replacement.as_str())),
+    }
+}
+
+/// Replaces the leftmost-first match of `re` in `haystack` with `rep`, like [`Regex::replace`].
+pub fn replace_compact<R: Replacer>(re: &Regex, haystack: &str, rep: R) -> CompactString {
+    replacen_compact(re, haystack, 1, rep)
+}
+
+/// Replaces all non-overlapping matches of `re` in `haystack` with `rep`, like
+/// [`Regex::replace_all`].
+pub fn replace_all_compact<R: Replacer>(re: &Regex, haystack: &str, rep: R) -> CompactString {
+    replacen_compact(re, haystack, 0, rep)
+}
+
+/// Replaces at most `limit` non-overlapping matches of `re` in `haystack` with `rep`, like
+/// [`Regex::replacen`]. If `limit` is 0, all matches are replaced.
+///
+/// The result is built in a single pass, starting out inline. Once it spills onto the heap room for
+/// the rest of `haystack` is reserved, so it's allocated at most once when the replacements don't
+/// make the string longer, and never when the result fits inline. If nothing matches `haystack` is
+/// copied as is.
+pub fn replacen_compact<R: Replacer>(
+    re: &Regex,
+    haystack: &str,
+    limit: usize,
+    mut rep: R,
+) -> CompactString {
+    // The replacement doesn't depend on the captures, so we can skip finding them
+    if let Some(rep) = rep.no_expansion() {
+        let mut matches = re.find_iter(haystack).peekable();
+        if matches.peek().is_none() {
+            return CompactString::new(haystack);
+        }
+
+        let mut new = CompactString::default();
+        let mut last_match = 0;
+        for (i, m) in matches.enumerate() {
+            if limit > 0 && i >= limit {
+                break;
+            }
+            let remaining = haystack.len() - last_match;
+            push_spilling(&mut new, &haystack[last_match..m.start()], remaining);
+            push_spilling(&mut new, &rep, remaining);
+            last_match = m.end();
+        }
+        push_spilling(&mut new, &haystack[last_match..], 0);
+        return new;
+    }
+
+    let mut captures = re.captures_iter(haystack).peekable();
+    if captures.peek().is_none() {
+        return CompactString::new(haystack);
+    }
+
+    let mut new = CompactString::default();
+    // `Replacer` can only append to a `String`, so each replacement is expanded into a scratch
+    // buffer that gets re-used for every match
+    let mut scratch = String::new();
+    let mut last_match = 0;
+    for (i, caps) in captures.enumerate() {
+        if limit > 0 && i >= limit {
+            break;
+        }
+        // unwrap on 0 is OK because captures only reports matches
+        let m = caps.get(0).unwrap();
+        let remaining = haystack.len() - last_match;
+        push_spilling(&mut new, &haystack[last_match..m.start()], remaining);
+        scratch.clear();
+        rep.replace_append(&caps, &mut scratch);
+        push_spilling(&mut new, &scratch, remaining);
+        last_match = m.end();
+    }
+    push_spilling(&mut new, &haystack[last_match..], 0);
+    new
+}
+
+/// Appends `s` to `new`, reserving room for the `remaining` bytes of the haystack when `new` first
+/// spills onto the heap.
+#[inline]
+fn push_spilling(new: &mut CompactString, s: &str, remaining: usize) {
+    if !new.is_heap_allocated() && new.would_reallocate(s.len()) {
+        new.reserve(s.len().max(remaining));
+    }
+    new.push_str(s);
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::{String, ToString};
+
+    use regex::{NoExpand, Regex};
+    use test_strategy::proptest;
+
+    use super::{replace_all_compact, replace_compact, replacen_compact};
+    use crate::CompactString;
+
+    #[test]
+    fn test_replacer() {
+        let re = Regex::new(r"(\w+)@(\w+)").unwrap();
+        let haystack = "alice@example bob@example";
+
+        let rep = CompactString::new("$2:$1");
+        assert_eq!(re.replace_all(haystack, &rep), "example:alice example:bob");
+        assert_eq!(re.replace(haystack, rep), "example:alice bob@example");
+
+        let rep = CompactString::new("<redacted>");
+        assert_eq!(re.replace_all(haystack, &rep), "<redacted> <redacted>");
+    }
+
+    #[test]
+    fn test_replace_compact() {
+        let re = Regex::new(r"\d+").unwrap();
+
+        let s = replace_all_compact(&re, "a1b22c333", "#");
+        assert_eq!(s, "a#b#c#");
+        assert!(!s.is_heap_allocated());
+
+        assert_eq!(replace_compact(&re, "a1b22c333", "#"), "a#b22c333");
+        assert_eq!(replacen_compact(&re, "a1b22c333", 2, "#"), "a#b#c333");
+        assert_eq!(replace_all_compact(&re, "no digits", "#"), "no digits");
+        assert_eq!(replace_all_compact(&re, "", "#"), "");
+
+        // expanded replacements, and closures
+        let re = Regex::new(r"(?<num>\d+)").unwrap();
+        let s = replace_all_compact(&re, "a1b22", "[$num]");
+        assert_eq!(s, "a[1]b[22]");
+        let s = replace_all_compact(&re, "a1b22", NoExpand("$num"));
+        assert_eq!(s, "a$numb$num");
+        let s = replace_all_compact(&re, "a1b22", |caps: &regex::Captures<'_>| {
+            caps[0].len().to_string()
+        });
+        assert_eq!(s, "a1b2");
+
+        // a long haystack with a short result
+        let long = "0123456789012345678901234567890123456789";
+        let s = replace_all_compact(&re, long, "x");
+        assert_eq!(s, "x");
+        assert!(!s.is_heap_allocated());
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[proptest]
+    fn proptest_replace_all_compact(
+        #[strategy("[a-c0-9$ ]{0,60}")] haystack: String,
+        #[strategy("[a-c$1 ]{0,8}")] rep: String,
+        #[strategy(0usize..4)] limit: usize,
+    ) {
+        let re = Regex::new(r"([a-c])\d*").unwrap();
+        let compact = replacen_compact(&re, &haystack, limit, rep.as_str());
+        assert_eq!(compact, re.replacen(&haystack, limit, rep.as_str()));
+
+        let compact = replace_all_compact(&re, &haystack, CompactString::new(&rep));
+        assert_eq!(compact, re.replace_all(&haystack, rep.as_str()));
+    }
+}
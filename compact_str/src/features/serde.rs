@@ -1,3 +1,6 @@
+//! Implements [`serde::Serialize`] and [`serde::Deserialize`] for [`CompactString`], and provides
+//! helpers for customizing how a [`CompactString`] gets deserialized.
+
 use alloc::string::String;
 use alloc::vec::Vec;
 
@@ -7,29 +10,51 @@ use crate::CompactString;
 
 fn compact_string<'de: 'a, 'a, D: Deserializer<'de>>(
     deserializer: D,
+    max_len: usize,
+    as_bytes: bool,
 ) -> Result<CompactString, D::Error> {
-    struct CompactStringVisitor;
+    struct CompactStringVisitor {
+        max_len: usize,
+    }
+
+    impl CompactStringVisitor {
+        fn check_len<E: Error>(&self, len: usize) -> Result<(), E> {
+            if len > self.max_len {
+                Err(Error::invalid_length(len, self))
+            } else {
+                Ok(())
+            }
+        }
+    }
 
     impl<'a> Visitor<'a> for CompactStringVisitor {
         type Value = CompactString;
 
         fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
-            formatter.write_str("a string")
+            if self.max_len == usize::MAX {
+                formatter.write_str("a string")
+            } else {
+                write!(formatter, "a string of at most {} bytes", self.max_len)
+            }
         }
 
         fn visit_str<E: Error>(self, v: &str) -> Result<Self::Value, E> {
+            self.check_len(v.len())?;
             Ok(CompactString::from(v))
         }
 
         fn visit_borrowed_str<E: Error>(self, v: &'a str) -> Result<Self::Value, E> {
+            self.check_len(v.len())?;
             Ok(CompactString::from(v))
         }
 
         fn visit_string<E: Error>(self, v: String) -> Result<Self::Value, E> {
+            self.check_len(v.len())?;
             Ok(CompactString::from(v))
         }
 
         fn visit_bytes<E: Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+            self.check_len(v.len())?;
             match core::str::from_utf8(v) {
                 Ok(s) => Ok(CompactString::from(s)),
                 Err(_) => Err(Error::invalid_value(Unexpected::Bytes(v), &self)),
@@ -37,6 +62,7 @@ fn compact_string<'de: 'a, 'a, D: Deserializer<'de>>(
         }
 
         fn visit_borrowed_bytes<E: Error>(self, v: &'a [u8]) -> Result<Self::Value, E> {
+            self.check_len(v.len())?;
             match core::str::from_utf8(v) {
                 Ok(s) => Ok(CompactString::from(s)),
                 Err(_) => Err(Error::invalid_value(Unexpected::Bytes(v), &self)),
@@ -44,6 +70,7 @@ fn compact_string<'de: 'a, 'a, D: Deserializer<'de>>(
         }
 
         fn visit_byte_buf<E: Error>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+            self.check_len(v.len())?;
             match String::from_utf8(v) {
                 Ok(s) => Ok(CompactString::from(s)),
                 Err(e) => Err(Error::invalid_value(
@@ -54,7 +81,111 @@ fn compact_string<'de: 'a, 'a, D: Deserializer<'de>>(
         }
     }
 
-    deserializer.deserialize_str(CompactStringVisitor)
+    let visitor = CompactStringVisitor { max_len };
+    if as_bytes {
+        deserializer.deserialize_bytes(visitor)
+    } else {
+        deserializer.deserialize_str(visitor)
+    }
+}
+
+/// Deserializes a [`CompactString`], rejecting any string that is longer than `N` bytes.
+///
+/// This is meant to be used with serde's `deserialize_with` attribute, as
+/// `Bounded::<N>::deserialize`, and works without the `std` feature, e.g. with `postcard` on
+/// embedded targets.
+///
+/// When a format hands us a borrowed or temporary string, the length is checked before anything
+/// gets copied, so an untrusted input can't make us allocate more than `N` bytes. This is the case
+/// for `postcard`, `bincode`, and `serde_json::from_str`. If a format has already allocated an
+/// owned `String` or `Vec<u8>` for the string, that allocation has happened by the time we see
+/// it, and a string that's too long is only rejected.
+///
+/// # Example
+/// ```
+/// use compact_str::CompactString;
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct User {
+///     #[serde(deserialize_with = "compact_str::serde::Bounded::<16>::deserialize")]
+///     name: CompactString,
+/// }
+///
+/// let user: User = serde_json::from_str(r#"{ "name": "ferris" }"#).unwrap();
+/// assert_eq!(user.name, "ferris");
+///
+/// let err = serde_json::from_str::<User>(r#"{ "name": "a name that is too long" }"#);
+/// assert!(err.is_err());
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+pub enum Bounded<const N: usize> {}
+
+impl<const N: usize> Bounded<N> {
+    /// Deserializes a [`CompactString`] of at most `N` bytes.
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<CompactString, D::Error> {
+        compact_string(deserializer, N, false)
+    }
+}
+
+/// Serializes a [`CompactString`] as raw bytes for binary formats, and as a string for
+/// human-readable formats.
+///
+/// This is meant to be used with serde's `with` attribute. Formats like `bincode` and `postcard`
+/// report that they're not [human-readable](serde::Serializer::is_human_readable), in which case
+/// the string is written with `serialize_bytes` and read back with `deserialize_bytes`. Its UTF-8
+/// is then validated exactly once, while creating the [`CompactString`]. Human-readable formats,
+/// like JSON, keep using plain strings.
+///
+/// # Example
+/// ```
+/// use compact_str::CompactString;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct User {
+///     #[serde(with = "compact_str::serde::binary")]
+///     name: CompactString,
+/// }
+///
+/// let user = User { name: CompactString::new("ferris") };
+///
+/// let json = serde_json::to_string(&user).unwrap();
+/// assert_eq!(json, r#"{"name":"ferris"}"#);
+///
+/// let bytes = postcard::to_allocvec(&user).unwrap();
+/// let user: User = postcard::from_bytes(&bytes).unwrap();
+/// assert_eq!(user.name, "ferris");
+/// ```
+pub mod binary {
+    use serde::{Deserializer, Serializer};
+
+    use crate::CompactString;
+
+    /// Serializes `value` as bytes if `serializer` is not human-readable.
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+    pub fn serialize<S: Serializer>(
+        value: &CompactString,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(value.as_str())
+        } else {
+            serializer.serialize_bytes(value.as_bytes())
+        }
+    }
+
+    /// Deserializes a [`CompactString`] from bytes if `deserializer` is not human-readable.
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<CompactString, D::Error> {
+        let as_bytes = !deserializer.is_human_readable();
+        super::compact_string(deserializer, usize::MAX, as_bytes)
+    }
 }
 
 #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
@@ -64,10 +195,41 @@ impl serde::Serialize for CompactString {
     }
 }
 
+/// Deserializing a [`CompactString`] copies the string straight into its own buffer, without an
+/// intermediate [`String`]. Short strings are inlined, so they don't allocate at all.
+///
+/// * Formats that deserialize from a slice, like `serde_json::from_str`, `rmp_serde::from_slice`,
+///   `bincode`, `postcard`, and `bitcode`, hand us a `&str` borrowed from their input.
+/// * Formats that deserialize from a reader, like `rmp_serde::from_read`, hand us a `&str` that
+///   points into their own scratch buffer, which is still copied only once.
+/// * If a format hands us an owned [`String`], its buffer is re-used instead of copied.
 #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
 impl<'de> serde::Deserialize<'de> for CompactString {
     fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
-        compact_string(deserializer)
+        compact_string(deserializer, usize::MAX, false)
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<V: ?Sized> serde::Serialize for crate::Validated<V> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.as_str().serialize(serializer)
+    }
+}
+
+/// Deserializing a [`Validated`](crate::Validated) runs its [`Validator`](crate::Validator), so
+/// invalid input gets rejected with the validator's error message. Strings longer than
+/// [`Validator::MAX_LEN`](crate::Validator::MAX_LEN) are rejected before they get copied, e.g. for
+/// a [`BoundedCompactString`](crate::BoundedCompactString).
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl<'de, V> serde::Deserialize<'de> for crate::Validated<V>
+where
+    V: crate::Validator + ?Sized,
+    V::Error: core::fmt::Display,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = compact_string(deserializer, V::MAX_LEN, false)?;
+        crate::Validated::new(value).map_err(|(_, err)| D::Error::custom(err))
     }
 }
 
@@ -126,6 +288,184 @@ mod tests {
         assert_eq!(compact_de_std, compact);
     }
 
+    #[derive(Debug, PartialEq, Eq, Deserialize)]
+    struct Bounded {
+        #[serde(deserialize_with = "crate::serde::Bounded::<24>::deserialize")]
+        name: CompactString,
+    }
+
+    #[test]
+    fn test_bounded() {
+        let ok: Bounded = serde_json::from_str(r#"{ "name": "ferris" }"#).unwrap();
+        assert_eq!(ok.name, "ferris");
+        assert!(!ok.name.is_heap_allocated());
+
+        let max: Bounded =
+            serde_json::from_str(r#"{ "name": "abcdefghijklmnopqrstuvwx" }"#).unwrap();
+        assert_eq!(max.name.len(), 24);
+
+        let err = serde_json::from_str::<Bounded>(r#"{ "name": "abcdefghijklmnopqrstuvwxyz" }"#)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("invalid length 26, expected a string of at most 24 bytes"));
+    }
+
+    #[derive(Debug, PartialEq, Eq, Deserialize, Serialize)]
+    struct Binary {
+        #[serde(with = "crate::serde::binary")]
+        name: CompactString,
+        #[serde(with = "crate::serde::binary")]
+        bio: CompactString,
+    }
+
+    #[derive(Debug, PartialEq, Eq, Deserialize, Serialize)]
+    struct Plain {
+        name: CompactString,
+        bio: CompactString,
+    }
+
+    fn assert_binary_roundtrip(name: &str, bio: &str) {
+        let binary = Binary {
+            name: name.into(),
+            bio: bio.into(),
+        };
+        let plain = Plain {
+            name: name.into(),
+            bio: bio.into(),
+        };
+
+        // human-readable formats still get a string
+        let json = serde_json::to_string(&binary).unwrap();
+        assert_eq!(json, serde_json::to_string(&plain).unwrap());
+        assert_eq!(serde_json::from_str::<Binary>(&json).unwrap(), binary);
+
+        // strings and bytes have the same layout in bincode and postcard, so either side can read
+        // what the other wrote
+        let bytes = bincode::serialize(&binary).unwrap();
+        assert_eq!(bytes, bincode::serialize(&plain).unwrap());
+        assert_eq!(bincode::deserialize::<Binary>(&bytes).unwrap(), binary);
+        assert_eq!(bincode::deserialize::<Plain>(&bytes).unwrap(), plain);
+
+        let bytes = postcard::to_allocvec(&binary).unwrap();
+        assert_eq!(bytes, postcard::to_allocvec(&plain).unwrap());
+        assert_eq!(postcard::from_bytes::<Binary>(&bytes).unwrap(), binary);
+        assert_eq!(postcard::from_bytes::<Plain>(&bytes).unwrap(), plain);
+    }
+
+    #[test]
+    fn test_binary_roundtrip() {
+        assert_binary_roundtrip("", "");
+        assert_binary_roundtrip(
+            "ferris",
+            "this is a long string that will be on the heap 🦀",
+        );
+    }
+
+    #[test]
+    fn test_binary_invalid_utf8() {
+        // a length prefix of 2, followed by an incomplete UTF-8 sequence
+        let bytes = [2, 0xF0, 0x9F, 0, 0];
+        assert!(postcard::from_bytes::<Binary>(&bytes).is_err());
+    }
+
+    #[test]
+    fn test_rmp_serde_and_bitcode_roundtrip() {
+        for text in [
+            "",
+            "ferris",
+            "this is a long string that will be on the heap 🦀",
+        ] {
+            let plain = Plain {
+                name: text.into(),
+                bio: text.into(),
+            };
+            let binary = Binary {
+                name: text.into(),
+                bio: text.into(),
+            };
+
+            let bytes = rmp_serde::to_vec(&plain).unwrap();
+            assert_eq!(rmp_serde::from_slice::<Plain>(&bytes).unwrap(), plain);
+            let read: Plain = rmp_serde::from_read(bytes.as_slice()).unwrap();
+            assert_eq!(read, plain);
+
+            // MessagePack has separate str and bin types, either can be read as a string
+            let bytes = rmp_serde::to_vec(&binary).unwrap();
+            assert_eq!(rmp_serde::from_slice::<Binary>(&bytes).unwrap(), binary);
+            assert_eq!(rmp_serde::from_slice::<Plain>(&bytes).unwrap(), plain);
+
+            let bytes = bitcode::serialize(&plain).unwrap();
+            assert_eq!(bitcode::deserialize::<Plain>(&bytes).unwrap(), plain);
+            let bytes = bitcode::serialize(&binary).unwrap();
+            assert_eq!(bitcode::deserialize::<Binary>(&bytes).unwrap(), binary);
+        }
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[proptest]
+    fn proptest_binary_roundtrip(name: String, bio: String) {
+        assert_binary_roundtrip(&name, &bio);
+    }
+
+    struct Lowercase;
+
+    impl crate::Validator for Lowercase {
+        type Error = &'static str;
+
+        fn validate(s: &str) -> Result<(), Self::Error> {
+            if s.chars().all(char::is_lowercase) {
+                Ok(())
+            } else {
+                Err("must be lowercase")
+            }
+        }
+    }
+
+    #[test]
+    fn test_validated() {
+        let ok: crate::Validated<Lowercase> = serde_json::from_str(r#""ferris""#).unwrap();
+        assert_eq!(ok, "ferris");
+        assert_eq!(serde_json::to_string(&ok).unwrap(), r#""ferris""#);
+
+        let err = serde_json::from_str::<crate::Validated<Lowercase>>(r#""Ferris""#)
+            .unwrap_err()
+            .to_string();
+        assert_eq!(err, "must be lowercase");
+    }
+
+    #[test]
+    fn test_bounded_compact_string() {
+        type Username = crate::BoundedCompactString<8>;
+
+        let ok: Username = serde_json::from_str(r#""ferris""#).unwrap();
+        assert_eq!(ok, "ferris");
+        assert_eq!(serde_json::to_string(&ok).unwrap(), r#""ferris""#);
+
+        let err = serde_json::from_str::<Username>(r#""a very long name""#)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("invalid length 16, expected a string of at most 8 bytes"));
+
+        // binary formats check the length before copying too
+        let bytes = bincode::serialize("a very long name").unwrap();
+        assert!(bincode::deserialize::<Username>(&bytes).is_err());
+        let bytes = bincode::serialize("ferris").unwrap();
+        assert_eq!(bincode::deserialize::<Username>(&bytes).unwrap(), "ferris");
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[proptest]
+    fn proptest_bounded(name: String) {
+        let json = serde_json::to_string(&name).unwrap();
+        let result = serde_json::from_str::<Bounded>(&alloc::format!(r#"{{ "name": {} }}"#, json));
+
+        if name.len() <= 24 {
+            assert_eq!(result.unwrap().name, name);
+        } else {
+            assert!(result.is_err());
+        }
+    }
+
     #[cfg_attr(miri, ignore)]
     #[proptest]
     fn proptest_roundtrip(name: String, phones: Vec<String>, address: Option<String>) {
@@ -0,0 +1,107 @@
+//! Conversions between [`CompactString`] and [`serde_json::Value`].
+
+use serde_json::Value;
+
+use crate::CompactString;
+
+/// Converts the [`CompactString`] into a [`Value::String`].
+///
+/// A heap allocated [`CompactString`] hands its buffer over to the `Value`, whereas an inline
+/// one has to be copied into a new allocation, since `Value` stores a [`String`].
+///
+/// [`String`]: alloc::string::String
+#[cfg_attr(docsrs, doc(cfg(feature = "serde_json")))]
+impl From<CompactString> for Value {
+    #[inline]
+    fn from(s: CompactString) -> Self {
+        Value::String(s.into_string())
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "serde_json")))]
+impl<'a> From<&'a CompactString> for Value {
+    #[inline]
+    fn from(s: &'a CompactString) -> Self {
+        Value::String(s.as_str().into())
+    }
+}
+
+/// Converts a [`Value::String`] into a [`CompactString`], re-using the `Value`'s buffer, or
+/// returns the original `Value` if it's not a string.
+#[cfg_attr(docsrs, doc(cfg(feature = "serde_json")))]
+impl TryFrom<Value> for CompactString {
+    type Error = Value;
+
+    #[inline]
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::String(s) => Ok(CompactString::from(s)),
+            other => Err(other),
+        }
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "serde_json")))]
+impl<'a> TryFrom<&'a Value> for CompactString {
+    type Error = &'a Value;
+
+    #[inline]
+    fn try_from(value: &'a Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::String(s) => Ok(CompactString::new(s)),
+            other => Err(other),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+    use test_strategy::proptest;
+
+    use crate::CompactString;
+
+    #[test]
+    fn test_into_value() {
+        let short = CompactString::new("hello");
+        let long = CompactString::new("this is a long string that will be on the heap");
+        let long_ptr = long.as_ptr();
+
+        assert_eq!(Value::from(&short), json!("hello"));
+        assert_eq!(Value::from(short), json!("hello"));
+
+        let value = Value::from(long);
+        match &value {
+            Value::String(s) => assert_eq!(s.as_ptr(), long_ptr),
+            other => panic!("expected a string, got {other:?}"),
+        }
+
+        let tree = json!({ "name": Value::from(CompactString::new("compact_str")) });
+        assert_eq!(tree["name"], "compact_str");
+    }
+
+    #[test]
+    fn test_try_from_value() {
+        let compact = CompactString::try_from(json!("hello")).unwrap();
+        assert_eq!(compact, "hello");
+        assert!(!compact.is_heap_allocated());
+
+        let value = json!("this is a long string that will be on the heap");
+        let compact = CompactString::try_from(&value).unwrap();
+        assert_eq!(value, compact.as_str());
+        let compact = CompactString::try_from(value).unwrap();
+        assert!(compact.is_heap_allocated());
+
+        for value in [json!(null), json!(42), json!(["a"]), json!({ "a": "b" })] {
+            assert_eq!(CompactString::try_from(&value), Err(&value));
+            assert_eq!(CompactString::try_from(value.clone()), Err(value));
+        }
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[proptest]
+    fn proptest_value_roundtrip(s: alloc::string::String) {
+        let value = Value::from(CompactString::from(s.as_str()));
+        assert_eq!(CompactString::try_from(value).unwrap(), s);
+    }
+}
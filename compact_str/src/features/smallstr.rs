@@ -0,0 +1,203 @@
+//! Helpers for migrating from [`smallstr::SmallString`] to [`CompactString`].
+//!
+//! Most of `SmallString`'s API is the same as [`CompactString`]'s, since both mirror [`String`].
+//! For the rest, [`SmallStringCompat`] provides the missing methods, and the table below lists
+//! the equivalents for everything else:
+//!
+//! | `SmallString`             | `CompactString`                                            |
+//! |---------------------------|------------------------------------------------------------|
+//! | `SmallString::from_str`   | [`CompactString::new`]                                     |
+//! | `SmallString::from_buf`   | [`SmallStringCompat::from_buf`]                            |
+//! | `inline_size()`           | [`SmallStringCompat::inline_size`], always [`MAX_INLINE_SIZE`] |
+//! | `spilled()`               | [`CompactString::is_heap_allocated`]                       |
+//! | `grow(new_cap)`           | [`SmallStringCompat::grow`]                                |
+//! | `drain()`                 | [`CompactString::drain`] with `..`                         |
+//! | `drain_range(range)`      | [`CompactString::drain`]                                   |
+//! | `into_string()`           | [`CompactString::into_string`]                             |
+//! | `into_inner()`            | [`CompactString::as_inline_bytes`], there's no owned array |
+//! | `as_mut_vec()`            | [`CompactString::as_mut_bytes`] and [`CompactString::set_len`] |
+//!
+//! `push_str`, `push`, `insert_str`, `truncate`, `retain` and friends behave the same. The one
+//! difference is when they allocate: a `SmallString<[u8; N]>` spills to the heap once it's longer
+//! than `N` bytes, whereas a [`CompactString`] always stores up to [`MAX_INLINE_SIZE`] bytes
+//! inline, and converting a short heap allocated string, e.g. from a [`String`], inlines it.
+//!
+//! [`String`]: alloc::string::String
+//! [`MAX_INLINE_SIZE`]: crate::MAX_INLINE_SIZE
+
+use core::str::Utf8Error;
+
+use smallstr::SmallString;
+use smallvec::Array;
+
+use crate::{CompactString, MAX_INLINE_SIZE};
+
+/// Methods from [`smallstr::SmallString`] that [`CompactString`] doesn't have under the same
+/// name, to make migrating mechanical.
+///
+/// # Examples
+/// ```
+/// use compact_str::smallstr::SmallStringCompat;
+/// use compact_str::CompactString;
+///
+/// let mut s = CompactString::from_buf(*b"hello").unwrap();
+/// assert!(!s.spilled());
+///
+/// s.grow(64);
+/// assert!(s.spilled());
+/// assert!(s.capacity() >= 64);
+/// ```
+pub trait SmallStringCompat: Sized {
+    /// Creates a string from an array of UTF-8 bytes, same as `SmallString::from_buf`.
+    fn from_buf<const N: usize>(buf: [u8; N]) -> Result<Self, Utf8Error>;
+
+    /// Creates a string from an array of bytes without checking that they're valid UTF-8, same
+    /// as `SmallString::from_buf_unchecked`.
+    ///
+    /// # Safety
+    /// * The caller must guarantee that `buf` is valid UTF-8.
+    unsafe fn from_buf_unchecked<const N: usize>(buf: [u8; N]) -> Self;
+
+    /// Returns the number of bytes that can be stored inline, which is always
+    /// [`MAX_INLINE_SIZE`] for a [`CompactString`].
+    fn inline_size(&self) -> usize;
+
+    /// Returns `true` if the string has spilled onto the heap, same as
+    /// [`CompactString::is_heap_allocated`].
+    fn spilled(&self) -> bool;
+
+    /// Grows the capacity of the string to at least `new_cap` bytes.
+    ///
+    /// Unlike `SmallString::grow` this doesn't panic if `new_cap` is less than the length of the
+    /// string, and it never shrinks, use [`CompactString::shrink_to`] for that.
+    fn grow(&mut self, new_cap: usize);
+}
+
+impl SmallStringCompat for CompactString {
+    #[inline]
+    fn from_buf<const N: usize>(buf: [u8; N]) -> Result<Self, Utf8Error> {
+        CompactString::from_utf8(&buf[..])
+    }
+
+    #[inline]
+    unsafe fn from_buf_unchecked<const N: usize>(buf: [u8; N]) -> Self {
+        CompactString::from_utf8_unchecked(&buf[..])
+    }
+
+    #[inline]
+    fn inline_size(&self) -> usize {
+        MAX_INLINE_SIZE
+    }
+
+    #[inline]
+    fn spilled(&self) -> bool {
+        self.is_heap_allocated()
+    }
+
+    #[inline]
+    fn grow(&mut self, new_cap: usize) {
+        self.reserve(new_cap.saturating_sub(self.len()));
+    }
+}
+
+/// Converts a [`SmallString`] into a [`CompactString`], re-using the heap allocation if the
+/// `SmallString` has spilled and the string is too long to be inlined.
+impl<A: Array<Item = u8>> From<SmallString<A>> for CompactString {
+    #[inline]
+    fn from(s: SmallString<A>) -> Self {
+        if s.spilled() {
+            CompactString::from(s.into_string())
+        } else {
+            CompactString::new(s.as_str())
+        }
+    }
+}
+
+/// Converts a [`CompactString`] into a [`SmallString`], re-using the heap allocation if the
+/// [`CompactString`] is heap allocated and too long to fit in the `SmallString`'s inline buffer.
+impl<A: Array<Item = u8>> From<CompactString> for SmallString<A> {
+    #[inline]
+    fn from(s: CompactString) -> Self {
+        if s.len() <= A::size() {
+            SmallString::from_str(s.as_str())
+        } else {
+            SmallString::from_string(s.into_string())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::String;
+
+    use smallstr::SmallString;
+    use test_strategy::proptest;
+
+    use super::SmallStringCompat;
+    use crate::{CompactString, MAX_INLINE_SIZE};
+
+    #[test]
+    fn test_compat_methods() {
+        let s = CompactString::from_buf(*b"hello").unwrap();
+        assert_eq!(s, "hello");
+        assert_eq!(s.inline_size(), MAX_INLINE_SIZE);
+        assert!(!s.spilled());
+
+        assert!(CompactString::from_buf([0xFF, 0xFE]).is_err());
+        let s = unsafe { CompactString::from_buf_unchecked(*b"world") };
+        assert_eq!(s, "world");
+
+        let mut s = CompactString::new("grow me");
+        s.grow(3);
+        assert!(!s.spilled());
+        s.grow(100);
+        assert!(s.spilled());
+        assert!(s.capacity() >= 100);
+        assert_eq!(s, "grow me");
+    }
+
+    #[test]
+    fn test_from_small_string() {
+        let long = "this is a long string that will be on the heap";
+
+        let small: SmallString<[u8; 8]> = SmallString::from_str(long);
+        assert!(small.spilled());
+        let ptr = small.as_ptr();
+        let compact = CompactString::from(small);
+        assert_eq!(compact, long);
+        assert_eq!(compact.as_ptr(), ptr);
+
+        // spilled, but short enough that we inline it
+        let small: SmallString<[u8; 4]> = SmallString::from_str("hello");
+        assert!(small.spilled());
+        let compact = CompactString::from(small);
+        assert_eq!(compact, "hello");
+        assert!(!compact.is_heap_allocated());
+    }
+
+    #[test]
+    fn test_into_small_string() {
+        let long = "this is a long string that will be on the heap";
+
+        let compact = CompactString::new(long);
+        let ptr = compact.as_ptr();
+        let small: SmallString<[u8; 16]> = SmallString::from(compact);
+        assert_eq!(small, long);
+        assert_eq!(small.as_ptr(), ptr);
+
+        let small: SmallString<[u8; 16]> = SmallString::from(CompactString::new("hello"));
+        assert_eq!(small, "hello");
+        assert!(!small.spilled());
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[proptest]
+    fn proptest_roundtrip(s: String) {
+        let small: SmallString<[u8; 8]> = SmallString::from_str(&s);
+        let compact = CompactString::from(small);
+        assert_eq!(compact, s);
+
+        let small: SmallString<[u8; 8]> = SmallString::from(compact);
+        assert_eq!(small.as_str(), s);
+    }
+}
@@ -0,0 +1,259 @@
+//! Formatting helpers for durations and timestamps that produce short, human-readable
+//! [`CompactString`]s, e.g. for metrics and log lines.
+//!
+//! Everything here fits inline on 64-bit targets, so formatting never allocates.
+//!
+//! # Examples
+//! ```
+//! use core::time::Duration;
+//!
+//! use compact_str::time::{format_duration_compact, format_rfc3339_unix};
+//!
+//! assert_eq!(format_duration_compact(Duration::from_micros(1_250)), "1.2ms");
+//! assert_eq!(format_duration_compact(Duration::from_secs(3)), "3s");
+//! assert_eq!(format_rfc3339_unix(1_700_000_000).unwrap(), "2023-11-14T22:13:20Z");
+//! ```
+
+use core::time::Duration;
+#[cfg(feature = "std")]
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::CompactString;
+
+/// Units to format a duration with, from largest to smallest, along with their length in
+/// nanoseconds.
+const DURATION_UNITS: [(u128, &str); 6] = [
+    (3_600_000_000_000, "h"),
+    (60_000_000_000, "m"),
+    (1_000_000_000, "s"),
+    (1_000_000, "ms"),
+    (1_000, "µs"),
+    (1, "ns"),
+];
+
+/// Formats a [`Duration`] in the largest unit it's at least one of, with at most one decimal
+/// place, e.g. `"1.2ms"`, `"3s"`, or `"1.5h"`.
+///
+/// The units used are `h`, `m`, `s`, `ms`, `µs`, and `ns`. The decimal place is truncated, not
+/// rounded, so a value never gets bumped up to the next unit, and it's omitted when it's zero. A
+/// zero duration is formatted as `"0s"`.
+///
+/// # Examples
+/// ```
+/// use core::time::Duration;
+///
+/// use compact_str::time::format_duration_compact;
+///
+/// assert_eq!(format_duration_compact(Duration::ZERO), "0s");
+/// assert_eq!(format_duration_compact(Duration::from_nanos(999)), "999ns");
+/// assert_eq!(format_duration_compact(Duration::from_nanos(999_999)), "999.9µs");
+/// assert_eq!(format_duration_compact(Duration::from_millis(1_500)), "1.5s");
+/// assert_eq!(format_duration_compact(Duration::from_secs(90)), "1.5m");
+/// assert_eq!(format_duration_compact(Duration::from_secs(7_200)), "2h");
+/// ```
+pub fn format_duration_compact(duration: Duration) -> CompactString {
+    let nanos = duration.as_nanos();
+    if nanos == 0 {
+        return CompactString::const_new("0s");
+    }
+
+    let (unit_nanos, unit) = DURATION_UNITS
+        .iter()
+        .copied()
+        .find(|(unit_nanos, _)| nanos >= *unit_nanos)
+        .unwrap_or(DURATION_UNITS[DURATION_UNITS.len() - 1]);
+    let whole = nanos / unit_nanos;
+    let tenths = (nanos % unit_nanos) * 10 / unit_nanos;
+
+    let mut buf = itoa::Buffer::new();
+    let mut out = CompactString::const_new("");
+    out.push_str(buf.format(whole));
+    if tenths != 0 {
+        out.push('.');
+        out.push(char::from(b'0' + tenths as u8));
+    }
+    out.push_str(unit);
+    out
+}
+
+/// Formats a Unix timestamp, in seconds, as an [RFC 3339] timestamp in UTC with second
+/// precision, e.g. `"2023-11-14T22:13:20Z"`.
+///
+/// Returns `None` if the year falls outside of `0000` to `9999`, which RFC 3339 can't represent.
+///
+/// # Examples
+/// ```
+/// use compact_str::time::format_rfc3339_unix;
+///
+/// assert_eq!(format_rfc3339_unix(0).unwrap(), "1970-01-01T00:00:00Z");
+/// assert_eq!(format_rfc3339_unix(-1).unwrap(), "1969-12-31T23:59:59Z");
+/// assert_eq!(format_rfc3339_unix(i64::MAX), None);
+/// ```
+///
+/// [RFC 3339]: https://www.rfc-editor.org/rfc/rfc3339
+pub fn format_rfc3339_unix(secs: i64) -> Option<CompactString> {
+    let days = secs.div_euclid(86_400);
+    let secs_of_day = secs.rem_euclid(86_400) as u32;
+    let (year, month, day) = civil_from_days(days);
+    if !(0..=9999).contains(&year) {
+        return None;
+    }
+
+    let mut buf = *b"0000-00-00T00:00:00Z";
+    write_digits(&mut buf[0..4], year as u32);
+    write_digits(&mut buf[5..7], month);
+    write_digits(&mut buf[8..10], day);
+    write_digits(&mut buf[11..13], secs_of_day / 3_600);
+    write_digits(&mut buf[14..16], secs_of_day / 60 % 60);
+    write_digits(&mut buf[17..19], secs_of_day % 60);
+
+    // SAFETY: The buffer only contains ASCII characters.
+    Some(unsafe { CompactString::from_utf8_unchecked(buf) })
+}
+
+/// Formats a [`SystemTime`] as an [RFC 3339] timestamp in UTC with second precision, e.g.
+/// `"2023-11-14T22:13:20Z"`. Sub-second precision is truncated.
+///
+/// Returns `None` if the year falls outside of `0000` to `9999`, which RFC 3339 can't represent.
+///
+/// # Examples
+/// ```
+/// use std::time::{Duration, UNIX_EPOCH};
+///
+/// use compact_str::time::format_rfc3339;
+///
+/// let time = UNIX_EPOCH + Duration::from_millis(1_700_000_000_999);
+/// assert_eq!(format_rfc3339(time).unwrap(), "2023-11-14T22:13:20Z");
+/// ```
+///
+/// [RFC 3339]: https://www.rfc-editor.org/rfc/rfc3339
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub fn format_rfc3339(time: SystemTime) -> Option<CompactString> {
+    let secs = match time.duration_since(UNIX_EPOCH) {
+        Ok(after) => i64::try_from(after.as_secs()).ok()?,
+        Err(err) => {
+            // Truncate towards the past, e.g. 0.5 seconds before the epoch is 1969-12-31T23:59:59
+            let before = err.duration();
+            let secs = i64::try_from(before.as_secs()).ok()?;
+            let secs = if before.subsec_nanos() != 0 {
+                secs.checked_add(1)?
+            } else {
+                secs
+            };
+            -secs
+        }
+    };
+    format_rfc3339_unix(secs)
+}
+
+/// Writes `value` as zero padded decimal digits, filling all of `buf`.
+fn write_digits(buf: &mut [u8], mut value: u32) {
+    for byte in buf.iter_mut().rev() {
+        *byte = b'0' + (value % 10) as u8;
+        value /= 10;
+    }
+}
+
+/// Converts a number of days since 1970-01-01 into a `(year, month, day)` in the proleptic
+/// Gregorian calendar.
+///
+/// See <http://howardhinnant.github.io/date_algorithms.html#civil_from_days>.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+    use std::time::UNIX_EPOCH;
+
+    use test_strategy::proptest;
+
+    use super::{format_duration_compact, format_rfc3339, format_rfc3339_unix, DURATION_UNITS};
+
+    #[test]
+    fn test_format_duration_compact() {
+        let cases = [
+            (Duration::ZERO, "0s"),
+            (Duration::from_nanos(1), "1ns"),
+            (Duration::from_nanos(1_999), "1.9µs"),
+            (Duration::from_micros(1_200), "1.2ms"),
+            (Duration::from_millis(999), "999ms"),
+            (Duration::from_secs(3), "3s"),
+            (Duration::from_secs(59), "59s"),
+            (Duration::from_secs(60), "1m"),
+            (Duration::from_secs(3_599), "59.9m"),
+            (Duration::from_secs(3_600 * 36), "36h"),
+            (Duration::MAX, "5124095576030431h"),
+        ];
+        for (duration, expected) in cases {
+            let formatted = format_duration_compact(duration);
+            assert_eq!(formatted, expected);
+            #[cfg(target_pointer_width = "64")]
+            assert!(!formatted.is_heap_allocated());
+        }
+    }
+
+    #[test]
+    fn test_format_rfc3339() {
+        assert_eq!(
+            format_rfc3339_unix(951_782_400).unwrap(),
+            "2000-02-29T00:00:00Z"
+        );
+        assert_eq!(
+            format_rfc3339_unix(253_402_300_799).unwrap(),
+            "9999-12-31T23:59:59Z"
+        );
+        assert_eq!(format_rfc3339_unix(253_402_300_800), None);
+        assert_eq!(
+            format_rfc3339_unix(-62_167_219_200).unwrap(),
+            "0000-01-01T00:00:00Z"
+        );
+        assert_eq!(format_rfc3339_unix(-62_167_219_201), None);
+        assert_eq!(format_rfc3339_unix(i64::MIN), None);
+
+        assert_eq!(
+            format_rfc3339(UNIX_EPOCH - Duration::from_millis(500)).unwrap(),
+            "1969-12-31T23:59:59Z"
+        );
+        assert_eq!(
+            format_rfc3339(UNIX_EPOCH - Duration::from_secs(1)).unwrap(),
+            "1969-12-31T23:59:59Z"
+        );
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[proptest]
+    fn proptest_format_duration_compact(nanos: u64) {
+        let formatted = format_duration_compact(Duration::from_nanos(nanos));
+
+        let split = formatted
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .unwrap();
+        let (number, unit) = formatted.split_at(split);
+        let unit_nanos = DURATION_UNITS
+            .iter()
+            .find(|(_, name)| *name == unit)
+            .map(|(unit_nanos, _)| *unit_nanos as u64)
+            .unwrap();
+        let (whole, tenths) = number.split_once('.').unwrap_or((number, "0"));
+        let whole: u64 = whole.parse().unwrap();
+        let tenths: u64 = tenths.parse().unwrap();
+
+        // the formatted value is a truncation of the actual duration
+        let lower = whole * unit_nanos + tenths * unit_nanos / 10;
+        let upper = lower + (unit_nanos / 10).max(1);
+        assert!(lower <= nanos && nanos < upper);
+        assert!(whole >= 1 || nanos == 0);
+    }
+}
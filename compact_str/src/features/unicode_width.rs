@@ -0,0 +1,53 @@
+//! Implements display width calculations for [`CompactString`] via the [`unicode_width`] crate
+
+use unicode_width::UnicodeWidthStr;
+
+use crate::CompactString;
+
+impl CompactString {
+    /// Returns the displayed width of the [`CompactString`] in columns, as defined by
+    /// [`UnicodeWidthStr::width`].
+    ///
+    /// # Example
+    /// ```
+    /// use compact_str::CompactString;
+    ///
+    /// let s = CompactString::new("hello");
+    /// assert_eq!(s.width(), 5);
+    ///
+    /// // full-width characters take up two columns
+    /// let s = CompactString::new("ｈｅｌｌｏ");
+    /// assert_eq!(s.width(), 10);
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "unicode-width")))]
+    #[inline]
+    pub fn width(&self) -> usize {
+        UnicodeWidthStr::width(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::String;
+
+    use test_strategy::proptest;
+    use unicode_width::UnicodeWidthStr;
+
+    use crate::tests::rand_unicode;
+    use crate::CompactString;
+
+    #[test]
+    fn test_width() {
+        assert_eq!(CompactString::new("").width(), 0);
+        assert_eq!(CompactString::new("abc").width(), 3);
+        assert_eq!(CompactString::new("🦀🦀").width(), 4);
+        assert_eq!(CompactString::new("e\u{301}").width(), 1);
+    }
+
+    #[proptest]
+    #[cfg_attr(miri, ignore)]
+    fn proptest_width(#[strategy(rand_unicode())] s: String) {
+        let compact = CompactString::new(&s);
+        assert_eq!(compact.width(), s.width());
+    }
+}
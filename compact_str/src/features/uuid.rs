@@ -0,0 +1,110 @@
+//! Conversions from [`Uuid`] and its formatters into [`CompactString`].
+//!
+//! Use [`CompactString::from`] for the fast path. [`ToCompactString::to_compact_string()`] can't
+//! specialize on [`Uuid`], so `uuid.to_compact_string()` still formats it with
+//! [`Display`](core::fmt::Display).
+//!
+//! [`ToCompactString::to_compact_string()`]: crate::ToCompactString::to_compact_string
+
+use uuid::fmt::{Braced, Hyphenated, Simple, Urn};
+use uuid::Uuid;
+
+use crate::CompactString;
+
+/// Encodes a UUID directly into the buffer of a new [`CompactString`], skipping the [`fmt`]
+/// machinery and any intermediate copies.
+///
+/// [`fmt`]: core::fmt
+fn encode(len: usize, encode_lower: impl FnOnce(&mut [u8]) -> &mut str) -> CompactString {
+    let mut s = CompactString::with_capacity(len);
+    // SAFETY: `encode_lower` writes exactly `len` bytes of ASCII into the buffer, which we then
+    // mark as initialized by setting the length
+    unsafe {
+        let written = encode_lower(&mut s.as_mut_bytes()[..len]).len();
+        s.set_len(written);
+    }
+    s
+}
+
+macro_rules! impl_from_uuid_fmt {
+    ($($ty:ty),*) => {
+        $(
+            #[cfg_attr(docsrs, doc(cfg(feature = "uuid")))]
+            impl From<$ty> for CompactString {
+                #[inline]
+                fn from(uuid: $ty) -> Self {
+                    encode(<$ty>::LENGTH, |buf| uuid.encode_lower(buf))
+                }
+            }
+        )*
+    };
+}
+
+impl_from_uuid_fmt!(Hyphenated, Simple, Urn, Braced);
+
+/// Encodes the [`Uuid`] in its hyphenated form, the same as its [`Display`](core::fmt::Display)
+/// impl, e.g. `67e55044-10b1-426f-9247-bb680e5fe0c8`. To pick a different format convert from
+/// [`Uuid::simple()`], [`Uuid::urn()`], or [`Uuid::braced()`] instead.
+///
+/// Prefer this over `uuid.to_compact_string()`, which goes through `Display`.
+#[cfg_attr(docsrs, doc(cfg(feature = "uuid")))]
+impl From<Uuid> for CompactString {
+    #[inline]
+    fn from(uuid: Uuid) -> Self {
+        CompactString::from(uuid.hyphenated())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use test_strategy::proptest;
+    use uuid::Uuid;
+
+    use crate::{CompactString, ToCompactString};
+
+    #[test]
+    fn test_uuid() {
+        let uuid = Uuid::from_u128(0x67e5504410b1426f9247bb680e5fe0c8);
+
+        let compact = CompactString::from(uuid);
+        assert_eq!(compact, "67e55044-10b1-426f-9247-bb680e5fe0c8");
+        // `to_compact_string()` goes through `Display`, but has to agree
+        assert_eq!(compact, uuid.to_compact_string());
+        // sized exactly, without growing as it's written
+        assert_eq!(
+            compact.capacity(),
+            CompactString::with_capacity(uuid::fmt::Hyphenated::LENGTH).capacity()
+        );
+
+        assert_eq!(
+            CompactString::from(uuid.simple()),
+            "67e5504410b1426f9247bb680e5fe0c8"
+        );
+        assert_eq!(
+            CompactString::from(uuid.urn()),
+            "urn:uuid:67e55044-10b1-426f-9247-bb680e5fe0c8"
+        );
+        assert_eq!(
+            CompactString::from(uuid.braced()),
+            "{67e55044-10b1-426f-9247-bb680e5fe0c8}"
+        );
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[proptest]
+    fn proptest_uuid_matches_display(val: u128) {
+        let uuid = Uuid::from_u128(val);
+        assert_eq!(CompactString::from(uuid), uuid.to_string());
+        assert_eq!(
+            CompactString::from(uuid.simple()),
+            uuid.simple().to_string()
+        );
+        assert_eq!(CompactString::from(uuid.urn()), uuid.urn().to_string());
+        assert_eq!(
+            CompactString::from(uuid.braced()),
+            uuid.braced().to_string()
+        );
+    }
+}
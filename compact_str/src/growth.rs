@@ -0,0 +1,94 @@
+//! Metadata about how a [`CompactString`](crate::CompactString) picks its capacity.
+
+use crate::repr::{amortized_growth, size_class, MIN_HEAP_SIZE};
+use crate::MAX_INLINE_SIZE;
+
+/// Describes how a [`CompactString`](crate::CompactString) picks its capacity, returned by
+/// [`CompactString::growth_policy()`](crate::CompactString::growth_policy).
+///
+/// When a string outgrows its buffer, the new heap allocation gets a capacity of
+///
+/// ```text
+/// max(min_heap_capacity, len + additional, len * 3 / 2)
+/// ```
+///
+/// bytes, where `len` is the current length of the string and `additional` the number of bytes
+/// being added, i.e. heap allocated strings grow by 1.5x. Strings of up to `inline_capacity` bytes
+/// are stored inline, and report that as their capacity.
+///
+/// [`CompactString::with_capacity_size_class()`] and [`CompactString::reserve_size_class()`]
+/// additionally round that capacity up to the size class an allocator like jemalloc or mimalloc
+/// would serve it from, see [`GrowthPolicy::size_class`]. No other method does.
+///
+/// The exact numbers are not part of our semver guarantees, and may be tuned in any release. The
+/// only guarantee is that a string has at least the capacity that was asked for. Tests that check
+/// [`capacity()`](crate::CompactString::capacity) should compute the expected value with this
+/// policy instead of hard coding it. Note that capacities which come from elsewhere, e.g.
+/// converting from a [`String`], are kept as is.
+///
+/// [`String`]: alloc::string::String
+/// [`CompactString::with_capacity_size_class()`]: crate::CompactString::with_capacity_size_class
+/// [`CompactString::reserve_size_class()`]: crate::CompactString::reserve_size_class
+///
+/// # Examples
+/// ```
+/// use compact_str::CompactString;
+///
+/// let policy = CompactString::growth_policy();
+///
+/// let mut s = CompactString::new("a".repeat(policy.inline_capacity()));
+/// assert_eq!(s.capacity(), policy.inline_capacity());
+///
+/// s.push('b');
+/// assert_eq!(s.capacity(), policy.grow(policy.inline_capacity(), 1));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GrowthPolicy {
+    _priv: (),
+}
+
+impl GrowthPolicy {
+    pub(crate) const CURRENT: GrowthPolicy = GrowthPolicy { _priv: () };
+
+    /// The number of bytes that can be stored inline, equal to [`MAX_INLINE_SIZE`].
+    #[inline]
+    pub const fn inline_capacity(&self) -> usize {
+        MAX_INLINE_SIZE
+    }
+
+    /// The smallest capacity we'll allocate on the heap.
+    #[inline]
+    pub const fn min_heap_capacity(&self) -> usize {
+        MIN_HEAP_SIZE
+    }
+
+    /// Returns the capacity that a heap allocation gets when a string of `len` bytes grows to fit
+    /// `additional` more bytes.
+    #[inline]
+    pub fn grow(&self, len: usize, additional: usize) -> usize {
+        amortized_growth(len, additional).max(MIN_HEAP_SIZE)
+    }
+
+    /// Rounds `capacity` up to a common allocator size class: multiples of 16 bytes up to 128,
+    /// and above that four classes per power of two, e.g. 160, 192, 224, 256, 320, ...
+    ///
+    /// This is the capacity [`CompactString::with_capacity_size_class()`] and
+    /// [`CompactString::reserve_size_class()`] pick for heap allocations.
+    ///
+    /// [`CompactString::with_capacity_size_class()`]: crate::CompactString::with_capacity_size_class
+    /// [`CompactString::reserve_size_class()`]: crate::CompactString::reserve_size_class
+    ///
+    /// # Examples
+    /// ```
+    /// use compact_str::CompactString;
+    ///
+    /// let policy = CompactString::growth_policy();
+    /// assert_eq!(policy.size_class(100), 112);
+    /// assert_eq!(policy.size_class(150), 160);
+    /// assert_eq!(policy.size_class(256), 256);
+    /// ```
+    #[inline]
+    pub const fn size_class(&self, capacity: usize) -> usize {
+        size_class(capacity)
+    }
+}
@@ -0,0 +1,216 @@
+//! An order-preserving binary encoding of [`CompactString`]s, for using them as keys in ordered
+//! key-value stores like RocksDB or LMDB.
+//!
+//! Encoded strings compare the same way, byte-wise, as the strings they came from, and since every
+//! encoding is self-delimiting, several of them can be concatenated to form a composite key.
+//!
+//! The layout: every `0x00` byte in the string is escaped as `0x00 0xFF`, and the string is
+//! terminated with `0x00 0x00`. Because the terminator sorts before any other byte, a string sorts
+//! before all of the strings that it is a prefix of.
+//!
+//! # Example
+//! ```
+//! use compact_str::CompactString;
+//!
+//! let mut key = Vec::new();
+//! CompactString::new("users").to_ordered_bytes(&mut key);
+//! CompactString::new("ferris").to_ordered_bytes(&mut key);
+//!
+//! let (table, rest) = CompactString::from_ordered_bytes(&key).unwrap();
+//! let (name, rest) = CompactString::from_ordered_bytes(rest).unwrap();
+//! assert_eq!((table.as_str(), name.as_str()), ("users", "ferris"));
+//! assert!(rest.is_empty());
+//! ```
+
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::Utf8Error;
+
+use crate::CompactString;
+
+const ESCAPE: u8 = 0x00;
+const ESCAPED_NULL: u8 = 0xFF;
+const TERMINATOR: u8 = 0x00;
+
+impl CompactString {
+    /// Appends the order-preserving encoding of this string onto `out`, see the [`keys`](crate::keys)
+    /// module for details.
+    ///
+    /// # Examples
+    /// ```
+    /// use compact_str::CompactString;
+    ///
+    /// let mut a = Vec::new();
+    /// CompactString::new("app").to_ordered_bytes(&mut a);
+    /// let mut b = Vec::new();
+    /// CompactString::new("apple").to_ordered_bytes(&mut b);
+    ///
+    /// assert!(a < b);
+    /// assert_eq!(a, b"app\x00\x00");
+    /// ```
+    pub fn to_ordered_bytes(&self, out: &mut Vec<u8>) {
+        out.reserve(self.len() + 2);
+
+        let mut segments = self.split_byte(ESCAPE);
+        if let Some(first) = segments.next() {
+            out.extend_from_slice(first.as_bytes());
+        }
+        for segment in segments {
+            out.extend_from_slice(&[ESCAPE, ESCAPED_NULL]);
+            out.extend_from_slice(segment.as_bytes());
+        }
+
+        out.extend_from_slice(&[ESCAPE, TERMINATOR]);
+    }
+
+    /// Decodes a string that was encoded with [`CompactString::to_ordered_bytes`] from the start
+    /// of `buf`, returning it along with the remainder of the buffer.
+    ///
+    /// The bytes are copied straight into the returned [`CompactString`], so short keys never
+    /// allocate.
+    ///
+    /// # Examples
+    /// ```
+    /// use compact_str::CompactString;
+    ///
+    /// let (key, rest) = CompactString::from_ordered_bytes(b"a\x00\xFFb\x00\x00tail").unwrap();
+    ///
+    /// assert_eq!(key, "a\0b");
+    /// assert!(!key.is_heap_allocated());
+    /// assert_eq!(rest, b"tail");
+    /// ```
+    pub fn from_ordered_bytes(mut buf: &[u8]) -> Result<(CompactString, &[u8]), KeyError> {
+        let mut key = CompactString::const_new("");
+
+        loop {
+            let idx = crate::find_byte(ESCAPE, buf).ok_or(KeyError::UnexpectedEof)?;
+
+            // Note: 0x00 never appears within a multi-byte UTF-8 sequence, so each segment between
+            // escapes has to be valid UTF-8 on its own
+            let segment = core::str::from_utf8(&buf[..idx]).map_err(KeyError::Utf8)?;
+            key.push_str(segment);
+
+            match buf.get(idx + 1) {
+                Some(&TERMINATOR) => return Ok((key, &buf[idx + 2..])),
+                Some(&ESCAPED_NULL) => key.push('\0'),
+                Some(_) => return Err(KeyError::InvalidEscape),
+                None => return Err(KeyError::UnexpectedEof),
+            }
+            buf = &buf[idx + 2..];
+        }
+    }
+}
+
+/// A possible error value when decoding a key with [`CompactString::from_ordered_bytes()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum KeyError {
+    /// The buffer ended before the terminator
+    UnexpectedEof,
+    /// A `0x00` byte was followed by something other than `0xFF` or `0x00`
+    InvalidEscape,
+    /// The key was not valid UTF-8
+    Utf8(Utf8Error),
+}
+
+impl fmt::Display for KeyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeyError::UnexpectedEof => f.write_str("unexpected end of ordered key"),
+            KeyError::InvalidEscape => f.write_str("invalid escape sequence in ordered key"),
+            KeyError::Utf8(err) => err.fmt(f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl std::error::Error for KeyError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            KeyError::Utf8(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::String;
+    use alloc::vec::Vec;
+
+    use proptest::prelude::*;
+    use test_strategy::proptest;
+
+    use super::KeyError;
+    use crate::CompactString;
+
+    fn encode(s: &str) -> Vec<u8> {
+        let mut out = Vec::new();
+        CompactString::new(s).to_ordered_bytes(&mut out);
+        out
+    }
+
+    #[test]
+    fn test_layout() {
+        assert_eq!(encode(""), [0, 0]);
+        assert_eq!(encode("a\0"), [b'a', 0, 0xFF, 0, 0]);
+        assert_eq!(encode("\0\0"), [0, 0xFF, 0, 0xFF, 0, 0]);
+    }
+
+    #[test]
+    fn test_ordering() {
+        let mut keys = ["b", "", "a\0", "a", "\0", "ab", "a\u{1}"];
+        let mut encoded: Vec<Vec<u8>> = keys.iter().map(|k| encode(k)).collect();
+        keys.sort();
+        encoded.sort();
+
+        let decoded: Vec<CompactString> = encoded
+            .iter()
+            .map(|e| CompactString::from_ordered_bytes(e).unwrap().0)
+            .collect();
+        assert_eq!(decoded, keys);
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            CompactString::from_ordered_bytes(b"abc").unwrap_err(),
+            KeyError::UnexpectedEof
+        );
+        assert_eq!(
+            CompactString::from_ordered_bytes(b"abc\x00").unwrap_err(),
+            KeyError::UnexpectedEof
+        );
+        assert_eq!(
+            CompactString::from_ordered_bytes(b"abc\x00\x01").unwrap_err(),
+            KeyError::InvalidEscape
+        );
+        assert!(matches!(
+            CompactString::from_ordered_bytes(b"\xF0\x9F\x00\x00").unwrap_err(),
+            KeyError::Utf8(_)
+        ));
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[proptest]
+    fn proptest_roundtrip(a: String, b: String) {
+        let mut buf = encode(&a);
+        CompactString::new(&b).to_ordered_bytes(&mut buf);
+
+        let (decoded_a, rest) = CompactString::from_ordered_bytes(&buf).unwrap();
+        let (decoded_b, rest) = CompactString::from_ordered_bytes(rest).unwrap();
+        prop_assert_eq!(decoded_a, a);
+        prop_assert_eq!(decoded_b, b);
+        prop_assert!(rest.is_empty());
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[proptest]
+    fn proptest_preserves_order(
+        #[strategy("[\\x00-\\x02a-b🦀]{0,6}")] a: String,
+        #[strategy("[\\x00-\\x02a-b🦀]{0,6}")] b: String,
+    ) {
+        prop_assert_eq!(a.cmp(&b), encode(&a).cmp(&encode(&b)));
+    }
+}
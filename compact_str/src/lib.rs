@@ -12,31 +12,80 @@ extern crate alloc;
 use alloc::borrow::Cow;
 use alloc::boxed::Box;
 use alloc::string::String;
+use alloc::vec::Vec;
 #[doc(hidden)] // Referenced in macros.
 pub use core;
 use core::borrow::{Borrow, BorrowMut};
 use core::cmp::Ordering;
 use core::hash::{Hash, Hasher};
 use core::iter::FusedIterator;
+use core::num::NonZeroU8;
 use core::ops::{Add, AddAssign, Bound, Deref, DerefMut, RangeBounds};
 use core::str::{FromStr, Utf8Error};
-use core::{fmt, mem, slice};
+use core::{fmt, mem, ptr, slice};
 #[cfg(feature = "std")]
 use std::ffi::OsStr;
 
 mod features;
+#[cfg(feature = "clap")]
+pub use features::clap::CompactStringValueParser;
+#[cfg(feature = "clone-tracking")]
+#[cfg_attr(docsrs, doc(cfg(feature = "clone-tracking")))]
+pub use features::clone_tracking;
+#[cfg(feature = "csv")]
+#[cfg_attr(docsrs, doc(cfg(feature = "csv")))]
+pub use features::csv;
+#[cfg(feature = "rand")]
+pub use features::rand::DistCompactString;
+#[cfg(feature = "regex")]
+#[cfg_attr(docsrs, doc(cfg(feature = "regex")))]
+pub use features::regex;
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+pub use features::serde;
+#[cfg(feature = "smallstr-compat")]
+#[cfg_attr(docsrs, doc(cfg(feature = "smallstr-compat")))]
+pub use features::smallstr;
+#[cfg(feature = "time")]
+#[cfg_attr(docsrs, doc(cfg(feature = "time")))]
+pub use features::time;
 mod macros;
+#[doc(hidden)] // Referenced in macros.
+pub use macros::__private;
 mod unicode_data;
 
 mod repr;
 use repr::Repr;
 
 mod traits;
-pub use traits::{CompactStringExt, ToCompactString};
+pub use traits::{
+    from_display, AsCompactStr, CollectTruncated, CompactStringExt, DisplayJoin, ToCompactString,
+    ToCompactStringPadded, ToCompactStringRadix,
+};
+
+mod growth;
+pub use growth::GrowthPolicy;
+
+mod column;
+pub use column::{CompactStringColumn, CompactStringColumnIter};
+
+mod str_table;
+pub use str_table::{CompactStrRef, StrTable, StrTableError, StrTableIter};
+
+pub mod keys;
+
+mod validated;
+pub use validated::{BoundedCompactString, MaxLen, TooLongError, Validated, Validator};
 
 #[cfg(test)]
 mod tests;
 
+/// The maximum number of bytes a [`CompactString`] can store inline, without allocating.
+///
+/// This is equal to `size_of::<String>()`, i.e. 24 bytes on 64-bit platforms and 12 bytes on
+/// 32-bit platforms.
+pub const MAX_INLINE_SIZE: usize = repr::MAX_SIZE;
+
 /// A [`CompactString`] is a compact string type that can be used almost anywhere a
 /// [`String`] or [`str`] can be used.
 ///
@@ -195,6 +244,79 @@ impl CompactString {
         Repr::new(text.as_ref()).map(CompactString)
     }
 
+    /// Creates a new [`CompactString`] that is always heap allocated, regardless of the length of
+    /// `text`.
+    ///
+    /// Note: You probably don't need to use this method, [`CompactString::new()`] will inline short
+    /// strings which is almost always what you want. This exists so tests and benchmarks can
+    /// deterministically exercise the heap allocated representation. Operations that create a new
+    /// [`CompactString`], e.g. [`Clone`], may still inline the result.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_str::CompactString;
+    /// let compact = CompactString::new_heap("hello");
+    ///
+    /// assert_eq!(compact, "hello");
+    /// assert!(compact.is_heap_allocated());
+    /// ```
+    #[inline]
+    #[track_caller]
+    pub fn new_heap<T: AsRef<str>>(text: T) -> Self {
+        Self::try_new_heap(text).unwrap_with_msg()
+    }
+
+    /// Fallible version of [`CompactString::new_heap()`]
+    ///
+    /// This method won't panic if the system is out-of-memory, but return an [`ReserveError`].
+    /// Otherwise it behaves the same as [`CompactString::new_heap()`].
+    #[inline]
+    pub fn try_new_heap<T: AsRef<str>>(text: T) -> Result<Self, ReserveError> {
+        Repr::new_heap(text.as_ref()).map(CompactString)
+    }
+
+    /// Creates a new inline [`CompactString`], or returns `None` if `text` is longer than
+    /// [`MAX_INLINE_SIZE`] bytes, instead of allocating.
+    ///
+    /// This never touches the allocator, so latency sensitive code can use it and explicitly
+    /// decide what to do with long strings, e.g. truncate or intern them. For `&'static str`s known
+    /// at compile time, see [`CompactString::const_new()`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_str::CompactString;
+    /// let short = CompactString::new_if_inline("hello").unwrap();
+    /// assert_eq!(short, "hello");
+    /// assert!(!short.is_heap_allocated());
+    ///
+    /// let long = "I am a longer string that would be allocated on the heap";
+    /// assert_eq!(CompactString::new_if_inline(long), None);
+    /// ```
+    #[inline]
+    pub fn new_if_inline(text: &str) -> Option<Self> {
+        Repr::new_inline(text).map(CompactString)
+    }
+
+    /// Returns an iterator over the lines of `text`, as owned [`CompactString`]s.
+    ///
+    /// Lines are split the same way as [`str::lines`], i.e. on `\n` or `\r\n`, and the line
+    /// endings are not included. Each line is created with [`CompactString::new`], so short lines
+    /// get inlined and don't allocate.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_str::CompactString;
+    /// let text = "foo\r\nbar\n\nbaz\n";
+    /// let lines: Vec<CompactString> = CompactString::lines_compact(text).collect();
+    ///
+    /// assert_eq!(lines, ["foo", "bar", "", "baz"]);
+    /// assert!(lines.iter().all(|line| !line.is_heap_allocated()));
+    /// ```
+    #[inline]
+    pub fn lines_compact(text: &str) -> LinesCompact<'_> {
+        LinesCompact(text.lines())
+    }
+
     /// Creates a new inline [`CompactString`] from `&'static str` at compile time.
     /// Complexity: O(1). As an optimization, short strings get inlined.
     ///
@@ -211,6 +333,52 @@ impl CompactString {
         CompactString(Repr::const_new(text))
     }
 
+    /// Creates a new inline [`CompactString`] containing a single [`char`], usable in `const`
+    /// contexts.
+    ///
+    /// # Examples
+    /// ```
+    /// use compact_str::CompactString;
+    ///
+    /// const SEPARATORS: [CompactString; 3] = [
+    ///     CompactString::from_char('/'),
+    ///     CompactString::from_char('·'),
+    ///     CompactString::from_char('🦀'),
+    /// ];
+    ///
+    /// assert_eq!(SEPARATORS[1], "·");
+    /// assert!(!SEPARATORS[2].is_heap_allocated());
+    /// ```
+    #[inline]
+    pub const fn from_char(c: char) -> Self {
+        CompactString(Repr::from_char(c))
+    }
+
+    /// Creates a new inline [`CompactString`] containing a single ASCII byte, usable in `const`
+    /// contexts.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `byte` is not ASCII, i.e. greater than `0x7F`. In a `const` context this is a
+    /// compile time error.
+    ///
+    /// # Examples
+    /// ```
+    /// use compact_str::CompactString;
+    ///
+    /// const NEWLINE: CompactString = CompactString::from_ascii_byte(b'\n');
+    ///
+    /// assert_eq!(NEWLINE, "\n");
+    /// ```
+    #[inline]
+    #[track_caller]
+    pub const fn from_ascii_byte(byte: u8) -> Self {
+        if !byte.is_ascii() {
+            panic!("byte is not ASCII");
+        }
+        CompactString::from_char(byte as char)
+    }
+
     /// Creates a new inline [`CompactString`] at compile time.
     #[deprecated(
         since = "0.8.0",
@@ -247,12 +415,43 @@ impl CompactString {
     ///     "That is not dead which can eternal lie.",
     /// );
     /// ```
+    ///
+    /// This is a `const fn` when compiled with Rust 1.64 or newer, see
+    /// [compiler dependent APIs](crate#compiler-dependent-apis).
     #[inline]
     #[rustversion::attr(since(1.64), const)]
     pub fn as_static_str(&self) -> Option<&'static str> {
         self.0.as_static_str()
     }
 
+    /// Converts the [`CompactString`] back into the `&'static str` it was constructed from with
+    /// [`CompactString::const_new`], otherwise returns the original [`CompactString`].
+    ///
+    /// Like [`CompactString::as_static_str`], if the string was short enough to be inlined then
+    /// it's no longer backed by a `&'static str`, and this method returns `Err`.
+    ///
+    /// # Examples
+    /// ```
+    /// use compact_str::CompactString;
+    ///
+    /// let s = CompactString::const_new("That is not dead which can eternal lie.");
+    /// assert_eq!(s.try_into_static(), Ok("That is not dead which can eternal lie."));
+    ///
+    /// let s = CompactString::from("And with strange aeons even death may die.");
+    /// let s = s.try_into_static().unwrap_err();
+    ///
+    /// // when the string isn't 'static, you can still intentionally leak it
+    /// let leaked: &'static str = Box::leak(s.into_string().into_boxed_str());
+    /// assert_eq!(leaked, "And with strange aeons even death may die.");
+    /// ```
+    #[inline]
+    pub fn try_into_static(self) -> Result<&'static str, CompactString> {
+        match self.as_static_str() {
+            Some(s) => Ok(s),
+            None => Err(self),
+        }
+    }
+
     /// Creates a new empty [`CompactString`] with the capacity to fit at least `capacity` bytes.
     ///
     /// A `CompactString` will inline strings on the stack, if they're small enough. Specifically,
@@ -322,6 +521,99 @@ impl CompactString {
         Repr::with_capacity(capacity).map(CompactString)
     }
 
+    /// Creates a new empty [`CompactString`] like [`CompactString::with_capacity()`], but if the
+    /// string gets heap allocated its capacity is rounded up to the allocator size class that
+    /// `capacity` falls into, see [`GrowthPolicy::size_class()`].
+    ///
+    /// Allocators like jemalloc and mimalloc round allocations up to one of their size classes
+    /// anyways, this makes those extra bytes usable capacity instead of wasting them.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_str::CompactString;
+    /// let s = CompactString::with_capacity_size_class(150);
+    /// assert_eq!(s.capacity(), 160);
+    ///
+    /// // strings that fit inline aren't affected
+    /// let s = CompactString::with_capacity_size_class(3);
+    /// assert!(!s.is_heap_allocated());
+    /// ```
+    #[inline]
+    #[track_caller]
+    pub fn with_capacity_size_class(capacity: usize) -> Self {
+        Self::try_with_capacity_size_class(capacity).unwrap_with_msg()
+    }
+
+    /// Fallible version of [`CompactString::with_capacity_size_class()`]
+    ///
+    /// This method won't panic if the system is out-of-memory, but return an [`ReserveError`].
+    /// Otherwise it behaves the same as [`CompactString::with_capacity_size_class()`].
+    #[inline]
+    pub fn try_with_capacity_size_class(capacity: usize) -> Result<Self, ReserveError> {
+        if capacity <= MAX_INLINE_SIZE {
+            return Self::try_with_capacity(capacity);
+        }
+        Self::try_with_capacity(Self::growth_policy().size_class(capacity))
+    }
+
+    /// Creates a new [`CompactString`] containing `ch` repeated `count` times, similar to
+    /// `vec![elem; n]` for a [`Vec`].
+    ///
+    /// The string is allocated exactly once, and only if `ch.len_utf8() * count` doesn't fit
+    /// inline. For single byte characters the buffer is filled with a `memset`.
+    ///
+    /// # Panics
+    /// This method panics if the length of the string overflows `usize` or if the system is
+    /// out-of-memory. Use [`CompactString::try_filled()`] if you want to handle such a problem
+    /// manually.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_str::CompactString;
+    /// let padding = CompactString::filled(' ', 8);
+    /// assert_eq!(padding, "        ");
+    /// assert!(!padding.is_heap_allocated());
+    ///
+    /// let crabs = CompactString::filled('🦀', 10);
+    /// assert_eq!(crabs.len(), 40);
+    /// assert!(crabs.is_heap_allocated());
+    /// ```
+    #[inline]
+    #[track_caller]
+    pub fn filled(ch: char, count: usize) -> Self {
+        Self::try_filled(ch, count).unwrap_with_msg()
+    }
+
+    /// Fallible version of [`CompactString::filled()`]
+    ///
+    /// This method won't panic if the system is out-of-memory or the length overflows `usize`,
+    /// but return a [`ReserveError`]. Otherwise it behaves the same as
+    /// [`CompactString::filled()`].
+    pub fn try_filled(ch: char, count: usize) -> Result<Self, ReserveError> {
+        let ch_len = ch.len_utf8();
+        let len = ch_len
+            .checked_mul(count)
+            .ok_or(ReserveError::capacity_overflow(0))?;
+        let mut this = Self::try_with_capacity(len)?;
+
+        // SAFETY: We fill the first `len` bytes with copies of `ch`, which is valid UTF-8, and
+        // `len` is within the capacity we just allocated.
+        unsafe {
+            let buf = &mut this.as_mut_bytes()[..len];
+            if ch_len == 1 {
+                buf.fill(ch as u8);
+            } else {
+                let mut encoded = [0; 4];
+                let encoded = ch.encode_utf8(&mut encoded).as_bytes();
+                buf.chunks_exact_mut(ch_len)
+                    .for_each(|chunk| chunk.copy_from_slice(encoded));
+            }
+            this.set_len(len);
+        }
+
+        Ok(this)
+    }
+
     /// Convert a slice of bytes into a [`CompactString`].
     ///
     /// A [`CompactString`] is a contiguous collection of bytes (`u8`s) that is valid [`UTF-8`](https://en.wikipedia.org/wiki/UTF-8).
@@ -354,6 +646,27 @@ impl CompactString {
         Repr::from_utf8(buf).map(CompactString)
     }
 
+    /// Converts a slice of bytes into a [`CompactString`] like [`CompactString::from_utf8`], but
+    /// first strips a leading UTF-8 byte order mark (`EF BB BF`), if there is one.
+    ///
+    /// Files written by some editors, notably on Windows, start with a byte order mark that is
+    /// not part of their text.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_str::CompactString;
+    /// let with_bom = CompactString::from_utf8_bom(b"\xEF\xBB\xBFhello").unwrap();
+    /// assert_eq!(with_bom, "hello");
+    ///
+    /// let without_bom = CompactString::from_utf8_bom(b"hello").unwrap();
+    /// assert_eq!(without_bom, "hello");
+    /// ```
+    #[inline]
+    pub fn from_utf8_bom(v: impl AsRef<[u8]>) -> Result<Self, Utf8Error> {
+        let v = v.as_ref();
+        CompactString::from_utf8(v.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(v))
+    }
+
     /// Converts a vector of bytes to a [`CompactString`] without checking that the string contains
     /// valid UTF-8.
     ///
@@ -387,6 +700,12 @@ impl CompactString {
     #[must_use]
     #[track_caller]
     pub unsafe fn from_utf8_unchecked<B: AsRef<[u8]>>(buf: B) -> Self {
+        #[cfg(all(feature = "debug-validate", debug_assertions))]
+        features::debug_validate::validate_utf8(
+            buf.as_ref(),
+            "after calling `from_utf8_unchecked`",
+        );
+
         Repr::from_utf8_unchecked(buf)
             .map(CompactString)
             .unwrap_with_msg()
@@ -415,21 +734,9 @@ impl CompactString {
     /// ```
     #[inline]
     pub fn from_utf16<B: AsRef<[u16]>>(buf: B) -> Result<Self, Utf16Error> {
-        // Note: we don't use collect::<Result<_, _>>() because that fails to pre-allocate a buffer,
-        // even though the size of our iterator, `buf`, is known ahead of time.
-        //
-        // rustlang issue #48994 is tracking the fix
-
-        let buf = buf.as_ref();
-        let mut ret = CompactString::with_capacity(buf.len());
-        for c in core::char::decode_utf16(buf.iter().copied()) {
-            if let Ok(c) = c {
-                ret.push(c);
-            } else {
-                return Err(Utf16Error(()));
-            }
-        }
-        Ok(ret)
+        CompactString::decode_utf16(buf.as_ref().iter().copied(), |c| {
+            c.map_err(|_| Utf16Error(()))
+        })
     }
 
     /// Decode a UTF-16–encoded slice `v` into a `CompactString`, replacing invalid data with
@@ -451,15 +758,98 @@ impl CompactString {
     /// ```
     #[inline]
     pub fn from_utf16_lossy<B: AsRef<[u16]>>(buf: B) -> Self {
-        let buf = buf.as_ref();
-        let mut ret = CompactString::with_capacity(buf.len());
-        for c in core::char::decode_utf16(buf.iter().copied()) {
-            match c {
-                Ok(c) => ret.push(c),
-                Err(_) => ret.push_str("�"),
+        CompactString::from_utf16_iter_lossy(buf.as_ref().iter().copied())
+    }
+
+    /// Decode an iterator of [`UTF-16`](https://en.wikipedia.org/wiki/UTF-16) code units into a
+    /// [`CompactString`], returning an [`Err`] if it contains any invalid data.
+    ///
+    /// This is like [`CompactString::from_utf16`], but doesn't require collecting the code units
+    /// into a slice first, e.g. when they're streamed from a decoder.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_str::CompactString;
+    /// let units = [0xD834_u16, 0xDD1E, 0x006d, 0x0075, 0x0073, 0x0069, 0x0063];
+    /// let compact = CompactString::from_utf16_iter(units.iter().copied()).unwrap();
+    /// assert_eq!(compact, "𝄞music");
+    ///
+    /// // a lone surrogate
+    /// assert!(CompactString::from_utf16_iter([0x0061, 0xD800]).is_err());
+    /// ```
+    #[inline]
+    pub fn from_utf16_iter<I: IntoIterator<Item = u16>>(iter: I) -> Result<Self, Utf16Error> {
+        CompactString::decode_utf16(iter.into_iter(), |c| c.map_err(|_| Utf16Error(())))
+    }
+
+    /// Decode an iterator of UTF-16 code units into a [`CompactString`], replacing invalid data
+    /// with the replacement character (`U+FFFD`), �.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_str::CompactString;
+    /// let units = "hello".encode_utf16().chain([0xD800]);
+    /// let compact = CompactString::from_utf16_iter_lossy(units);
+    ///
+    /// assert_eq!(compact, "hello\u{FFFD}");
+    /// ```
+    #[inline]
+    pub fn from_utf16_iter_lossy<I: IntoIterator<Item = u16>>(iter: I) -> Self {
+        let res = CompactString::decode_utf16(iter.into_iter(), |c| {
+            Ok::<_, core::convert::Infallible>(c.unwrap_or(char::REPLACEMENT_CHARACTER))
+        });
+        match res {
+            Ok(s) => s,
+            Err(never) => match never {},
+        }
+    }
+
+    /// Shared implementation of the `from_utf16*` constructors, `handle` decides what to do with
+    /// invalid data.
+    ///
+    /// Most UTF-16 inputs we see are short, e.g. file names on Windows, so we first decode onto
+    /// the stack and create the inline [`CompactString`] in one shot, instead of pushing one
+    /// `char` at a time. Only if the decoded string doesn't fit inline do we move to the heap.
+    fn decode_utf16<I: Iterator<Item = u16>, E>(
+        units: I,
+        mut handle: impl FnMut(Result<char, core::char::DecodeUtf16Error>) -> Result<char, E>,
+    ) -> Result<Self, E> {
+        let mut scratch = [0_u8; repr::MAX_SIZE];
+        let mut len = 0;
+
+        // Every code unit decodes into at least one byte, so this is a lower bound on the length
+        let (min_len, _) = units.size_hint();
+
+        let mut chars = core::char::decode_utf16(units);
+        while let Some(c) = chars.next() {
+            let c = handle(c)?;
+            let ch_len = c.len_utf8();
+
+            if len + ch_len > repr::MAX_SIZE {
+                // Note: we don't use collect::<Result<_, _>>() because that fails to pre-allocate
+                // a buffer, even though a lower bound on the size of `units` is known ahead of time.
+                //
+                // rustlang issue #48994 is tracking the fix
+                let capacity = min_len.max(len + ch_len);
+                // Ignore the error and hope that the lower bound is incorrect.
+                let mut ret = CompactString::try_with_capacity(capacity)
+                    .unwrap_or_else(|_| CompactString::const_new(""));
+                // SAFETY: `scratch[..len]` only contains whole chars that we encoded below
+                ret.push_str(unsafe { core::str::from_utf8_unchecked(&scratch[..len]) });
+                ret.push(c);
+                for c in chars {
+                    ret.push(handle(c)?);
+                }
+                return Ok(ret);
             }
+
+            c.encode_utf8(&mut scratch[len..]);
+            len += ch_len;
         }
-        ret
+
+        // SAFETY: `scratch[..len]` only contains whole chars that we encoded above
+        let text = unsafe { core::str::from_utf8_unchecked(&scratch[..len]) };
+        Ok(CompactString::new(text))
     }
 
     /// Returns the length of the [`CompactString`] in `bytes`, not [`char`]s or graphemes.
@@ -482,6 +872,34 @@ impl CompactString {
         self.0.len()
     }
 
+    /// Returns the number of [`char`]s in the [`CompactString`].
+    ///
+    /// This is equivalent to `s.chars().count()`, but counts directly over the underlying buffer:
+    /// ASCII strings return their length immediately, otherwise we count every byte that is not a
+    /// UTF-8 continuation byte, which the compiler is able to vectorize.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_str::CompactString;
+    /// let ascii = CompactString::new("hello world");
+    /// assert_eq!(ascii.char_count(), 11);
+    ///
+    /// let emoji = CompactString::new("👱 hi");
+    /// assert_eq!(emoji.len(), 7);
+    /// assert_eq!(emoji.char_count(), 4);
+    /// ```
+    #[inline]
+    pub fn char_count(&self) -> usize {
+        let bytes = self.as_bytes();
+        if bytes.is_ascii() {
+            return bytes.len();
+        }
+
+        // Continuation bytes are in the range 0b1000_0000..=0b1011_1111, which as an i8 is every
+        // value less than -0x40
+        bytes.iter().filter(|b| **b as i8 >= -0x40).count()
+    }
+
     /// Returns `true` if the [`CompactString`] has a length of 0, `false` otherwise
     ///
     /// # Examples
@@ -525,6 +943,71 @@ impl CompactString {
         self.0.capacity()
     }
 
+    /// Returns the number of bytes that can be appended to the [`CompactString`] before it has
+    /// to grow, i.e. `capacity() - len()`.
+    ///
+    /// Note: a [`CompactString`] that refers to a `&'static str` always has a spare capacity of
+    /// zero, since it has to be copied before it can be modified, see
+    /// [`CompactString::would_reallocate`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_str::CompactString;
+    /// let mut compact = CompactString::with_capacity(64);
+    /// compact.push_str("hello");
+    /// assert_eq!(compact.spare_capacity(), 59);
+    /// ```
+    #[inline]
+    pub fn spare_capacity(&self) -> usize {
+        self.capacity() - self.len()
+    }
+
+    /// Returns `true` if appending `additional` more bytes would need to call into the allocator,
+    /// either to grow the heap buffer or to move the string onto the heap.
+    ///
+    /// This lets latency sensitive code pick a different path, e.g. flushing a buffer, instead of
+    /// growing at the wrong time. Appending nothing never allocates. An inline string, or one
+    /// that refers to a `&'static str`, only needs to allocate once the result no longer fits
+    /// inline. A heap allocated string needs to reallocate once it runs out of capacity.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_str::CompactString;
+    /// let mut compact = CompactString::with_capacity(32);
+    /// compact.push_str("hello world");
+    ///
+    /// assert!(!compact.would_reallocate(16));
+    /// assert!(compact.would_reallocate(64));
+    ///
+    /// // a long static string has to be copied onto the heap before it can be modified
+    /// let text = CompactString::const_new("a &'static str that's too long to be inlined");
+    /// assert!(text.would_reallocate(1));
+    /// ```
+    #[inline]
+    pub fn would_reallocate(&self, additional: usize) -> bool {
+        if additional == 0 {
+            return false;
+        }
+        match self.len().checked_add(additional) {
+            Some(needed) if self.is_heap_allocated() => needed > self.capacity(),
+            Some(needed) => needed > MAX_INLINE_SIZE,
+            None => true,
+        }
+    }
+
+    /// Returns the [`GrowthPolicy`] that describes how a [`CompactString`] picks its capacity.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_str::CompactString;
+    /// let policy = CompactString::growth_policy();
+    /// assert_eq!(policy.grow(100, 1), 150);
+    /// ```
+    #[inline]
+    pub const fn growth_policy() -> GrowthPolicy {
+        GrowthPolicy::CURRENT
+    }
+
     /// Ensures that this [`CompactString`]'s capacity is at least `additional` bytes longer than
     /// its length. The capacity may be increased by more than `additional` bytes if it chooses,
     /// to prevent frequent reallocations.
@@ -564,6 +1047,50 @@ impl CompactString {
         self.0.reserve(additional)
     }
 
+    /// Like [`CompactString::reserve()`], but if this reallocates onto the heap the new capacity
+    /// is rounded up to an allocator size class, see [`GrowthPolicy::size_class()`].
+    ///
+    /// If the string already has enough capacity, or still fits inline, this does the same as
+    /// [`CompactString::reserve()`].
+    ///
+    /// # Panics
+    /// This method panics if the new capacity overflows `usize` or if the system is out-of-memory.
+    /// Use [`CompactString::try_reserve_size_class()`] if you want to handle such a problem
+    /// manually.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_str::CompactString;
+    /// let mut compact = CompactString::new("hello");
+    /// let policy = CompactString::growth_policy();
+    ///
+    /// compact.reserve_size_class(95);
+    /// assert_eq!(compact.capacity(), policy.size_class(100));
+    /// ```
+    #[inline]
+    #[track_caller]
+    pub fn reserve_size_class(&mut self, additional: usize) {
+        self.try_reserve_size_class(additional).unwrap_with_msg()
+    }
+
+    /// Fallible version of [`CompactString::reserve_size_class()`]
+    ///
+    /// This method won't panic if the system is out-of-memory, but return an [`ReserveError`]
+    /// Otherwise it behaves the same as [`CompactString::reserve_size_class()`].
+    #[inline]
+    pub fn try_reserve_size_class(&mut self, additional: usize) -> Result<(), ReserveError> {
+        let len = self.len();
+        match len.checked_add(additional) {
+            Some(needed) if needed > MAX_INLINE_SIZE && self.would_reallocate(additional) => {
+                let policy = Self::growth_policy();
+                let capacity = policy.size_class(policy.grow(len, additional));
+                self.0.reserve(capacity - len)
+            }
+            // still fits inline, already has enough capacity, or overflows
+            _ => self.0.reserve(additional),
+        }
+    }
+
     /// Returns a string slice containing the entire [`CompactString`].
     ///
     /// # Examples
@@ -594,6 +1121,36 @@ impl CompactString {
         unsafe { core::str::from_utf8_unchecked_mut(&mut self.0.as_mut_buf()[..len]) }
     }
 
+    /// Returns a [`fmt::Write`]r that appends onto the end of this [`CompactString`].
+    ///
+    /// `&mut CompactString` already implements [`fmt::Write`], so this is mostly useful for
+    /// passing a string held in a struct field to generic code that takes `W: fmt::Write` by
+    /// value, without needing to move it out or spell out the reborrow.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_str::CompactString;
+    /// use core::fmt::Write;
+    ///
+    /// fn render<W: Write>(mut w: W, n: u32) -> core::fmt::Result {
+    ///     write!(w, "[{n:>4}]")
+    /// }
+    ///
+    /// struct Report {
+    ///     out: CompactString,
+    /// }
+    ///
+    /// let mut report = Report { out: CompactString::default() };
+    /// render(report.out.as_fmt_writer(), 7).unwrap();
+    /// render(&mut report.out, 42).unwrap();
+    ///
+    /// assert_eq!(report.out, "[   7][  42]");
+    /// ```
+    #[inline]
+    pub fn as_fmt_writer(&mut self) -> impl fmt::Write + '_ {
+        self
+    }
+
     unsafe fn spare_capacity_mut(&mut self) -> &mut [mem::MaybeUninit<u8>] {
         let buf = self.0.as_mut_buf();
         let ptr = buf.as_mut_ptr();
@@ -617,12 +1174,67 @@ impl CompactString {
         self.0.as_slice()
     }
 
-    // TODO: Implement a `try_as_mut_slice(...)` that will fail if it results in cloning?
-    //
-    /// Provides a mutable reference to the underlying buffer of bytes.
+    /// Returns the [`CompactString`]'s contents as an [`IoSlice`](std::io::IoSlice), for use
+    /// with vectored writes like [`Write::write_vectored`](std::io::Write::write_vectored).
     ///
-    /// # Safety
-    /// * All Rust strings, including `CompactString`, must be valid UTF-8. The caller must
+    /// # Examples
+    /// ```
+    /// # use compact_str::CompactString;
+    /// use std::io::Write;
+    ///
+    /// let header = CompactString::new("HTTP/1.1 200 OK\r\n");
+    /// let body = CompactString::new("\r\nhello");
+    ///
+    /// let mut out = Vec::new();
+    /// let written = out.write_vectored(&[header.as_io_slice(), body.as_io_slice()]).unwrap();
+    ///
+    /// assert_eq!(written, 24);
+    /// assert_eq!(out, b"HTTP/1.1 200 OK\r\n\r\nhello");
+    /// ```
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    #[inline]
+    pub fn as_io_slice(&self) -> std::io::IoSlice<'_> {
+        std::io::IoSlice::new(self.as_bytes())
+    }
+
+    /// Fills `dst` with [`IoSlice`](std::io::IoSlice)s of the [`CompactString`]'s contents,
+    /// returning how many were written, like `bytes::Buf::chunks_vectored`.
+    ///
+    /// A [`CompactString`] is always stored contiguously, so this writes at most one slice, and
+    /// none if the string or `dst` is empty.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_str::CompactString;
+    /// use std::io::IoSlice;
+    ///
+    /// let s = CompactString::new("hello");
+    /// let mut slices = [IoSlice::new(&[]); 4];
+    ///
+    /// let n = s.as_io_slices(&mut slices);
+    /// assert_eq!(n, 1);
+    /// assert_eq!(&*slices[0], b"hello");
+    /// ```
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    #[inline]
+    pub fn as_io_slices<'a>(&'a self, dst: &mut [std::io::IoSlice<'a>]) -> usize {
+        match dst.first_mut() {
+            Some(slot) if !self.is_empty() => {
+                *slot = self.as_io_slice();
+                1
+            }
+            _ => 0,
+        }
+    }
+
+    // TODO: Implement a `try_as_mut_slice(...)` that will fail if it results in cloning?
+    //
+    /// Provides a mutable reference to the underlying buffer of bytes.
+    ///
+    /// # Safety
+    /// * All Rust strings, including `CompactString`, must be valid UTF-8. The caller must
     ///   guarantee that any modifications made to the underlying buffer are valid UTF-8.
     ///
     /// # Examples
@@ -638,11 +1250,59 @@ impl CompactString {
     ///
     /// assert_eq!(s, "hello world");
     /// ```
+    ///
+    /// With the `debug-validate` feature, writes through the returned slice can only be checked
+    /// the next time the string is accessed through an `unsafe` method. To have them checked right
+    /// after they're made, use [`CompactString::with_mut_bytes`].
     #[inline]
+    #[cfg_attr(all(feature = "debug-validate", debug_assertions), track_caller)]
     pub unsafe fn as_mut_bytes(&mut self) -> &mut [u8] {
+        // Note: we can't check what gets written into the returned slice, so instead we check that
+        // any previous writes left us with valid UTF-8
+        #[cfg(all(feature = "debug-validate", debug_assertions))]
+        features::debug_validate::validate_utf8(
+            self.as_bytes(),
+            "when calling `as_mut_bytes`, left by an earlier write through its buffer",
+        );
+
         self.0.as_mut_buf()
     }
 
+    /// Calls `f` with a mutable reference to the underlying buffer of bytes, like
+    /// [`CompactString::as_mut_bytes`], and returns its result.
+    ///
+    /// With the `debug-validate` feature, the string is checked to still be valid UTF-8 as soon as
+    /// `f` returns, and an invalid write panics at the call site of this method. Without the
+    /// feature, or in release builds, this is the same as calling `f(s.as_mut_bytes())`.
+    ///
+    /// # Safety
+    /// * All Rust strings, including `CompactString`, must be valid UTF-8. The caller must
+    ///   guarantee that any modifications made to the underlying buffer are valid UTF-8.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_str::CompactString;
+    /// let mut s = CompactString::new("hello");
+    ///
+    /// unsafe {
+    ///     s.with_mut_bytes(|buf| buf[..5].copy_from_slice(b"jello"));
+    /// }
+    /// assert_eq!(s, "jello");
+    /// ```
+    #[inline]
+    #[cfg_attr(all(feature = "debug-validate", debug_assertions), track_caller)]
+    pub unsafe fn with_mut_bytes<R>(&mut self, f: impl FnOnce(&mut [u8]) -> R) -> R {
+        let result = f(self.as_mut_bytes());
+
+        #[cfg(all(feature = "debug-validate", debug_assertions))]
+        features::debug_validate::validate_utf8(
+            self.as_bytes(),
+            "after writing through `with_mut_bytes`",
+        );
+
+        result
+    }
+
     /// Appends the given [`char`] to the end of this [`CompactString`].
     ///
     /// # Examples
@@ -660,6 +1320,15 @@ impl CompactString {
         self.push_str(ch.encode_utf8(&mut [0; 4]));
     }
 
+    /// Fallible version of [`CompactString::push()`]
+    ///
+    /// This method won't panic if the system is out-of-memory, but return a [`ReserveError`].
+    /// Otherwise it behaves the same as [`CompactString::push()`].
+    #[inline]
+    pub fn try_push(&mut self, ch: char) -> Result<(), ReserveError> {
+        self.try_push_str(ch.encode_utf8(&mut [0; 4]))
+    }
+
     /// Removes the last character from the [`CompactString`] and returns it.
     /// Returns `None` if this [`CompactString`] is empty.
     ///
@@ -679,6 +1348,39 @@ impl CompactString {
         self.0.pop()
     }
 
+    /// Removes the last `n` [`char`]s from the [`CompactString`] and returns them, in order, as a
+    /// new [`CompactString`]. If the string has fewer than `n` [`char`]s, all of them are removed.
+    ///
+    /// Unlike calling [`CompactString::pop()`] in a loop, this finds the new end of the string in
+    /// a single scan from the back, and only shortens the string once.
+    ///
+    /// Calling this function does not change the capacity of the [`CompactString`], unless the
+    /// [`CompactString`] is backed by a `&'static str`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_str::CompactString;
+    /// let mut s = CompactString::new("report.tar.gz");
+    ///
+    /// assert_eq!(s.pop_n(3), ".gz");
+    /// assert_eq!(s, "report.tar");
+    ///
+    /// assert_eq!(s.pop_n(100), "report.tar");
+    /// assert_eq!(s, "");
+    /// ```
+    pub fn pop_n(&mut self, n: usize) -> CompactString {
+        if n == 0 {
+            return CompactString::default();
+        }
+        let at = self
+            .as_str()
+            .char_indices()
+            .rev()
+            .nth(n - 1)
+            .map_or(0, |(idx, _)| idx);
+        self.split_off(at)
+    }
+
     /// Appends a given string slice onto the end of this [`CompactString`]
     ///
     /// # Examples
@@ -695,6 +1397,119 @@ impl CompactString {
         self.0.push_str(s)
     }
 
+    /// Fallible version of [`CompactString::push_str()`]
+    ///
+    /// This method won't panic if the system is out-of-memory or the new length overflows
+    /// `usize`, but return a [`ReserveError`]. Otherwise it behaves the same as
+    /// [`CompactString::push_str()`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_str::CompactString;
+    /// let mut s = CompactString::new("abc");
+    ///
+    /// s.try_push_str("123").expect("out of memory");
+    ///
+    /// assert_eq!("abc123", s);
+    /// ```
+    #[inline]
+    pub fn try_push_str(&mut self, s: &str) -> Result<(), ReserveError> {
+        self.0.try_push_str(s)
+    }
+
+    /// Appends all of the given string slices onto the end of this [`CompactString`].
+    ///
+    /// The lengths of `pieces` are summed up front and reserved for at once, so the
+    /// [`CompactString`] grows at most once, instead of possibly once per call to
+    /// [`CompactString::push_str()`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_str::CompactString;
+    /// let mut s = CompactString::new("key=");
+    ///
+    /// s.extend_from_slices(&["a", "long", "value", "that", "will", "be", "on", "the", "heap"]);
+    ///
+    /// assert_eq!(s, "key=alongvaluethatwillbeontheheap");
+    /// ```
+    pub fn extend_from_slices(&mut self, pieces: &[&str]) {
+        let additional = pieces
+            .iter()
+            .fold(0usize, |len, piece| len.saturating_add(piece.len()));
+        self.reserve(additional);
+
+        for piece in pieces {
+            self.push_str(piece);
+        }
+    }
+
+    /// Creates a new [`CompactString`] by concatenating `pieces`, allocating at most once.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_str::CompactString;
+    /// let s = CompactString::from_slices(&["hello", " ", "world"]);
+    ///
+    /// assert_eq!(s, "hello world");
+    /// assert!(!s.is_heap_allocated());
+    /// ```
+    pub fn from_slices(pieces: &[&str]) -> Self {
+        let mut s = CompactString::default();
+        s.extend_from_slices(pieces);
+        s
+    }
+
+    /// Fallible version of [`Clone::clone()`]
+    ///
+    /// Cloning an inline or static string never fails. Cloning a heap allocated string returns a
+    /// [`ReserveError`] instead of panicking if the system is out-of-memory.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_str::CompactString;
+    /// let s = CompactString::from("this string is long enough to be allocated on the heap");
+    /// let clone = s.try_clone().expect("out of memory");
+    ///
+    /// assert_eq!(s, clone);
+    /// ```
+    #[inline]
+    pub fn try_clone(&self) -> Result<Self, ReserveError> {
+        self.0.try_clone().map(CompactString)
+    }
+
+    /// Appends `item` onto the end of this [`CompactString`], separated by `separator`.
+    ///
+    /// No separator is emitted if the [`CompactString`] is empty, and at most one separator ends
+    /// up between the existing contents and `item`, i.e. if the [`CompactString`] already ends
+    /// with `separator` or `item` already starts with it, it won't be duplicated. This makes it
+    /// easy to build up paths or keys in a loop.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_str::CompactString;
+    /// let mut key = CompactString::default();
+    ///
+    /// for segment in ["users", "/42", "settings/", "/theme"] {
+    ///     key.push_joined("/", segment);
+    /// }
+    ///
+    /// assert_eq!(key, "users/42/settings/theme");
+    /// assert!(!key.is_heap_allocated());
+    /// ```
+    pub fn push_joined(&mut self, separator: &str, item: &str) {
+        if self.is_empty() {
+            self.push_str(item);
+        } else if self.ends_with(separator) {
+            self.push_str(item.strip_prefix(separator).unwrap_or(item));
+        } else if item.starts_with(separator) {
+            self.push_str(item);
+        } else {
+            self.reserve(separator.len() + item.len());
+            self.push_str(separator);
+            self.push_str(item);
+        }
+    }
+
     /// Removes a [`char`] from this [`CompactString`] at a byte position and returns it.
     ///
     /// This is an *O*(*n*) operation, as it requires copying every element in the
@@ -761,6 +1576,54 @@ impl CompactString {
         ch
     }
 
+    /// Replaces the [`char`] starting at byte index `idx` with `ch`, returning the old [`char`].
+    ///
+    /// If both characters have the same UTF-8 width the bytes are overwritten in place, otherwise
+    /// the remainder of the string is shifted only once. This is cheaper than calling
+    /// [`CompactString::remove`] followed by [`CompactString::insert`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is larger than or equal to the [`CompactString`]'s length,
+    /// or if it does not lie on a [`char`] boundary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use compact_str::CompactString;
+    /// let mut c = CompactString::from("hello world");
+    ///
+    /// assert_eq!(c.replace_char_at(0, 'j'), 'h');
+    /// assert_eq!(c, "jello world");
+    ///
+    /// assert_eq!(c.replace_char_at(5, '🌍'), ' ');
+    /// assert_eq!(c, "jello🌍world");
+    ///
+    /// assert_eq!(c.replace_char_at(5, '-'), '🌍');
+    /// assert_eq!(c, "jello-world");
+    /// ```
+    pub fn replace_char_at(&mut self, idx: usize, ch: char) -> char {
+        let old = self.as_str()[idx..]
+            .chars()
+            .next()
+            .expect("cannot replace a char at the end of a string");
+        let start = idx;
+        let end = idx + old.len_utf8();
+
+        let mut buf = [0; 4];
+        let new = ch.encode_utf8(&mut buf);
+
+        // SAFETY: `start..end` is the range of a single char in the string, so both ends lie on a
+        // char boundary and are in bounds
+        match old.len_utf8().cmp(&new.len()) {
+            Ordering::Equal => unsafe { self.replace_range_same_size(start, end, new) },
+            Ordering::Greater => unsafe { self.replace_range_shrink(start, end, new) },
+            Ordering::Less => unsafe { self.replace_range_grow(start, end, new) },
+        }
+
+        old
+    }
+
     /// Forces the length of the [`CompactString`] to `new_len`.
     ///
     /// This is a low-level operation that maintains none of the normal invariants for
@@ -771,8 +1634,12 @@ impl CompactString {
     /// * `new_len` must be less than or equal to `capacity()`
     /// * The elements at `old_len..new_len` must be initialized
     #[inline]
+    #[cfg_attr(all(feature = "debug-validate", debug_assertions), track_caller)]
     pub unsafe fn set_len(&mut self, new_len: usize) {
-        self.0.set_len(new_len)
+        self.0.set_len(new_len);
+
+        #[cfg(all(feature = "debug-validate", debug_assertions))]
+        features::debug_validate::validate_utf8(self.as_bytes(), "after calling `set_len`");
     }
 
     /// Returns whether or not the [`CompactString`] is heap allocated.
@@ -798,6 +1665,55 @@ impl CompactString {
         self.0.is_heap_allocated()
     }
 
+    /// Returns the raw inline buffer and the length of the string stored in it, or [`None`] if
+    /// the [`CompactString`] is heap allocated or wraps a `&'static str`.
+    ///
+    /// Only the first `len` bytes of the buffer are part of the string, the contents of the rest
+    /// are unspecified, e.g. when the string is shorter than [`MAX_INLINE_SIZE`] the last byte
+    /// is used to store its length.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_str::{CompactString, MAX_INLINE_SIZE};
+    /// let s = CompactString::new("hello");
+    /// let (buf, len) = s.as_inline_bytes().unwrap();
+    ///
+    /// assert_eq!(buf.len(), MAX_INLINE_SIZE);
+    /// assert_eq!(&buf[..len], b"hello");
+    ///
+    /// let long = CompactString::new("this is a long string that will be on the heap");
+    /// assert!(long.as_inline_bytes().is_none());
+    /// ```
+    #[inline]
+    pub fn as_inline_bytes(&self) -> Option<(&[u8; MAX_INLINE_SIZE], usize)> {
+        self.0.as_inline_buffer()
+    }
+
+    /// Returns the raw inline buffer and the length of the string stored in it, without checking
+    /// that the [`CompactString`] is actually inline.
+    ///
+    /// See [`CompactString::as_inline_bytes`] for what the buffer contains.
+    ///
+    /// # Safety
+    /// * The caller must guarantee that the [`CompactString`] is stored inline, i.e. that
+    ///   [`CompactString::as_inline_bytes`] would return `Some(..)`. Note that
+    ///   [`CompactString::is_heap_allocated`] returning `false` is not enough, since a
+    ///   [`CompactString`] created with [`CompactString::const_new`] may wrap a `&'static str`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_str::CompactString;
+    /// let s = CompactString::new("hello");
+    /// assert!(s.as_inline_bytes().is_some());
+    ///
+    /// let (buf, len) = unsafe { s.as_inline_bytes_unchecked() };
+    /// assert_eq!(&buf[..len], b"hello");
+    /// ```
+    #[inline]
+    pub unsafe fn as_inline_bytes_unchecked(&self) -> (&[u8; MAX_INLINE_SIZE], usize) {
+        self.0.as_inline_buffer_unchecked()
+    }
+
     /// Ensure that the given range is inside the set data, and that no codepoints are split.
     ///
     /// Returns the range `start..end` as a tuple.
@@ -809,32 +1725,38 @@ impl CompactString {
             panic!("illegal range");
         }
 
+        match self.try_ensure_range(range) {
+            Ok(range) => range,
+            Err(_) => illegal_range(),
+        }
+    }
+
+    /// Fallible version of [`CompactString::ensure_range`].
+    #[inline]
+    fn try_ensure_range(
+        &self,
+        range: impl RangeBounds<usize>,
+    ) -> Result<(usize, usize), RangeError> {
         let start = match range.start_bound() {
             Bound::Included(&n) => n,
-            Bound::Excluded(&n) => match n.checked_add(1) {
-                Some(n) => n,
-                None => illegal_range(),
-            },
+            Bound::Excluded(&n) => n.checked_add(1).ok_or(RangeError::OutOfBounds)?,
             Bound::Unbounded => 0,
         };
         let end = match range.end_bound() {
-            Bound::Included(&n) => match n.checked_add(1) {
-                Some(n) => n,
-                None => illegal_range(),
-            },
+            Bound::Included(&n) => n.checked_add(1).ok_or(RangeError::OutOfBounds)?,
             Bound::Excluded(&n) => n,
             Bound::Unbounded => self.len(),
         };
-        if end < start {
-            illegal_range();
+        if end < start || end > self.len() {
+            return Err(RangeError::OutOfBounds);
         }
 
         let s = self.as_str();
         if !s.is_char_boundary(start) || !s.is_char_boundary(end) {
-            illegal_range();
+            return Err(RangeError::NotCharBoundary);
         }
 
-        (start, end)
+        Ok((start, end))
     }
 
     /// Removes the specified range in the [`CompactString`],
@@ -866,11 +1788,49 @@ impl CompactString {
     #[inline]
     pub fn replace_range(&mut self, range: impl RangeBounds<usize>, replace_with: &str) {
         let (start, end) = self.ensure_range(range);
+        // SAFETY: `ensure_range` checked that the range is in bounds and on char boundaries
+        unsafe { self.replace_range_unchecked(start, end, replace_with) }
+    }
+
+    /// Fallible version of [`CompactString::replace_range`].
+    ///
+    /// Instead of panicking, this returns a [`RangeError`] if the starting point or end point are
+    /// out of bounds, or do not lie on a [`char`] boundary. The [`CompactString`] is left
+    /// unmodified in that case.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use compact_str::{CompactString, RangeError};
+    /// let mut s = CompactString::new("Hello, 🌍!");
+    ///
+    /// assert_eq!(s.try_replace_range(7..100, "world"), Err(RangeError::OutOfBounds));
+    /// assert_eq!(s.try_replace_range(7..9, "world"), Err(RangeError::NotCharBoundary));
+    /// assert_eq!(s, "Hello, 🌍!");
+    ///
+    /// assert_eq!(s.try_replace_range(7..11, "world"), Ok(()));
+    /// assert_eq!(s, "Hello, world!");
+    /// ```
+    #[inline]
+    pub fn try_replace_range(
+        &mut self,
+        range: impl RangeBounds<usize>,
+        replace_with: &str,
+    ) -> Result<(), RangeError> {
+        let (start, end) = self.try_ensure_range(range)?;
+        // SAFETY: `try_ensure_range` checked that the range is in bounds and on char boundaries
+        unsafe { self.replace_range_unchecked(start, end, replace_with) };
+        Ok(())
+    }
+
+    /// Replace `start..end`, which must be in bounds and lie on char boundaries.
+    #[inline]
+    unsafe fn replace_range_unchecked(&mut self, start: usize, end: usize, replace_with: &str) {
         let dest_len = end - start;
         match dest_len.cmp(&replace_with.len()) {
-            Ordering::Equal => unsafe { self.replace_range_same_size(start, end, replace_with) },
-            Ordering::Greater => unsafe { self.replace_range_shrink(start, end, replace_with) },
-            Ordering::Less => unsafe { self.replace_range_grow(start, end, replace_with) },
+            Ordering::Equal => self.replace_range_same_size(start, end, replace_with),
+            Ordering::Greater => self.replace_range_shrink(start, end, replace_with),
+            Ordering::Less => self.replace_range_grow(start, end, replace_with),
         }
     }
 
@@ -910,7 +1870,9 @@ impl CompactString {
         let new_len = total_len + (replace_with.len() - dest_len);
         let amount = total_len - end;
         // first grow the string, so MIRI knows that the full range is usable
-        self.set_len(new_len);
+        //
+        // Note: this temporarily breaks the UTF-8 invariant, so we skip `CompactString::set_len`
+        self.0.set_len(new_len);
         let data = self.as_mut_ptr();
         // then move the tail of the CompactString back to its new place
         core::ptr::copy(
@@ -991,13 +1953,116 @@ impl CompactString {
         unsafe { self.set_len(new_len) };
     }
 
+    /// Shortens this [`CompactString`] to at most `n_chars` [`char`]s, as opposed to
+    /// [`CompactString::truncate`] which takes a length in bytes.
+    ///
+    /// If the string has `n_chars` or fewer [`char`]s, the call is a no-op. The new end of the
+    /// string is found in a single scan from the front, so this never panics.
+    ///
+    /// Calling this function does not change the capacity of the [`CompactString`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_str::CompactString;
+    /// let mut s = CompactString::new("🦀 crab");
+    ///
+    /// s.truncate_chars(3);
+    /// assert_eq!(s, "🦀 c");
+    ///
+    /// s.truncate_chars(10);
+    /// assert_eq!(s, "🦀 c");
+    /// ```
+    pub fn truncate_chars(&mut self, n_chars: usize) {
+        if let Some((new_len, _)) = self.as_str().char_indices().nth(n_chars) {
+            // SAFETY: `new_len` is the index of a char, so it lies on a char boundary
+            unsafe { self.set_len(new_len) };
+        }
+    }
+
+    /// Fallible version of [`CompactString::truncate`].
+    ///
+    /// Returns [`RangeError::NotCharBoundary`] instead of panicking if `new_len` does not lie on
+    /// a [`char`] boundary. Like [`CompactString::truncate`], this is a no-op if `new_len` is
+    /// greater than or equal to the string's current length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use compact_str::{CompactString, RangeError};
+    /// let mut s = CompactString::new("🦀 crab");
+    ///
+    /// assert_eq!(s.try_truncate(2), Err(RangeError::NotCharBoundary));
+    /// assert_eq!(s.try_truncate(4), Ok(()));
+    /// assert_eq!(s, "🦀");
+    /// ```
+    pub fn try_truncate(&mut self, new_len: usize) -> Result<(), RangeError> {
+        let s = self.as_str();
+        if new_len >= s.len() {
+            return Ok(());
+        }
+
+        if !s.is_char_boundary(new_len) {
+            return Err(RangeError::NotCharBoundary);
+        }
+        // SAFETY: `new_len` is less than our length, and we checked above that it lies on a char
+        // boundary
+        unsafe { self.set_len(new_len) };
+        Ok(())
+    }
+
     /// Converts a [`CompactString`] to a raw pointer.
+    ///
+    /// The pointer is valid for reads of [`CompactString::len()`] bytes, and must not be written
+    /// through. Where it points depends on how the string is stored:
+    ///
+    /// * inline strings: into the [`CompactString`] itself, so moving the [`CompactString`]
+    ///   invalidates the pointer
+    /// * heap allocated strings: to the heap buffer, which stays put until the string is dropped
+    ///   or grows
+    /// * strings created with [`CompactString::const_new`] that were too long to be inlined: to
+    ///   the original `&'static str`
+    ///
+    /// In every case the pointer is invalidated by any method that takes `&mut self`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_str::CompactString;
+    /// let s = CompactString::new("hello");
+    /// let ptr = s.as_ptr();
+    ///
+    /// let bytes = unsafe { core::slice::from_raw_parts(ptr, s.len()) };
+    /// assert_eq!(bytes, b"hello");
+    /// ```
     #[inline]
     pub fn as_ptr(&self) -> *const u8 {
         self.0.as_slice().as_ptr()
     }
 
     /// Converts a mutable [`CompactString`] to a raw pointer.
+    ///
+    /// The pointer is valid for reads and writes of [`CompactString::capacity()`] bytes, as long
+    /// as the [`CompactString`] is neither moved nor used in any other way. Like
+    /// [`CompactString::as_mut_bytes()`], anything written through it must leave the first
+    /// [`CompactString::len()`] bytes valid UTF-8, and a longer string only becomes visible after
+    /// calling [`CompactString::set_len()`].
+    ///
+    /// Inline strings are stored within the [`CompactString`] itself, and heap allocated strings
+    /// in their own buffer. A string created with [`CompactString::const_new`] that was too long
+    /// to be inlined refers to read-only memory, so it is first copied into a heap buffer.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_str::CompactString;
+    /// let mut s = CompactString::const_new("hello");
+    /// let ptr = s.as_mut_ptr();
+    ///
+    /// unsafe {
+    ///     ptr.copy_from_nonoverlapping(b"J".as_ptr(), 1);
+    ///     ptr.add(5).copy_from_nonoverlapping(b"!".as_ptr(), 1);
+    ///     s.set_len(6);
+    /// }
+    /// assert_eq!(s, "Jello!");
+    /// ```
     #[inline]
     pub fn as_mut_ptr(&mut self) -> *mut u8 {
         unsafe { self.0.as_mut_buf().as_mut_ptr() }
@@ -1143,26 +2208,54 @@ impl CompactString {
         }
     }
 
-    /// Shrinks the capacity of this [`CompactString`] with a lower bound.
+    /// Fallible version of [`CompactString::drain`].
     ///
-    /// The resulting capactity is never less than the size of 3×[`usize`],
-    /// i.e. the capacity than can be inlined.
+    /// Returns a [`RangeError`] instead of panicking if the range is out of bounds, or if its
+    /// start or end does not lie on a [`char`] boundary.
     ///
     /// # Examples
     ///
-    /// Basic usage:
-    ///
     /// ```
-    /// # use compact_str::CompactString;
-    /// let mut s = CompactString::with_capacity(100);
-    /// assert_eq!(s.capacity(), 100);
+    /// # use compact_str::{CompactString, RangeError};
+    /// let mut s = CompactString::new("Hello, world!");
     ///
-    /// // if the capacity was already bigger than the argument, the call is a no-op
-    /// s.shrink_to(100);
-    /// assert_eq!(s.capacity(), 100);
+    /// assert_eq!(s.try_drain(5..20).unwrap_err(), RangeError::OutOfBounds);
     ///
-    /// s.shrink_to(50);
-    /// assert_eq!(s.capacity(), 50);
+    /// let d = s.try_drain(5..12).unwrap();
+    /// assert_eq!(d.as_str(), ", world");
+    /// drop(d);
+    /// assert_eq!(s, "Hello!");
+    /// ```
+    pub fn try_drain(&mut self, range: impl RangeBounds<usize>) -> Result<Drain<'_>, RangeError> {
+        let (start, end) = self.try_ensure_range(range)?;
+        Ok(Drain {
+            compact_string: self as *mut Self,
+            start,
+            end,
+            chars: self[start..end].chars(),
+        })
+    }
+
+    /// Shrinks the capacity of this [`CompactString`] with a lower bound.
+    ///
+    /// The resulting capactity is never less than the size of 3×[`usize`],
+    /// i.e. the capacity than can be inlined.
+    ///
+    /// # Examples
+    ///
+    /// Basic usage:
+    ///
+    /// ```
+    /// # use compact_str::CompactString;
+    /// let mut s = CompactString::with_capacity(100);
+    /// assert_eq!(s.capacity(), 100);
+    ///
+    /// // if the capacity was already bigger than the argument, the call is a no-op
+    /// s.shrink_to(100);
+    /// assert_eq!(s.capacity(), 100);
+    ///
+    /// s.shrink_to(50);
+    /// assert_eq!(s.capacity(), 50);
     ///
     /// // if the string can be inlined, it is
     /// s.shrink_to(10);
@@ -1266,6 +2359,263 @@ impl CompactString {
         drop(g);
     }
 
+    /// Replaces every `\r\n` line ending with `\n`, in place, without reallocating.
+    ///
+    /// A lone `\r` is left as is. The string is fixed up in a single pass that shifts the
+    /// remaining bytes over each removed `\r`, and it isn't written to at all if there are no
+    /// `\r\n`s, so inline strings stay inline and static strings stay static.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_str::CompactString;
+    /// let mut s = CompactString::new("one\r\ntwo\r\nthree\n");
+    /// s.normalize_newlines();
+    ///
+    /// assert_eq!(s, "one\ntwo\nthree\n");
+    /// ```
+    pub fn normalize_newlines(&mut self) {
+        // SAFETY: We only remove a `\r`, which is a whole char
+        unsafe {
+            self.remove_in_place(|rest| match rest {
+                [b'\r', b'\n', ..] => 1,
+                _ => 0,
+            })
+        }
+    }
+
+    /// Removes all control characters except `\n` and `\t`, in place, without reallocating.
+    ///
+    /// Control characters are the ones for which [`char::is_control`] returns `true`, i.e. the C0
+    /// controls `U+0000` to `U+001F`, `U+007F`, and the C1 controls `U+0080` to `U+009F`. Newlines
+    /// and tabs are kept so the structure of the text is preserved. Like
+    /// [`CompactString::normalize_newlines`], this is a single pass over the bytes that leaves
+    /// strings without control characters untouched.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_str::CompactString;
+    /// let mut s = CompactString::new("\x07error:\tdisk\u{85} full\0\n");
+    /// s.strip_control_chars();
+    ///
+    /// assert_eq!(s, "error:\tdisk full\n");
+    /// ```
+    pub fn strip_control_chars(&mut self) {
+        // SAFETY: C0 controls and DEL are a single byte, and C1 controls are `0xC2` followed by a
+        // continuation byte, so we only ever remove whole chars
+        unsafe {
+            self.remove_in_place(|rest| match rest {
+                [b'\n' | b'\t', ..] => 0,
+                [0x00..=0x1F | 0x7F, ..] => 1,
+                [0xC2, 0x80..=0x9F, ..] => 2,
+                _ => 0,
+            })
+        }
+    }
+
+    /// Removes spans of bytes from the string in a single pass. `remove` is called with the
+    /// remaining bytes at every position, and returns how many bytes to remove from the start,
+    /// or 0 to keep the next byte.
+    ///
+    /// Nothing is written if `remove` always returns 0.
+    ///
+    /// # Safety
+    /// * `remove` must only ever remove whole chars
+    unsafe fn remove_in_place(&mut self, mut remove: impl FnMut(&[u8]) -> usize) {
+        let len = self.len();
+        let bytes = self.as_bytes();
+        let mut src = match (0..len).find(|&idx| remove(&bytes[idx..]) > 0) {
+            Some(idx) => idx,
+            None => return,
+        };
+
+        let bytes = &mut self.as_mut_bytes()[..len];
+        let mut dst = src;
+        while src < len {
+            match remove(&bytes[src..]) {
+                0 => {
+                    bytes[dst] = bytes[src];
+                    dst += 1;
+                    src += 1;
+                }
+                n => src += n,
+            }
+        }
+        self.set_len(dst);
+    }
+
+    /// Removes leading and trailing whitespace in place, without reallocating.
+    ///
+    /// 'Whitespace' is defined according to the terms of the Unicode Derived Core Property
+    /// `White_Space`, the same as [`str::trim`]. Calling this function does not change the
+    /// capacity of the [`CompactString`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_str::CompactString;
+    /// let mut s = CompactString::new("\n Hello\tworld\t\n");
+    /// s.trim_in_place();
+    ///
+    /// assert_eq!(s, "Hello\tworld");
+    /// ```
+    #[inline]
+    pub fn trim_in_place(&mut self) {
+        self.trim_end_in_place();
+        self.trim_start_in_place();
+    }
+
+    /// Removes leading whitespace in place, shifting the remaining bytes to the start of the
+    /// existing buffer.
+    ///
+    /// 'Whitespace' is defined according to the terms of the Unicode Derived Core Property
+    /// `White_Space`, the same as [`str::trim_start`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_str::CompactString;
+    /// let mut s = CompactString::new("\n Hello\tworld\t\n");
+    /// s.trim_start_in_place();
+    ///
+    /// assert_eq!(s, "Hello\tworld\t\n");
+    /// ```
+    pub fn trim_start_in_place(&mut self) {
+        let len = self.len();
+        let start = len - self.trim_start().len();
+        if start == 0 {
+            return;
+        }
+
+        let new_len = len - start;
+        // SAFETY: `start` lies on a char boundary since it's where `trim_start` begins, so moving
+        // the remaining `new_len` bytes to the front of the buffer leaves valid UTF-8
+        unsafe {
+            self.as_mut_bytes().copy_within(start..len, 0);
+            self.set_len(new_len);
+        }
+    }
+
+    /// Removes trailing whitespace in place, without reallocating.
+    ///
+    /// 'Whitespace' is defined according to the terms of the Unicode Derived Core Property
+    /// `White_Space`, the same as [`str::trim_end`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_str::CompactString;
+    /// let mut s = CompactString::new("\n Hello\tworld\t\n");
+    /// s.trim_end_in_place();
+    ///
+    /// assert_eq!(s, "\n Hello\tworld");
+    /// ```
+    #[inline]
+    pub fn trim_end_in_place(&mut self) {
+        let new_len = self.trim_end().len();
+        self.truncate(new_len);
+    }
+
+    /// Returns a new [`CompactString`] with leading and trailing whitespace removed.
+    ///
+    /// This is equivalent to `CompactString::new(s.trim())`, which means short results get
+    /// inlined.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_str::CompactString;
+    /// let s = CompactString::new("   this string is short once it gets trimmed   ");
+    /// let trimmed = s.trimmed();
+    ///
+    /// assert_eq!(trimmed, "this string is short once it gets trimmed");
+    /// ```
+    #[inline]
+    pub fn trimmed(&self) -> CompactString {
+        CompactString::new(self.trim())
+    }
+
+    /// Pads the string in place with `fill` until it is `width` characters long, like formatting
+    /// it with `{:<width$}`, `{:^width$}`, or `{:>width$}`.
+    ///
+    /// Like [`format!`], `width` is measured in [`char`]s, and centered strings get the extra fill
+    /// character on the right. If the string is already at least `width` characters long, it's
+    /// left untouched.
+    ///
+    /// This reserves space for the padding once and then shifts the existing bytes into place, so
+    /// a string that already has enough capacity is padded without allocating.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity overflows `usize`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use compact_str::CompactString;
+    /// use core::fmt::Alignment;
+    ///
+    /// let mut s = CompactString::new("42");
+    /// s.pad_to(6, '0', Alignment::Right);
+    /// assert_eq!(s, "000042");
+    ///
+    /// let mut s = CompactString::new("title");
+    /// s.pad_to(12, '─', Alignment::Center);
+    /// assert_eq!(s, format!("{:─^12}", "title"));
+    /// ```
+    pub fn pad_to(&mut self, width: usize, fill: char, align: fmt::Alignment) {
+        let chars = self.chars().count();
+        if chars >= width {
+            return;
+        }
+
+        let padding = width - chars;
+        let (before, after) = match align {
+            fmt::Alignment::Left => (0, padding),
+            fmt::Alignment::Right => (padding, 0),
+            fmt::Alignment::Center => (padding / 2, padding - padding / 2),
+        };
+
+        let mut buf = [0; 4];
+        let fill = fill.encode_utf8(&mut buf).as_bytes();
+        let len = self.len();
+        let additional = padding.checked_mul(fill.len()).expect("capacity overflow");
+        self.reserve(additional);
+
+        let start = before * fill.len();
+        let end = start + len;
+        // SAFETY: The existing bytes are moved into the newly reserved space as a whole, and every
+        // byte around them gets overwritten with complete `fill` characters before we update the
+        // length, so the string stays valid UTF-8
+        unsafe {
+            let bytes = self.as_mut_bytes();
+            bytes.copy_within(..len, start);
+            for chunk in bytes[..start].chunks_exact_mut(fill.len()) {
+                chunk.copy_from_slice(fill);
+            }
+            for chunk in bytes[end..end + after * fill.len()].chunks_exact_mut(fill.len()) {
+                chunk.copy_from_slice(fill);
+            }
+            self.set_len(end + after * fill.len());
+        }
+    }
+
+    /// Converts an [`OsStr`](std::ffi::OsStr) into a [`CompactString`], replacing any invalid
+    /// Unicode sequences with the \<REPLACEMENT CHARACTER\> `'�'`.
+    ///
+    /// This is equivalent to [`OsStr::to_string_lossy`](std::ffi::OsStr::to_string_lossy), but
+    /// short strings get inlined.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_str::CompactString;
+    /// use std::ffi::OsStr;
+    ///
+    /// let compact = CompactString::from_os_str_lossy(OsStr::new("hello"));
+    /// assert_eq!(compact, "hello");
+    /// ```
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    #[inline]
+    pub fn from_os_str_lossy(s: &OsStr) -> Self {
+        CompactString::from(s.to_string_lossy())
+    }
+
     /// Decode a bytes slice as UTF-8 string, replacing any illegal codepoints
     ///
     /// # Examples
@@ -1552,117 +2902,586 @@ impl CompactString {
         CompactString::from_utf16x_lossy(v.as_ref(), u16::from_be, u16::from_be_bytes)
     }
 
-    /// Convert the [`CompactString`] into a [`String`].
+    /// Decode a slice of bytes as UTF-16 encoded string, picking the byte order from its byte
+    /// order mark.
+    ///
+    /// A leading `FF FE` selects little endian, and `FE FF` selects big endian, the byte order
+    /// mark itself is not part of the result. Without a byte order mark the input is decoded as
+    /// big endian, as specified by [RFC 2781](https://www.rfc-editor.org/rfc/rfc2781#section-4.3).
+    ///
+    /// # Errors
+    ///
+    /// If the slice has an odd number of bytes, or if it did not contain valid UTF-16 characters,
+    /// a [`Utf16Error`] is returned.
     ///
     /// # Examples
     ///
     /// ```
     /// # use compact_str::CompactString;
-    /// let s = CompactString::new("Hello world");
-    /// let s = s.into_string();
-    /// assert_eq!(s, "Hello world");
-    /// ```
-    pub fn into_string(self) -> String {
-        self.0.into_string()
+    /// let le = CompactString::from_utf16_auto(b"\xFF\xFEh\0i\0").unwrap();
+    /// let be = CompactString::from_utf16_auto(b"\xFE\xFF\0h\0i").unwrap();
+    /// let no_bom = CompactString::from_utf16_auto(b"\0h\0i").unwrap();
+    ///
+    /// assert_eq!(le, "hi");
+    /// assert_eq!(be, "hi");
+    /// assert_eq!(no_bom, "hi");
+    /// ```
+    pub fn from_utf16_auto(v: impl AsRef<[u8]>) -> Result<Self, Utf16Error> {
+        match v.as_ref() {
+            [0xFF, 0xFE, rest @ ..] => CompactString::from_utf16le(rest),
+            [0xFE, 0xFF, rest @ ..] => CompactString::from_utf16be(rest),
+            v => CompactString::from_utf16be(v),
+        }
     }
 
-    /// Convert a [`String`] into a [`CompactString`] _without inlining_.
+    /// Decode a [`UTF-32`](https://en.wikipedia.org/wiki/UTF-32) slice into a [`CompactString`],
+    /// returning an [`Err`] if any of the code units is not a valid Unicode scalar value.
     ///
-    /// Note: You probably don't need to use this method, instead you should use `From<String>`
-    /// which is implemented for [`CompactString`].
+    /// # Examples
+    /// ```
+    /// # use compact_str::CompactString;
+    /// let buf: &[u32] = &[0x1D11E, 0x006d, 0x0075, 0x0073, 0x0069, 0x0063];
+    /// let compact = CompactString::from_utf32(buf).unwrap();
+    /// assert_eq!(compact, "𝄞music");
     ///
-    /// This method exists incase your code is very sensitive to memory allocations. Normally when
-    /// converting a [`String`] to a [`CompactString`] we'll inline short strings onto the stack.
-    /// But this results in [`Drop`]-ing the original [`String`], which causes memory it owned on
-    /// the heap to be deallocated. Instead when using this method, we always reuse the buffer that
-    /// was previously owned by the [`String`], so no trips to the allocator are needed.
+    /// // a surrogate is not a valid scalar value
+    /// assert!(CompactString::from_utf32([0x0061, 0xD800]).is_err());
+    /// // neither is anything past U+10FFFF
+    /// assert!(CompactString::from_utf32([0x110000]).is_err());
+    /// ```
+    #[inline]
+    pub fn from_utf32<B: AsRef<[u32]>>(buf: B) -> Result<Self, Utf32Error> {
+        CompactString::decode_utf32(buf.as_ref().iter().copied(), 0, |c| {
+            char::from_u32(c).ok_or(Utf32Error(()))
+        })
+    }
+
+    /// Decode a UTF-32 slice into a [`CompactString`], replacing invalid code units with the
+    /// replacement character (`U+FFFD`), �.
     ///
     /// # Examples
-    ///
-    /// ### Short Strings
     /// ```
-    /// use compact_str::CompactString;
+    /// # use compact_str::CompactString;
+    /// let buf: &[u32] = &[0x1D11E, 0x006d, 0xD800, 0x0073, 0x110000];
+    /// assert_eq!(CompactString::from_utf32_lossy(buf), "𝄞m\u{FFFD}s\u{FFFD}");
+    /// ```
+    #[inline]
+    pub fn from_utf32_lossy<B: AsRef<[u32]>>(buf: B) -> Self {
+        CompactString::from_utf32x_lossy(buf.as_ref().iter().copied(), 0)
+    }
+
+    /// Decode a slice of bytes as UTF-32 encoded string, in little endian.
     ///
-    /// let short = "hello world".to_string();
-    /// let c_heap = CompactString::from_string_buffer(short);
+    /// # Errors
     ///
-    /// // using CompactString::from_string_buffer, we'll re-use the String's underlying buffer
-    /// assert!(c_heap.is_heap_allocated());
+    /// If the length of the slice is not a multiple of 4, or if it did not contain valid UTF-32
+    /// characters, a [`Utf32Error`] is returned.
     ///
-    /// // note: when Clone-ing a short heap allocated string, we'll eagerly inline at that point
-    /// let c_inline = c_heap.clone();
-    /// assert!(!c_inline.is_heap_allocated());
+    /// # Examples
     ///
-    /// assert_eq!(c_heap, c_inline);
     /// ```
-    ///
-    /// ### Longer Strings
+    /// # use compact_str::CompactString;
+    /// const CRAB: &[u8] = b"\x80\xf9\x01\x00!\x00\x00\x00";
+    /// let crab = CompactString::from_utf32le(CRAB).unwrap();
+    /// assert_eq!(crab, "🦀!");
     /// ```
-    /// use compact_str::CompactString;
+    #[inline]
+    pub fn from_utf32le(v: impl AsRef<[u8]>) -> Result<Self, Utf32Error> {
+        CompactString::from_utf32x(v.as_ref(), u32::from_le_bytes)
+    }
+
+    /// Decode a slice of bytes as UTF-32 encoded string, in big endian.
     ///
-    /// let x = "longer string that will be on the heap".to_string();
-    /// let c1 = CompactString::from(x);
+    /// # Errors
     ///
-    /// let y = "longer string that will be on the heap".to_string();
-    /// let c2 = CompactString::from_string_buffer(y);
+    /// If the length of the slice is not a multiple of 4, or if it did not contain valid UTF-32
+    /// characters, a [`Utf32Error`] is returned.
     ///
-    /// // for longer strings, we re-use the underlying String's buffer in both cases
-    /// assert!(c1.is_heap_allocated());
-    /// assert!(c2.is_heap_allocated());
-    /// ```
+    /// # Examples
     ///
-    /// ### Buffer Re-use
     /// ```
-    /// use compact_str::CompactString;
-    ///
-    /// let og = "hello world".to_string();
-    /// let og_addr = og.as_ptr();
-    ///
-    /// let mut c = CompactString::from_string_buffer(og);
-    /// let ex_addr = c.as_ptr();
-    ///
-    /// // When converting to/from String and CompactString with from_string_buffer we always re-use
-    /// // the same underlying allocated memory/buffer
-    /// assert_eq!(og_addr, ex_addr);
+    /// # use compact_str::CompactString;
+    /// const CRAB: &[u8] = b"\x00\x01\xf9\x80\x00\x00\x00!";
+    /// let crab = CompactString::from_utf32be(CRAB).unwrap();
+    /// assert_eq!(crab, "🦀!");
+    /// ```
+    #[inline]
+    pub fn from_utf32be(v: impl AsRef<[u8]>) -> Result<Self, Utf32Error> {
+        CompactString::from_utf32x(v.as_ref(), u32::from_be_bytes)
+    }
+
+    /// Lossy decode a slice of bytes as UTF-32 encoded string, in little endian.
     ///
-    /// let long = "this is a long string that will be on the heap".to_string();
-    /// let long_addr = long.as_ptr();
+    /// Any code unit that is not a valid Unicode scalar value is replaced by the
+    /// \<REPLACEMENT CHARACTER\> `'�'`, as is an incomplete code unit at the end of the input.
     ///
-    /// let mut long_c = CompactString::from(long);
-    /// let long_ex_addr = long_c.as_ptr();
+    /// # Examples
     ///
-    /// // When converting to/from String and CompactString with From<String>, we'll also re-use the
-    /// // underlying buffer, if the string is long, otherwise when converting to CompactString we
-    /// // eagerly inline
-    /// assert_eq!(long_addr, long_ex_addr);
+    /// ```
+    /// # use compact_str::CompactString;
+    /// // a surrogate, followed by a truncated code unit
+    /// const BROKEN: &[u8] = b"a\x00\x00\x00\x00\xd8\x00\x00b\x00";
+    /// let broken = CompactString::from_utf32le_lossy(BROKEN);
+    /// assert_eq!(broken, "a\u{FFFD}\u{FFFD}");
     /// ```
     #[inline]
-    #[track_caller]
-    pub fn from_string_buffer(s: String) -> Self {
-        let repr = Repr::from_string(s, false).unwrap_with_msg();
-        CompactString(repr)
+    pub fn from_utf32le_lossy(v: impl AsRef<[u8]>) -> Self {
+        CompactString::from_utf32x_bytes_lossy(v.as_ref(), u32::from_le_bytes)
     }
 
-    /// Returns a copy of this string where each character is mapped to its
-    /// ASCII lower case equivalent.
-    ///
-    /// ASCII letters 'A' to 'Z' are mapped to 'a' to 'z',
-    /// but non-ASCII letters are unchanged.
-    ///
-    /// To lowercase the value in-place, use [`str::make_ascii_lowercase`].
+    /// Lossy decode a slice of bytes as UTF-32 encoded string, in big endian.
     ///
-    /// To lowercase ASCII characters in addition to non-ASCII characters, use
-    /// [`CompactString::to_lowercase`].
+    /// Any code unit that is not a valid Unicode scalar value is replaced by the
+    /// \<REPLACEMENT CHARACTER\> `'�'`, as is an incomplete code unit at the end of the input.
     ///
     /// # Examples
     ///
     /// ```
-    /// use compact_str::CompactString;
-    /// let s = CompactString::new("Grüße, Jürgen ❤");
-    ///
-    /// assert_eq!("grüße, jürgen ❤", s.to_ascii_lowercase());
+    /// # use compact_str::CompactString;
+    /// // a code unit past U+10FFFF
+    /// const BROKEN: &[u8] = b"\x00\x00\x00a\x00\x11\x00\x00\x00\x00\x00b";
+    /// let broken = CompactString::from_utf32be_lossy(BROKEN);
+    /// assert_eq!(broken, "a\u{FFFD}b");
     /// ```
-    #[must_use = "to lowercase the value in-place, use `make_ascii_lowercase()`"]
+    #[inline]
+    pub fn from_utf32be_lossy(v: impl AsRef<[u8]>) -> Self {
+        CompactString::from_utf32x_bytes_lossy(v.as_ref(), u32::from_be_bytes)
+    }
+
+    // `usize::is_multiple_of` is newer than our MSRV
+    #[allow(clippy::manual_is_multiple_of)]
+    fn from_utf32x(v: &[u8], from_bytes: fn([u8; 4]) -> u32) -> Result<Self, Utf32Error> {
+        if v.len() % 4 != 0 {
+            // Input ended in the middle of a code unit.
+            return Err(Utf32Error(()));
+        }
+        let units = v
+            .chunks_exact(4)
+            .map(|b| from_bytes([b[0], b[1], b[2], b[3]]));
+        CompactString::decode_utf32(units, 0, |c| char::from_u32(c).ok_or(Utf32Error(())))
+    }
+
+    fn from_utf32x_bytes_lossy(v: &[u8], from_bytes: fn([u8; 4]) -> u32) -> Self {
+        let chunks = v.chunks_exact(4);
+        let trailing_extra_bytes = !chunks.remainder().is_empty();
+        // make room for the replacement character of the trailing bytes up front
+        let extra = if trailing_extra_bytes { "�".len() } else { 0 };
+
+        let units = chunks.map(|b| from_bytes([b[0], b[1], b[2], b[3]]));
+        let mut result = CompactString::from_utf32x_lossy(units, extra);
+        if trailing_extra_bytes {
+            result.push_str("�");
+        }
+        result
+    }
+
+    fn from_utf32x_lossy<I: Iterator<Item = u32> + Clone>(units: I, extra: usize) -> Self {
+        let res = CompactString::decode_utf32(units, extra, |c| {
+            Ok::<_, core::convert::Infallible>(
+                char::from_u32(c).unwrap_or(char::REPLACEMENT_CHARACTER),
+            )
+        });
+        match res {
+            Ok(s) => s,
+            Err(never) => match never {},
+        }
+    }
+
+    /// Shared implementation of the `from_utf32*` constructors, `decode` decides what to do with
+    /// invalid code units, and `extra` bytes are reserved on top of the decoded length.
+    ///
+    /// Unlike UTF-16 every code unit is exactly one `char`, so decoding is cheap enough that we
+    /// make a first pass to compute the exact UTF-8 length, and then write into a [`CompactString`]
+    /// of exactly that capacity, which is inline whenever the result fits.
+    fn decode_utf32<I: Iterator<Item = u32> + Clone, E>(
+        units: I,
+        extra: usize,
+        decode: impl Fn(u32) -> Result<char, E>,
+    ) -> Result<Self, E> {
+        let mut len = extra;
+        for c in units.clone() {
+            len += decode(c)?.len_utf8();
+        }
+
+        let mut result = CompactString::with_capacity(len);
+        for c in units {
+            result.push(decode(c)?);
+        }
+        Ok(result)
+    }
+
+    /// Convert the [`CompactString`] into a [`String`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use compact_str::CompactString;
+    /// let s = CompactString::new("Hello world");
+    /// let s = s.into_string();
+    /// assert_eq!(s, "Hello world");
+    /// ```
+    pub fn into_string(self) -> String {
+        self.0.into_string()
+    }
+
+    /// Converts the [`CompactString`] into an [`Arc<str>`](alloc::sync::Arc), for cheaply sharing
+    /// the same string between many owners, e.g. when fanning it out to many tasks.
+    ///
+    /// Cloning a [`CompactString`] is a plain copy while it's inline, but a heap allocated one
+    /// gets copied into a new allocation on every clone. An [`Arc<str>`](alloc::sync::Arc) costs
+    /// a single allocation up front, after which every clone is just a reference count increment,
+    /// no matter how long the string is.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use compact_str::CompactString;
+    /// use std::sync::Arc;
+    ///
+    /// let s = CompactString::new("a long string we want to share between many tasks");
+    /// let shared: Arc<str> = s.clone().into_arc();
+    ///
+    /// let handle = Arc::clone(&shared);
+    /// assert!(Arc::ptr_eq(&shared, &handle));
+    /// assert_eq!(s, shared);
+    /// assert_eq!(shared, s);
+    /// ```
+    #[cfg(target_has_atomic = "ptr")]
+    #[inline]
+    pub fn into_arc(self) -> alloc::sync::Arc<str> {
+        alloc::sync::Arc::from(self.as_str())
+    }
+
+    /// Convert the [`CompactString`] into a [`Vec<u8>`] of its UTF-8 bytes.
+    ///
+    /// If the [`CompactString`] is heap allocated its buffer is transferred to the returned
+    /// [`Vec`] in `O(1)`, otherwise the contents are copied into a new allocation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use compact_str::CompactString;
+    /// let s = CompactString::new("this is a long string that will be on the heap");
+    /// let ptr = s.as_ptr();
+    ///
+    /// let bytes = s.into_bytes_vec();
+    /// assert_eq!(bytes, b"this is a long string that will be on the heap");
+    /// assert_eq!(bytes.as_ptr(), ptr);
+    /// ```
+    #[inline]
+    pub fn into_bytes_vec(self) -> Vec<u8> {
+        if self.is_heap_allocated() {
+            self.into_string().into_bytes()
+        } else {
+            self.as_bytes().to_vec()
+        }
+    }
+
+    /// Returns the byte index of the first occurrence of `byte` in the [`CompactString`].
+    ///
+    /// This skips the [`str`] pattern machinery, and with the `memchr` feature enabled uses
+    /// SIMD accelerated searching, which makes it well suited for scanning long strings.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use compact_str::CompactString;
+    /// let s = CompactString::new("level=info msg=hello");
+    ///
+    /// assert_eq!(s.find_byte(b'='), Some(5));
+    /// assert_eq!(s.find_byte(b'!'), None);
+    /// ```
+    #[inline]
+    pub fn find_byte(&self, byte: u8) -> Option<usize> {
+        find_byte(byte, self.as_bytes())
+    }
+
+    /// Returns an iterator over the substrings of the [`CompactString`] separated by the ASCII
+    /// `byte`, like [`str::split`] with a single [`char`], but searching with
+    /// [`CompactString::find_byte`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `byte` is not ASCII, since splitting on it could produce invalid UTF-8.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use compact_str::CompactString;
+    /// let s = CompactString::new("2024-01-01\tINFO\t\tstarted 🚀");
+    /// let fields: Vec<&str> = s.split_byte(b'\t').collect();
+    ///
+    /// assert_eq!(fields, ["2024-01-01", "INFO", "", "started 🚀"]);
+    /// ```
+    #[inline]
+    #[track_caller]
+    pub fn split_byte(&self, byte: u8) -> SplitByte<'_> {
+        assert!(byte.is_ascii(), "separator byte must be ASCII");
+        SplitByte {
+            remainder: Some(self.as_str()),
+            byte,
+        }
+    }
+
+    /// Returns the number of non-overlapping occurrences of `needle` in the [`CompactString`],
+    /// same as `s.matches(needle).count()`.
+    ///
+    /// This skips the [`str`] pattern machinery, and with the `memchr` feature enabled single
+    /// byte needles are counted with SIMD, while longer needles are searched for with
+    /// [`memchr::memmem`](https://docs.rs/memchr/2/memchr/memmem/index.html).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use compact_str::CompactString;
+    /// let s = CompactString::new("a,b,,c");
+    ///
+    /// assert_eq!(s.count_matches(","), 3);
+    /// assert_eq!(s.count_matches(",,"), 1);
+    /// assert_eq!(s.count_matches("d"), 0);
+    /// ```
+    #[inline]
+    pub fn count_matches(&self, needle: &str) -> usize {
+        #[cfg(feature = "memchr")]
+        if let [byte] = *needle.as_bytes() {
+            return memchr::memchr_iter(byte, self.as_bytes()).count();
+        }
+        self.find_iter(needle).count()
+    }
+
+    /// Returns an iterator over the byte indices of the non-overlapping occurrences of `needle`
+    /// in the [`CompactString`], like [`str::match_indices`] but without the matched substring.
+    ///
+    /// Like [`CompactString::count_matches`] this uses SIMD accelerated searching when the
+    /// `memchr` feature is enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use compact_str::CompactString;
+    /// let s = CompactString::new("abcXXXabcYYYabc");
+    /// let indices: Vec<usize> = s.find_iter("abc").collect();
+    ///
+    /// assert_eq!(indices, [0, 6, 12]);
+    /// ```
+    #[inline]
+    pub fn find_iter<'a>(&'a self, needle: &'a str) -> FindIter<'a> {
+        FindIter {
+            haystack: self.as_str(),
+            needle,
+            position: Some(0),
+        }
+    }
+
+    /// Converts the [`CompactString`] into an owning iterator over its [`char`]s.
+    ///
+    /// Unlike [`str::chars`] the returned iterator doesn't borrow from the [`CompactString`], so
+    /// it can be held across `.await` points, or returned from a function. Inline strings are kept
+    /// by value, so this never allocates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use compact_str::CompactString;
+    /// let s = CompactString::new("héllo");
+    /// let mut chars = s.into_chars();
+    ///
+    /// assert_eq!(chars.next(), Some('h'));
+    /// assert_eq!(chars.next_back(), Some('o'));
+    /// assert_eq!(chars.as_str(), "éll");
+    /// assert_eq!(chars.collect::<String>(), "éll");
+    /// ```
+    #[inline]
+    pub fn into_chars(self) -> IntoChars {
+        let back = self.len();
+        IntoChars {
+            string: self,
+            front: 0,
+            back,
+        }
+    }
+
+    /// Converts the [`CompactString`] into an owning iterator over its [`char`]s and their byte
+    /// positions, like [`str::char_indices`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use compact_str::CompactString;
+    /// let s = CompactString::new("héllo");
+    /// let indices: Vec<(usize, char)> = s.into_char_indices().collect();
+    ///
+    /// assert_eq!(indices, [(0, 'h'), (1, 'é'), (3, 'l'), (4, 'l'), (5, 'o')]);
+    /// ```
+    #[inline]
+    pub fn into_char_indices(self) -> IntoCharIndices {
+        IntoCharIndices {
+            chars: self.into_chars(),
+        }
+    }
+
+    /// Converts the [`CompactString`] into an owning iterator over its bytes.
+    ///
+    /// This is the owning counterpart of [`str::bytes`], which is also available on a
+    /// [`CompactString`] through [`Deref`]. Like [`CompactString::into_chars`], the returned
+    /// iterator doesn't borrow from the [`CompactString`] and inline strings are kept by value, so
+    /// it can be held across `.await` points without allocating.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use compact_str::CompactString;
+    /// let s = CompactString::new("hello");
+    /// let mut bytes = s.into_bytes_iter();
+    ///
+    /// assert_eq!(bytes.next(), Some(b'h'));
+    /// assert_eq!(bytes.next_back(), Some(b'o'));
+    /// assert_eq!(bytes.as_slice(), b"ell");
+    /// assert_eq!(bytes.len(), 3);
+    /// ```
+    #[inline]
+    pub fn into_bytes_iter(self) -> IntoBytesIter {
+        let back = self.len();
+        IntoBytesIter {
+            string: self,
+            front: 0,
+            back,
+        }
+    }
+
+    /// Convert a [`String`] into a [`CompactString`] _without inlining_.
+    ///
+    /// Note: You probably don't need to use this method, instead you should use `From<String>`
+    /// which is implemented for [`CompactString`].
+    ///
+    /// This method exists incase your code is very sensitive to memory allocations. Normally when
+    /// converting a [`String`] to a [`CompactString`] we'll inline short strings onto the stack.
+    /// But this results in [`Drop`]-ing the original [`String`], which causes memory it owned on
+    /// the heap to be deallocated. Instead when using this method, we always reuse the buffer that
+    /// was previously owned by the [`String`], so no trips to the allocator are needed.
+    ///
+    /// This conversion is `O(1)` and never allocates or deallocates. The one exception is a
+    /// [`String`] with a capacity of more than 16 MB on a 32-bit target, which gets copied. To go
+    /// back to a [`String`] without allocating, see [`CompactString::into_string_buffer`].
+    ///
+    /// # Examples
+    ///
+    /// ### Short Strings
+    /// ```
+    /// use compact_str::CompactString;
+    ///
+    /// let short = "hello world".to_string();
+    /// let c_heap = CompactString::from_string_buffer(short);
+    ///
+    /// // using CompactString::from_string_buffer, we'll re-use the String's underlying buffer
+    /// assert!(c_heap.is_heap_allocated());
+    ///
+    /// // note: when Clone-ing a short heap allocated string, we'll eagerly inline at that point
+    /// let c_inline = c_heap.clone();
+    /// assert!(!c_inline.is_heap_allocated());
+    ///
+    /// assert_eq!(c_heap, c_inline);
+    /// ```
+    ///
+    /// ### Longer Strings
+    /// ```
+    /// use compact_str::CompactString;
+    ///
+    /// let x = "longer string that will be on the heap".to_string();
+    /// let c1 = CompactString::from(x);
+    ///
+    /// let y = "longer string that will be on the heap".to_string();
+    /// let c2 = CompactString::from_string_buffer(y);
+    ///
+    /// // for longer strings, we re-use the underlying String's buffer in both cases
+    /// assert!(c1.is_heap_allocated());
+    /// assert!(c2.is_heap_allocated());
+    /// ```
+    ///
+    /// ### Buffer Re-use
+    /// ```
+    /// use compact_str::CompactString;
+    ///
+    /// let og = "hello world".to_string();
+    /// let og_addr = og.as_ptr();
+    ///
+    /// let mut c = CompactString::from_string_buffer(og);
+    /// let ex_addr = c.as_ptr();
+    ///
+    /// // When converting to/from String and CompactString with from_string_buffer we always re-use
+    /// // the same underlying allocated memory/buffer
+    /// assert_eq!(og_addr, ex_addr);
+    ///
+    /// let long = "this is a long string that will be on the heap".to_string();
+    /// let long_addr = long.as_ptr();
+    ///
+    /// let mut long_c = CompactString::from(long);
+    /// let long_ex_addr = long_c.as_ptr();
+    ///
+    /// // When converting to/from String and CompactString with From<String>, we'll also re-use the
+    /// // underlying buffer, if the string is long, otherwise when converting to CompactString we
+    /// // eagerly inline
+    /// assert_eq!(long_addr, long_ex_addr);
+    /// ```
+    #[inline]
+    #[track_caller]
+    pub fn from_string_buffer(s: String) -> Self {
+        let repr = Repr::from_string(s, false).unwrap_with_msg();
+        CompactString(repr)
+    }
+
+    /// Convert a [`CompactString`] into a [`String`], handing over the heap buffer if there is
+    /// one. The inverse of [`CompactString::from_string_buffer`].
+    ///
+    /// If the [`CompactString`] is heap allocated, the conversion is `O(1)` and never allocates or
+    /// deallocates; the returned [`String`] takes ownership of the buffer, with the same capacity.
+    /// Only inline and static strings need to be copied into a newly allocated [`String`], and
+    /// empty ones don't allocate at all. As with [`CompactString::from_string_buffer`], a buffer
+    /// with more than 16 MB of capacity on a 32-bit target gets copied.
+    ///
+    /// This behaves the same as [`CompactString::into_string`], but spells out the guarantee, so
+    /// a round trip through [`CompactString::from_string_buffer`] and back is allocation free.
+    ///
+    /// # Examples
+    /// ```
+    /// use compact_str::CompactString;
+    ///
+    /// let og = "hello world".to_string();
+    /// let og_addr = og.as_ptr();
+    /// let og_cap = og.capacity();
+    ///
+    /// let compact = CompactString::from_string_buffer(og);
+    /// let string = compact.into_string_buffer();
+    ///
+    /// // the same buffer made it all the way through
+    /// assert_eq!(string.as_ptr(), og_addr);
+    /// assert_eq!(string.capacity(), og_cap);
+    /// ```
+    #[inline]
+    pub fn into_string_buffer(self) -> String {
+        self.0.into_string()
+    }
+
+    /// Returns a copy of this string where each character is mapped to its
+    /// ASCII lower case equivalent.
+    ///
+    /// ASCII letters 'A' to 'Z' are mapped to 'a' to 'z',
+    /// but non-ASCII letters are unchanged.
+    ///
+    /// To lowercase the value in-place, use [`str::make_ascii_lowercase`].
+    ///
+    /// To lowercase ASCII characters in addition to non-ASCII characters, use
+    /// [`CompactString::to_lowercase`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use compact_str::CompactString;
+    /// let s = CompactString::new("Grüße, Jürgen ❤");
+    ///
+    /// assert_eq!("grüße, jürgen ❤", s.to_ascii_lowercase());
+    /// ```
+    #[must_use = "to lowercase the value in-place, use `make_ascii_lowercase()`"]
     #[inline]
     pub fn to_ascii_lowercase(&self) -> Self {
         let mut s = self.clone();
@@ -1814,15 +3633,6 @@ impl CompactString {
                 && !case_ignorable_then_cased(from[i + 2..].chars());
             to.push_str(if is_word_final { "ς" } else { "σ" });
         }
-
-        fn case_ignorable_then_cased<I: Iterator<Item = char>>(mut iter: I) -> bool {
-            use unicode_data::case_ignorable::lookup as Case_Ignorable;
-            use unicode_data::cased::lookup as Cased;
-            match iter.find(|&c| !Case_Ignorable(c)) {
-                Some(c) => Cased(c),
-                None => false,
-            }
-        }
     }
 
     /// Returns the uppercase equivalent of this string slice, as a new [`CompactString`].
@@ -1916,6 +3726,312 @@ impl CompactString {
 
         out
     }
+
+    /// Converts this string to its lowercase equivalent in-place, with the same Unicode semantics
+    /// as [`CompactString::to_lowercase`].
+    ///
+    /// Unlike [`str::make_ascii_lowercase`] this maps every character, not just ASCII ones. Some
+    /// characters change length when lowercased, in which case the rest of the string is shifted
+    /// over within the same buffer, so a string that was inline stays inline as long as the result
+    /// still fits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use compact_str::CompactString;
+    /// let mut s = CompactString::new("ὈΔΥΣΣΕΎΣ");
+    /// s.make_lowercase();
+    ///
+    /// assert_eq!(s, "ὀδυσσεύς");
+    /// assert!(!s.is_heap_allocated());
+    /// ```
+    #[cfg(feature = "unicode-case")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "unicode-case")))]
+    pub fn make_lowercase(&mut self) {
+        self.make_case(true)
+    }
+
+    /// Converts this string to its uppercase equivalent in-place, with the same Unicode semantics
+    /// as [`CompactString::to_uppercase`].
+    ///
+    /// Unlike [`str::make_ascii_uppercase`] this maps every character, not just ASCII ones. A
+    /// character can uppercase into multiple characters, e.g. `ß` becomes `SS`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use compact_str::CompactString;
+    /// let mut s = CompactString::new("tschüß");
+    /// s.make_uppercase();
+    ///
+    /// assert_eq!(s, "TSCHÜSS");
+    /// ```
+    #[cfg(feature = "unicode-case")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "unicode-case")))]
+    pub fn make_uppercase(&mut self) {
+        self.make_case(false)
+    }
+
+    /// Maps every character of the string to its lowercase or uppercase equivalent in a single
+    /// pass, reading from the front of the unconverted tail and writing behind it. If a mapping
+    /// would overwrite bytes we haven't read yet, the tail is shifted further back first.
+    #[cfg(feature = "unicode-case")]
+    fn make_case(&mut self, lowercase: bool) {
+        let was_heap_allocated = self.is_heap_allocated();
+        let mut len = self.len();
+        let mut read = 0;
+        let mut write = 0;
+
+        // SAFETY: Bytes `0..write` are always the converted chars, `read..len` are the unconverted
+        // tail, which begins on a char boundary. Whatever is between them is overwritten with
+        // zeros before the buffer is handed back to `CompactString`, so it's always valid UTF-8
+        // when observed, and the final length is `write`.
+        let mut bytes = unsafe { self.as_mut_bytes() };
+
+        while read < len {
+            let byte = bytes[read];
+            if byte.is_ascii() {
+                bytes[write] = if lowercase {
+                    byte.to_ascii_lowercase()
+                } else {
+                    byte.to_ascii_uppercase()
+                };
+                read += 1;
+                write += 1;
+                continue;
+            }
+
+            // SAFETY: See above, `read..len` hasn't been modified and starts on a char boundary
+            let rest = unsafe { core::str::from_utf8_unchecked(&bytes[read..len]) };
+            let c = match rest.chars().next() {
+                Some(c) => c,
+                None => break,
+            };
+            let tail = read + c.len_utf8();
+
+            // The longest mapping of a single char is 3 chars
+            let mut mapped = ['\0'; 3];
+            let count = if lowercase && c == 'Σ' {
+                // SAFETY: See above, `0..write` are whole converted chars
+                let converted = unsafe { core::str::from_utf8_unchecked(&bytes[..write]) };
+                let is_word_final = case_ignorable_then_cased(converted.chars().rev())
+                    && !case_ignorable_then_cased(rest[c.len_utf8()..].chars());
+                mapped[0] = if is_word_final { 'ς' } else { 'σ' };
+                1
+            } else if lowercase {
+                fill_mapping(&mut mapped, c.to_lowercase())
+            } else {
+                fill_mapping(&mut mapped, c.to_uppercase())
+            };
+            let mapped = &mapped[..count];
+            let mapped_len: usize = mapped.iter().map(|c| c.len_utf8()).sum();
+
+            read = tail;
+            if write + mapped_len > read {
+                // Make room by shifting the tail back, by at least half its length so growing a
+                // long string with many expanding chars stays linear
+                let gap = (write + mapped_len - read).max((len - read) / 2);
+                bytes[write..read].fill(0);
+
+                self.reserve(gap);
+                // SAFETY: We zeroed the only bytes that could be invalid UTF-8 above, and the
+                // newly initialized bytes get zeroed too before we update the length
+                unsafe {
+                    let buf = self.as_mut_bytes();
+                    buf.copy_within(read..len, read + gap);
+                    buf[read..read + gap].fill(0);
+                    self.set_len(len + gap);
+                    bytes = self.as_mut_bytes();
+                }
+                len += gap;
+                read += gap;
+            }
+
+            for c in mapped {
+                write += c.encode_utf8(&mut bytes[write..]).len();
+            }
+        }
+
+        // SAFETY: `0..write` are the converted chars
+        unsafe { self.set_len(write) };
+
+        // Growing may have moved an inline string onto the heap, move it back if it fits
+        if !was_heap_allocated && self.is_heap_allocated() && write <= MAX_INLINE_SIZE {
+            self.shrink_to_fit();
+        }
+
+        fn fill_mapping(mapped: &mut [char; 3], chars: impl Iterator<Item = char>) -> usize {
+            let mut count = 0;
+            for (slot, c) in mapped.iter_mut().zip(chars) {
+                *slot = c;
+                count += 1;
+            }
+            count
+        }
+    }
+
+    /// Returns a copy of this string with `f` applied to every character, like
+    /// `s.chars().map(f).collect()`.
+    ///
+    /// The result is allocated with `self.len()` bytes of capacity up front, which is exact when
+    /// `f` maps every character to one of the same UTF-8 width, e.g. ROT13 or masking ASCII
+    /// characters. If a mapped character is wider, the string grows like it would for
+    /// [`CompactString::push`].
+    ///
+    /// To map the characters in-place, use [`CompactString::map_chars_in_place`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use compact_str::CompactString;
+    /// let s = CompactString::new("Hello, World!");
+    ///
+    /// let rot13 = s.map_chars(|c| match c {
+    ///     'a'..='m' | 'A'..='M' => (c as u8 + 13) as char,
+    ///     'n'..='z' | 'N'..='Z' => (c as u8 - 13) as char,
+    ///     _ => c,
+    /// });
+    /// assert_eq!(rot13, "Uryyb, Jbeyq!");
+    /// ```
+    #[must_use = "to map the characters in-place, use `map_chars_in_place()`"]
+    pub fn map_chars(&self, mut f: impl FnMut(char) -> char) -> Self {
+        let mut out = CompactString::with_capacity(self.len());
+        for c in self.chars() {
+            out.push(f(c));
+        }
+        out
+    }
+
+    /// Applies `f` to every character of the string, in-place.
+    ///
+    /// As long as `f` maps each character to one with the same UTF-8 width, the new character is
+    /// written over the old one without allocating. If `f` ever returns a character of a different
+    /// width, the rest of the string is mapped into a new [`CompactString`] instead, so the result
+    /// is always the same as [`CompactString::map_chars`]. `f` is called exactly once per
+    /// character either way.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use compact_str::CompactString;
+    /// let mut s = CompactString::new("card 4111 1111 1111 1234");
+    ///
+    /// s.map_chars_in_place(|c| if c.is_ascii_digit() { '*' } else { c });
+    /// assert_eq!(s, "card **** **** **** ****");
+    ///
+    /// // characters can change width, too
+    /// s.map_chars_in_place(|c| if c == '*' { '•' } else { c });
+    /// assert_eq!(s, "card •••• •••• •••• ••••");
+    /// ```
+    pub fn map_chars_in_place(&mut self, mut f: impl FnMut(char) -> char) {
+        let len = self.len();
+        let mut pos = 0;
+
+        // SAFETY: We only ever overwrite a char with another char of the same UTF-8 width, so the
+        // buffer is valid UTF-8 after every write
+        let bytes = unsafe { self.as_mut_bytes() };
+
+        while pos < len {
+            // SAFETY: `pos` always lands on a char boundary of a valid UTF-8 string
+            let rest = unsafe { core::str::from_utf8_unchecked(&bytes[pos..len]) };
+            let c = match rest.chars().next() {
+                Some(c) => c,
+                None => break,
+            };
+            let mapped = f(c);
+
+            if mapped.len_utf8() != c.len_utf8() {
+                let mut out = CompactString::with_capacity(len);
+                out.push_str(&self[..pos]);
+                out.push(mapped);
+                for c in self[pos + c.len_utf8()..].chars() {
+                    out.push(f(c));
+                }
+                *self = out;
+                return;
+            }
+
+            mapped.encode_utf8(&mut bytes[pos..]);
+            pos += c.len_utf8();
+        }
+    }
+
+    /// Returns a copy of this string with each character escaped using [`char::escape_debug`],
+    /// the same as [`str::escape_debug`] but collected into a [`CompactString`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use compact_str::CompactString;
+    /// let s = CompactString::new("❤\n!");
+    ///
+    /// assert_eq!(s.escape_debug_compact(), "❤\\n!");
+    /// assert!(!s.escape_debug_compact().is_heap_allocated());
+    /// ```
+    #[must_use = "this returns the escaped string as a new CompactString, \
+                  without modifying the original"]
+    pub fn escape_debug_compact(&self) -> Self {
+        collect_escaped(self.as_str().escape_debug())
+    }
+
+    /// Returns a copy of this string with each character escaped using
+    /// [`char::escape_default`], the same as [`str::escape_default`] but collected into a
+    /// [`CompactString`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use compact_str::CompactString;
+    /// let s = CompactString::new("❤\n!");
+    ///
+    /// assert_eq!(s.escape_default_compact(), "\\u{2764}\\n!");
+    /// ```
+    #[must_use = "this returns the escaped string as a new CompactString, \
+                  without modifying the original"]
+    pub fn escape_default_compact(&self) -> Self {
+        collect_escaped(self.as_str().escape_default())
+    }
+
+    /// Returns a copy of this string with each character escaped using
+    /// [`char::escape_unicode`], the same as [`str::escape_unicode`] but collected into a
+    /// [`CompactString`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use compact_str::CompactString;
+    /// let s = CompactString::new("❤\n!");
+    ///
+    /// assert_eq!(s.escape_unicode_compact(), "\\u{2764}\\u{a}\\u{21}");
+    /// ```
+    #[must_use = "this returns the escaped string as a new CompactString, \
+                  without modifying the original"]
+    pub fn escape_unicode_compact(&self) -> Self {
+        collect_escaped(self.as_str().escape_unicode())
+    }
+}
+
+/// Collects one of the `str::escape_*` iterators into a [`CompactString`].
+///
+/// Escaping is cheap enough that we run the iterator twice, once to compute the exact length of
+/// the output, so we only allocate once, and not at all if the result fits inline.
+#[inline]
+fn collect_escaped(escaped: impl Iterator<Item = char> + Clone) -> CompactString {
+    let len = escaped.clone().map(char::len_utf8).sum();
+    let mut out = CompactString::with_capacity(len);
+    out.extend(escaped);
+    out
+}
+
+/// Returns whether the first char that isn't `Case_Ignorable` is `Cased`, used to determine
+/// whether a `Σ` is at the end of a word.
+fn case_ignorable_then_cased<I: Iterator<Item = char>>(mut iter: I) -> bool {
+    use unicode_data::case_ignorable::lookup as Case_Ignorable;
+    use unicode_data::cased::lookup as Cased;
+    match iter.find(|&c| !Case_Ignorable(c)) {
+        Some(c) => Cased(c),
+        None => false,
+    }
 }
 
 /// Converts the bytes while the bytes are still ascii.
@@ -1968,12 +4084,22 @@ fn convert_while_ascii(b: &[u8], convert: fn(&u8) -> u8) -> CompactString {
 
 impl Clone for CompactString {
     #[inline]
+    #[cfg_attr(all(feature = "clone-tracking", debug_assertions), track_caller)]
     fn clone(&self) -> Self {
+        #[cfg(all(feature = "clone-tracking", debug_assertions))]
+        if self.is_heap_allocated() {
+            features::clone_tracking::track_heap_clone(self.len());
+        }
         Self(self.0.clone())
     }
 
     #[inline]
+    #[cfg_attr(all(feature = "clone-tracking", debug_assertions), track_caller)]
     fn clone_from(&mut self, source: &Self) {
+        #[cfg(all(feature = "clone-tracking", debug_assertions))]
+        if source.is_heap_allocated() {
+            features::clone_tracking::track_heap_clone(source.len());
+        }
         self.0.clone_from(&source.0)
     }
 }
@@ -2023,6 +4149,13 @@ impl AsRef<[u8]> for CompactString {
     }
 }
 
+impl AsMut<str> for CompactString {
+    #[inline]
+    fn as_mut(&mut self) -> &mut str {
+        self.as_mut_str()
+    }
+}
+
 impl Borrow<str> for CompactString {
     #[inline]
     fn borrow(&self) -> &str {
@@ -2040,80 +4173,123 @@ impl BorrowMut<str> for CompactString {
 impl Eq for CompactString {}
 
 impl<T: AsRef<str> + ?Sized> PartialEq<T> for CompactString {
+    #[inline]
     fn eq(&self, other: &T) -> bool {
-        self.as_str() == other.as_ref()
+        self.0.eq_str(other.as_ref())
     }
 }
 
 impl PartialEq<CompactString> for &CompactString {
+    #[inline]
     fn eq(&self, other: &CompactString) -> bool {
-        self.as_str() == other.as_str()
+        self.0.eq_str(other)
     }
 }
 
 impl PartialEq<CompactString> for String {
+    #[inline]
     fn eq(&self, other: &CompactString) -> bool {
-        self.as_str() == other.as_str()
+        other.0.eq_str(self)
     }
 }
 
 impl PartialEq<&CompactString> for String {
+    #[inline]
     fn eq(&self, other: &&CompactString) -> bool {
-        self.as_str() == other.as_str()
+        other.0.eq_str(self)
     }
 }
 
 impl PartialEq<CompactString> for &String {
+    #[inline]
     fn eq(&self, other: &CompactString) -> bool {
-        self.as_str() == other.as_str()
+        other.0.eq_str(self)
     }
 }
 
 impl PartialEq<CompactString> for str {
+    #[inline]
     fn eq(&self, other: &CompactString) -> bool {
-        self == other.as_str()
+        other.0.eq_str(self)
     }
 }
 
 impl PartialEq<&'_ CompactString> for str {
+    #[inline]
     fn eq(&self, other: &&CompactString) -> bool {
-        self == other.as_str()
+        other.0.eq_str(self)
     }
 }
 
 impl PartialEq<CompactString> for &str {
+    #[inline]
     fn eq(&self, other: &CompactString) -> bool {
-        *self == other.as_str()
+        other.0.eq_str(self)
     }
 }
 
 impl PartialEq<CompactString> for &&str {
+    #[inline]
     fn eq(&self, other: &CompactString) -> bool {
-        **self == other.as_str()
+        other.0.eq_str(self)
     }
 }
 
 impl PartialEq<CompactString> for Cow<'_, str> {
+    #[inline]
     fn eq(&self, other: &CompactString) -> bool {
-        *self == other.as_str()
+        other.0.eq_str(self)
     }
 }
 
 impl PartialEq<CompactString> for &Cow<'_, str> {
+    #[inline]
     fn eq(&self, other: &CompactString) -> bool {
-        *self == other.as_str()
+        other.0.eq_str(self)
     }
 }
 
 impl PartialEq<String> for &CompactString {
+    #[inline]
     fn eq(&self, other: &String) -> bool {
-        self.as_str() == other.as_str()
+        self.0.eq_str(other)
     }
 }
 
 impl PartialEq<Cow<'_, str>> for &CompactString {
+    #[inline]
     fn eq(&self, other: &Cow<'_, str>) -> bool {
-        self.as_str() == other
+        self.0.eq_str(other)
+    }
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl PartialEq<CompactString> for alloc::sync::Arc<str> {
+    #[inline]
+    fn eq(&self, other: &CompactString) -> bool {
+        other.0.eq_str(self)
+    }
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl PartialEq<&CompactString> for alloc::sync::Arc<str> {
+    #[inline]
+    fn eq(&self, other: &&CompactString) -> bool {
+        other.0.eq_str(self)
+    }
+}
+
+impl PartialEq<CompactString> for alloc::rc::Rc<str> {
+    #[inline]
+    fn eq(&self, other: &CompactString) -> bool {
+        other.0.eq_str(self)
+    }
+}
+
+impl PartialEq<&CompactString> for alloc::rc::Rc<str> {
+    #[inline]
+    fn eq(&self, other: &&CompactString) -> bool {
+        other.0.eq_str(self)
     }
 }
 
@@ -2129,6 +4305,32 @@ impl PartialOrd for CompactString {
     }
 }
 
+#[cfg(target_has_atomic = "ptr")]
+impl PartialOrd<alloc::sync::Arc<str>> for CompactString {
+    fn partial_cmp(&self, other: &alloc::sync::Arc<str>) -> Option<Ordering> {
+        self.as_str().partial_cmp(&**other)
+    }
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl PartialOrd<CompactString> for alloc::sync::Arc<str> {
+    fn partial_cmp(&self, other: &CompactString) -> Option<Ordering> {
+        (**self).partial_cmp(other.as_str())
+    }
+}
+
+impl PartialOrd<alloc::rc::Rc<str>> for CompactString {
+    fn partial_cmp(&self, other: &alloc::rc::Rc<str>) -> Option<Ordering> {
+        self.as_str().partial_cmp(&**other)
+    }
+}
+
+impl PartialOrd<CompactString> for alloc::rc::Rc<str> {
+    fn partial_cmp(&self, other: &CompactString) -> Option<Ordering> {
+        (**self).partial_cmp(other.as_str())
+    }
+}
+
 impl Hash for CompactString {
     fn hash<H: Hasher>(&self, state: &mut H) {
         self.as_str().hash(state)
@@ -2160,6 +4362,51 @@ impl<'a> From<&'a String> for CompactString {
     }
 }
 
+impl<'a> From<&'a mut str> for CompactString {
+    #[inline]
+    #[track_caller]
+    fn from(s: &'a mut str) -> Self {
+        CompactString::new(s)
+    }
+}
+
+impl<'a> From<&'a Box<str>> for CompactString {
+    #[inline]
+    #[track_caller]
+    fn from(s: &'a Box<str>) -> Self {
+        CompactString::new(s)
+    }
+}
+
+/// Copies the contents of the shared string, since the buffer is owned by the [`Rc`](alloc::rc::Rc)
+/// it can't be re-used. Like [`CompactString::new`], short strings get inlined.
+impl From<alloc::rc::Rc<String>> for CompactString {
+    #[inline]
+    #[track_caller]
+    fn from(s: alloc::rc::Rc<String>) -> Self {
+        CompactString::new(s.as_str())
+    }
+}
+
+/// Copies the contents of the shared string, short strings get inlined.
+impl From<alloc::rc::Rc<str>> for CompactString {
+    #[inline]
+    #[track_caller]
+    fn from(s: alloc::rc::Rc<str>) -> Self {
+        CompactString::new(&s)
+    }
+}
+
+/// Copies the contents of the shared string, short strings get inlined.
+#[cfg(target_has_atomic = "ptr")]
+impl From<alloc::sync::Arc<str>> for CompactString {
+    #[inline]
+    #[track_caller]
+    fn from(s: alloc::sync::Arc<str>) -> Self {
+        CompactString::new(&s)
+    }
+}
+
 impl<'a> From<Cow<'a, str>> for CompactString {
     fn from(cow: Cow<'a, str>) -> Self {
         match cow {
@@ -2180,6 +4427,141 @@ impl From<Box<str>> for CompactString {
     }
 }
 
+impl<'a> TryFrom<&'a [u8]> for CompactString {
+    type Error = Utf8Error;
+
+    /// Converts a slice of bytes into a [`CompactString`], failing if the bytes are not valid
+    /// UTF-8. This is equivalent to [`CompactString::from_utf8`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_str::CompactString;
+    /// let compact = CompactString::try_from(&b"hello"[..]).unwrap();
+    /// assert_eq!(compact, "hello");
+    ///
+    /// assert!(CompactString::try_from(&b"\xF0\x9F"[..]).is_err());
+    /// ```
+    #[inline]
+    fn try_from(value: &'a [u8]) -> Result<Self, Self::Error> {
+        CompactString::from_utf8(value)
+    }
+}
+
+impl TryFrom<Vec<u8>> for CompactString {
+    type Error = alloc::string::FromUtf8Error;
+
+    /// Converts a [`Vec<u8>`] into a [`CompactString`], failing if the bytes are not valid UTF-8.
+    ///
+    /// Like `From<String>`, short strings get inlined and longer strings re-use the [`Vec`]'s
+    /// buffer. On failure the returned error holds onto the original [`Vec`], which can be
+    /// recovered with [`FromUtf8Error::into_bytes`](alloc::string::FromUtf8Error::into_bytes).
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_str::CompactString;
+    /// let compact = CompactString::try_from(b"hello".to_vec()).unwrap();
+    /// assert_eq!(compact, "hello");
+    ///
+    /// let err = CompactString::try_from(b"\xF0\x9F".to_vec()).unwrap_err();
+    /// assert_eq!(err.utf8_error().valid_up_to(), 0);
+    /// assert_eq!(err.into_bytes(), b"\xF0\x9F");
+    /// ```
+    #[inline]
+    fn try_from(value: Vec<u8>) -> Result<Self, Self::Error> {
+        String::from_utf8(value).map(CompactString::from)
+    }
+}
+
+impl<'a> TryFrom<Cow<'a, [u8]>> for CompactString {
+    type Error = Utf8Error;
+
+    /// Converts a [`Cow<[u8]>`](Cow) into a [`CompactString`], failing if the bytes are not valid
+    /// UTF-8. If the [`Cow`] is owned, its buffer is re-used the same way as with
+    /// `TryFrom<Vec<u8>>`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use std::borrow::Cow;
+    /// # use compact_str::CompactString;
+    /// let compact = CompactString::try_from(Cow::Borrowed(&b"hello"[..])).unwrap();
+    /// assert_eq!(compact, "hello");
+    /// ```
+    #[inline]
+    fn try_from(value: Cow<'a, [u8]>) -> Result<Self, Self::Error> {
+        match value {
+            Cow::Borrowed(bytes) => CompactString::try_from(bytes),
+            Cow::Owned(bytes) => CompactString::try_from(bytes).map_err(|err| err.utf8_error()),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a> TryFrom<&'a std::ffi::CStr> for CompactString {
+    type Error = Utf8Error;
+
+    /// Converts a [`CStr`](std::ffi::CStr) into a [`CompactString`], failing if the contents are
+    /// not valid UTF-8. Short strings get inlined.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_str::CompactString;
+    /// let compact = CompactString::try_from(c"hello").unwrap();
+    ///
+    /// assert_eq!(compact, "hello");
+    /// assert!(!compact.is_heap_allocated());
+    /// ```
+    #[inline]
+    fn try_from(value: &'a std::ffi::CStr) -> Result<Self, Self::Error> {
+        value.to_str().map(CompactString::new)
+    }
+}
+
+impl<'a> TryFrom<&'a [NonZeroU8]> for CompactString {
+    type Error = Utf8Error;
+
+    #[inline]
+    fn try_from(value: &'a [NonZeroU8]) -> Result<Self, Self::Error> {
+        // SAFETY: `NonZeroU8` is guaranteed to have the same layout as `u8`
+        let bytes = unsafe { slice::from_raw_parts(value.as_ptr().cast::<u8>(), value.len()) };
+        CompactString::from_utf8(bytes)
+    }
+}
+
+impl TryFrom<Vec<NonZeroU8>> for CompactString {
+    type Error = Utf8Error;
+
+    /// Converts a vector of NUL-free bytes into a [`CompactString`], failing if the bytes are not
+    /// valid UTF-8.
+    ///
+    /// Like `From<String>`, short strings get inlined and longer strings re-use the underlying
+    /// buffer of the [`Vec`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_str::CompactString;
+    /// use std::num::NonZeroU8;
+    ///
+    /// let bytes: Vec<NonZeroU8> = b"hello".iter().map(|b| NonZeroU8::new(*b).unwrap()).collect();
+    /// let compact = CompactString::try_from(bytes).unwrap();
+    ///
+    /// assert_eq!(compact, "hello");
+    /// ```
+    fn try_from(value: Vec<NonZeroU8>) -> Result<Self, Self::Error> {
+        let mut value = mem::ManuallyDrop::new(value);
+        // SAFETY: `NonZeroU8` is guaranteed to have the same layout as `u8`, and we took
+        // ownership of the allocation by wrapping the original `Vec` in `ManuallyDrop`
+        let bytes = unsafe {
+            Vec::from_raw_parts(
+                value.as_mut_ptr().cast::<u8>(),
+                value.len(),
+                value.capacity(),
+            )
+        };
+        let s = String::from_utf8(bytes).map_err(|err| err.utf8_error())?;
+        Ok(CompactString::from(s))
+    }
+}
+
 impl From<CompactString> for String {
     #[inline]
     fn from(s: CompactString) -> Self {
@@ -2258,11 +4640,7 @@ impl From<CompactString> for Box<dyn std::error::Error> {
 
 impl From<CompactString> for Box<str> {
     fn from(value: CompactString) -> Self {
-        if value.is_heap_allocated() {
-            value.into_string().into_boxed_str()
-        } else {
-            Box::from(value.as_str())
-        }
+        value.0.into_boxed_str()
     }
 }
 
@@ -2273,6 +4651,52 @@ impl From<CompactString> for std::ffi::OsString {
     }
 }
 
+#[cfg(feature = "std")]
+impl TryFrom<std::ffi::OsString> for CompactString {
+    type Error = std::ffi::OsString;
+
+    /// Converts an [`OsString`](std::ffi::OsString) into a [`CompactString`], returning the
+    /// original [`OsString`](std::ffi::OsString) if it is not valid Unicode.
+    ///
+    /// Note: On all platforms, including Windows, the standard library stores an `OsString` as
+    /// (a superset of) UTF-8, so this only validates the contents. Like `From<String>`, short
+    /// strings get inlined and longer strings re-use the existing buffer without copying.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_str::CompactString;
+    /// use std::ffi::OsString;
+    ///
+    /// let compact = CompactString::try_from(OsString::from("hello")).unwrap();
+    /// assert_eq!(compact, "hello");
+    /// ```
+    #[inline]
+    fn try_from(value: std::ffi::OsString) -> Result<Self, Self::Error> {
+        value.into_string().map(CompactString::from)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a> TryFrom<&'a OsStr> for CompactString {
+    type Error = &'a OsStr;
+
+    /// Converts an [`OsStr`] into a [`CompactString`], returning the original [`OsStr`] if it is
+    /// not valid Unicode.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_str::CompactString;
+    /// use std::ffi::OsStr;
+    ///
+    /// let compact = CompactString::try_from(OsStr::new("hello")).unwrap();
+    /// assert_eq!(compact, "hello");
+    /// ```
+    #[inline]
+    fn try_from(value: &'a OsStr) -> Result<Self, Self::Error> {
+        value.to_str().map(CompactString::new).ok_or(value)
+    }
+}
+
 #[cfg(feature = "std")]
 impl From<CompactString> for std::path::PathBuf {
     fn from(value: CompactString) -> Self {
@@ -2287,12 +4711,74 @@ impl AsRef<std::path::Path> for CompactString {
     }
 }
 
-impl From<CompactString> for alloc::vec::Vec<u8> {
+impl From<CompactString> for Vec<u8> {
     fn from(value: CompactString) -> Self {
-        if value.is_heap_allocated() {
-            value.into_string().into_bytes()
-        } else {
-            value.as_bytes().to_vec()
+        value.into_bytes_vec()
+    }
+}
+
+impl TryFrom<&CompactString> for char {
+    type Error = core::char::ParseCharError;
+
+    /// Converts a [`CompactString`] holding exactly one [`char`] into that [`char`], like
+    /// [`str::parse::<char>()`](str::parse).
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_str::CompactString;
+    /// let delimiter = CompactString::new(";");
+    /// assert_eq!(char::try_from(&delimiter), Ok(';'));
+    ///
+    /// let err = char::try_from(&CompactString::new(";;")).unwrap_err();
+    /// assert_eq!(err.to_string(), "too many characters in string");
+    ///
+    /// let err = char::try_from(&CompactString::new("")).unwrap_err();
+    /// assert_eq!(err.to_string(), "cannot parse char from empty string");
+    /// ```
+    #[inline]
+    fn try_from(value: &CompactString) -> Result<Self, Self::Error> {
+        value.as_str().parse()
+    }
+}
+
+impl TryFrom<CompactString> for char {
+    type Error = core::char::ParseCharError;
+
+    /// Converts a [`CompactString`] holding exactly one [`char`] into that [`char`].
+    ///
+    /// See `TryFrom<&CompactString> for char` for details.
+    #[inline]
+    fn try_from(value: CompactString) -> Result<Self, Self::Error> {
+        char::try_from(&value)
+    }
+}
+
+impl<const N: usize> TryFrom<CompactString> for [u8; N] {
+    type Error = IntoArrayError;
+
+    /// Copies the bytes of a [`CompactString`] that is exactly `N` bytes long into an array.
+    ///
+    /// On failure the returned error holds onto the original [`CompactString`], which can be
+    /// recovered with [`IntoArrayError::into_compact_string`].
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_str::CompactString;
+    /// let code: [u8; 3] = CompactString::new("USD").try_into().unwrap();
+    /// assert_eq!(&code, b"USD");
+    ///
+    /// let err = <[u8; 3]>::try_from(CompactString::new("EURO")).unwrap_err();
+    /// assert_eq!(err.to_string(), "string is 4 bytes long, but exactly 3 bytes were expected");
+    /// assert_eq!(err.into_compact_string(), "EURO");
+    /// ```
+    #[inline]
+    fn try_from(value: CompactString) -> Result<Self, Self::Error> {
+        match value.as_bytes().try_into() {
+            Ok(array) => Ok(array),
+            Err(_) => Err(IntoArrayError {
+                string: value,
+                expected: N,
+            }),
         }
     }
 }
@@ -2306,12 +4792,28 @@ impl FromStr for CompactString {
 
 impl fmt::Debug for CompactString {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        fmt::Debug::fmt(self.as_str(), f)
+        use fmt::Write;
+
+        // Fast path: printable ASCII, other than quotes and backslashes, never needs escaping,
+        // which is the common case for short strings, so we can skip escaping char by char
+        let needs_escaping = self
+            .as_bytes()
+            .iter()
+            .any(|b| !matches!(b, b' '..=b'~') || *b == b'"' || *b == b'\\');
+        if needs_escaping {
+            return fmt::Debug::fmt(self.as_str(), f);
+        }
+
+        f.write_char('"')?;
+        f.write_str(self.as_str())?;
+        f.write_char('"')
     }
 }
 
 impl fmt::Display for CompactString {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Note: `str`'s implementation goes through `Formatter::pad`, which handles width, fill,
+        // alignment, and precision without allocating
         fmt::Display::fmt(self.as_str(), f)
     }
 }
@@ -2411,7 +4913,7 @@ impl Extend<Box<str>> for CompactString {
 
 impl<'a> Extend<Cow<'a, str>> for CompactString {
     fn extend<T: IntoIterator<Item = Cow<'a, str>>>(&mut self, iter: T) {
-        iter.into_iter().for_each(move |s| self.push_str(&s));
+        self.0.extend(iter)
     }
 }
 
@@ -2439,7 +4941,14 @@ impl Extend<CompactString> for CompactString {
 
 impl Extend<CompactString> for Cow<'_, str> {
     fn extend<T: IntoIterator<Item = CompactString>>(&mut self, iter: T) {
-        self.to_mut().extend(iter);
+        // Only convert into an owned `String` once there's something to append, so extending a
+        // borrowed `Cow` with nothing, or with empty strings, doesn't copy it.
+        let mut iter = iter.into_iter().filter(|s| !s.is_empty());
+        if let Some(first) = iter.next() {
+            let s = self.to_mut();
+            s.push_str(&first);
+            s.extend(iter);
+        }
     }
 }
 
@@ -2449,7 +4958,7 @@ impl fmt::Write for CompactString {
         Ok(())
     }
 
-    fn write_fmt(mut self: &mut Self, args: fmt::Arguments<'_>) -> fmt::Result {
+    fn write_fmt(&mut self, args: fmt::Arguments<'_>) -> fmt::Result {
         match args.as_str() {
             Some(s) => {
                 if self.is_empty() && !self.is_heap_allocated() {
@@ -2463,8 +4972,78 @@ impl fmt::Write for CompactString {
                 }
                 Ok(())
             }
-            None => fmt::write(&mut self, args),
+            None => fmt::write(&mut FmtWriter::new(self), args),
+        }
+    }
+}
+
+/// A [`fmt::Write`]r used by [`CompactString`]'s `write_fmt`.
+///
+/// Formatting usually makes many tiny writes, e.g. a few bytes of padding at a time. Instead of
+/// going through [`CompactString::push_str`], which has to figure out where our buffer lives and
+/// how much capacity it has on every call, we resolve the buffer once and keep a cursor into it.
+/// The length of the string is only committed when we need to grow, and when we're dropped.
+struct FmtWriter<'a> {
+    string: &'a mut CompactString,
+    buf: *mut u8,
+    cap: usize,
+    len: usize,
+}
+
+impl<'a> FmtWriter<'a> {
+    #[inline]
+    fn new(string: &'a mut CompactString) -> Self {
+        let len = string.len();
+        // SAFETY: We only ever write whole `&str`s into the buffer
+        let buf = unsafe { string.0.as_mut_buf() };
+        let (buf, cap) = (buf.as_mut_ptr(), buf.len());
+
+        FmtWriter {
+            string,
+            buf,
+            cap,
+            len,
+        }
+    }
+
+    #[inline]
+    fn commit(&mut self) {
+        // SAFETY: Everything up to `self.len` was written as whole `&str`s, so it's valid UTF-8
+        unsafe { self.string.set_len(self.len) };
+    }
+
+    #[cold]
+    fn grow(&mut self, additional: usize) {
+        self.commit();
+        self.string.reserve(additional);
+
+        // SAFETY: We only ever write whole `&str`s into the buffer
+        let buf = unsafe { self.string.0.as_mut_buf() };
+        self.buf = buf.as_mut_ptr();
+        self.cap = buf.len();
+    }
+}
+
+impl fmt::Write for FmtWriter<'_> {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if s.len() > self.cap - self.len {
+            self.grow(s.len());
         }
+
+        // SAFETY: We just made sure there are at least `s.len()` bytes of capacity past `self.len`,
+        // and `s` can't overlap with our buffer since we hold a mutable borrow of it
+        unsafe { ptr::copy_nonoverlapping(s.as_ptr(), self.buf.add(self.len), s.len()) };
+        self.len += s.len();
+
+        Ok(())
+    }
+}
+
+impl Drop for FmtWriter<'_> {
+    #[inline]
+    fn drop(&mut self) {
+        self.commit();
     }
 }
 
@@ -2486,28 +5065,53 @@ impl AddAssign<&str> for CompactString {
 ///
 /// This type is the error type for the [`from_utf16`] method on [`CompactString`].
 ///
-/// [`from_utf16`]: CompactString::from_utf16
+/// [`from_utf16`]: CompactString::from_utf16
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// # use compact_str::CompactString;
+/// // 𝄞mu<invalid>ic
+/// let v = &[0xD834, 0xDD1E, 0x006d, 0x0075,
+///           0xD800, 0x0069, 0x0063];
+///
+/// assert!(CompactString::from_utf16(v).is_err());
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct Utf16Error(());
+
+impl fmt::Display for Utf16Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt("invalid utf-16: lone surrogate found", f)
+    }
+}
+
+/// A possible error value when converting a [`CompactString`] from UTF-32 data.
+///
+/// This type is the error type for [`CompactString::from_utf32`],
+/// [`CompactString::from_utf32le`], and [`CompactString::from_utf32be`].
+///
 /// # Examples
 ///
-/// Basic usage:
-///
 /// ```
 /// # use compact_str::CompactString;
-/// // 𝄞mu<invalid>ic
-/// let v = &[0xD834, 0xDD1E, 0x006d, 0x0075,
-///           0xD800, 0x0069, 0x0063];
-///
-/// assert!(CompactString::from_utf16(v).is_err());
+/// // a surrogate half is not a valid `char`
+/// assert!(CompactString::from_utf32([0x0061, 0xDC00]).is_err());
 /// ```
 #[derive(Copy, Clone, Debug)]
-pub struct Utf16Error(());
+pub struct Utf32Error(());
 
-impl fmt::Display for Utf16Error {
+impl fmt::Display for Utf32Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        fmt::Display::fmt("invalid utf-16: lone surrogate found", f)
+        fmt::Display::fmt("invalid utf-32: not a unicode scalar value", f)
     }
 }
 
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl std::error::Error for Utf32Error {}
+
 /// An iterator over the exacted data by [`CompactString::drain()`].
 #[must_use = "iterators are lazy and do nothing unless consumed"]
 pub struct Drain<'a> {
@@ -2594,9 +5198,437 @@ impl DoubleEndedIterator for Drain<'_> {
 
 impl FusedIterator for Drain<'_> {}
 
+/// An iterator over the lines of a string as [`CompactString`]s, created by
+/// [`CompactString::lines_compact()`].
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct LinesCompact<'a>(core::str::Lines<'a>);
+
+impl Iterator for LinesCompact<'_> {
+    type Item = CompactString;
+
+    #[inline]
+    fn next(&mut self) -> Option<CompactString> {
+        self.0.next().map(CompactString::new)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for LinesCompact<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<CompactString> {
+        self.0.next_back().map(CompactString::new)
+    }
+}
+
+impl FusedIterator for LinesCompact<'_> {}
+
+/// An iterator over the substrings of a [`CompactString`] separated by a byte, created by
+/// [`CompactString::split_byte()`].
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct SplitByte<'a> {
+    remainder: Option<&'a str>,
+    byte: u8,
+}
+
+impl<'a> Iterator for SplitByte<'a> {
+    type Item = &'a str;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a str> {
+        let rest = self.remainder?;
+        match find_byte(self.byte, rest.as_bytes()) {
+            // Note: `byte` is ASCII, so both sides of it are char boundaries
+            Some(idx) => {
+                self.remainder = Some(&rest[idx + 1..]);
+                Some(&rest[..idx])
+            }
+            None => self.remainder.take(),
+        }
+    }
+}
+
+impl<'a> DoubleEndedIterator for SplitByte<'a> {
+    #[inline]
+    fn next_back(&mut self) -> Option<&'a str> {
+        let rest = self.remainder?;
+        match rfind_byte(self.byte, rest.as_bytes()) {
+            Some(idx) => {
+                self.remainder = Some(&rest[..idx]);
+                Some(&rest[idx + 1..])
+            }
+            None => self.remainder.take(),
+        }
+    }
+}
+
+impl FusedIterator for SplitByte<'_> {}
+
+/// An iterator over the byte indices of the occurrences of a substring in a [`CompactString`],
+/// created by [`CompactString::find_iter()`].
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct FindIter<'a> {
+    haystack: &'a str,
+    needle: &'a str,
+    /// Where to start searching from, or `None` once we're done.
+    position: Option<usize>,
+}
+
+impl Iterator for FindIter<'_> {
+    type Item = usize;
+
+    #[inline]
+    fn next(&mut self) -> Option<usize> {
+        let position = self.position?;
+
+        // Note: like `str::match_indices`, an empty needle matches at every char boundary
+        if self.needle.is_empty() {
+            self.position = self.haystack[position..]
+                .chars()
+                .next()
+                .map(|c| position + c.len_utf8());
+            return Some(position);
+        }
+
+        match find_str(self.needle, &self.haystack[position..]) {
+            Some(idx) => {
+                let start = position + idx;
+                self.position = Some(start + self.needle.len());
+                Some(start)
+            }
+            None => {
+                self.position = None;
+                None
+            }
+        }
+    }
+}
+
+impl FusedIterator for FindIter<'_> {}
+
+#[inline]
+fn find_byte(needle: u8, haystack: &[u8]) -> Option<usize> {
+    #[cfg(feature = "memchr")]
+    {
+        memchr::memchr(needle, haystack)
+    }
+    #[cfg(not(feature = "memchr"))]
+    {
+        haystack.iter().position(|b| *b == needle)
+    }
+}
+
+#[inline]
+fn rfind_byte(needle: u8, haystack: &[u8]) -> Option<usize> {
+    #[cfg(feature = "memchr")]
+    {
+        memchr::memrchr(needle, haystack)
+    }
+    #[cfg(not(feature = "memchr"))]
+    {
+        haystack.iter().rposition(|b| *b == needle)
+    }
+}
+
+#[inline]
+fn find_str(needle: &str, haystack: &str) -> Option<usize> {
+    #[cfg(feature = "memchr")]
+    {
+        match *needle.as_bytes() {
+            [byte] => memchr::memchr(byte, haystack.as_bytes()),
+            _ => memchr::memmem::find(haystack.as_bytes(), needle.as_bytes()),
+        }
+    }
+    #[cfg(not(feature = "memchr"))]
+    {
+        haystack.find(needle)
+    }
+}
+
+/// An owning iterator over the [`char`]s of a [`CompactString`], created by
+/// [`CompactString::into_chars()`].
+#[derive(Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct IntoChars {
+    string: CompactString,
+    front: usize,
+    back: usize,
+}
+
+impl IntoChars {
+    /// The remaining, unconsumed characters of the string.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        // SAFETY: `front` and `back` only ever move by whole chars, so they lie on char boundaries
+        unsafe { self.string.as_str().get_unchecked(self.front..self.back) }
+    }
+}
+
+impl fmt::Debug for IntoChars {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("IntoChars").field(&self.as_str()).finish()
+    }
+}
+
+impl Iterator for IntoChars {
+    type Item = char;
+
+    #[inline]
+    fn next(&mut self) -> Option<char> {
+        let ch = self.as_str().chars().next()?;
+        self.front += ch.len_utf8();
+        Some(ch)
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.as_str().chars().count()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.as_str().chars().size_hint()
+    }
+
+    #[inline]
+    fn last(mut self) -> Option<char> {
+        self.next_back()
+    }
+}
+
+impl DoubleEndedIterator for IntoChars {
+    #[inline]
+    fn next_back(&mut self) -> Option<char> {
+        let ch = self.as_str().chars().next_back()?;
+        self.back -= ch.len_utf8();
+        Some(ch)
+    }
+}
+
+impl FusedIterator for IntoChars {}
+
+/// An owning iterator over the [`char`]s of a [`CompactString`] and their byte positions, created
+/// by [`CompactString::into_char_indices()`].
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct IntoCharIndices {
+    chars: IntoChars,
+}
+
+impl IntoCharIndices {
+    /// The remaining, unconsumed characters of the string.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        self.chars.as_str()
+    }
+
+    /// The byte position of the next character, or the length of the string if the iterator has
+    /// been exhausted from the front.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.chars.front
+    }
+}
+
+impl Iterator for IntoCharIndices {
+    type Item = (usize, char);
+
+    #[inline]
+    fn next(&mut self) -> Option<(usize, char)> {
+        let idx = self.chars.front;
+        self.chars.next().map(|ch| (idx, ch))
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.chars.count()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.chars.size_hint()
+    }
+
+    #[inline]
+    fn last(mut self) -> Option<(usize, char)> {
+        self.next_back()
+    }
+}
+
+impl DoubleEndedIterator for IntoCharIndices {
+    #[inline]
+    fn next_back(&mut self) -> Option<(usize, char)> {
+        let ch = self.chars.next_back()?;
+        Some((self.chars.back, ch))
+    }
+}
+
+impl FusedIterator for IntoCharIndices {}
+
+/// An owning iterator over the bytes of a [`CompactString`], created by
+/// [`CompactString::into_bytes_iter()`].
+#[derive(Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct IntoBytesIter {
+    string: CompactString,
+    front: usize,
+    back: usize,
+}
+
+impl IntoBytesIter {
+    /// The remaining, unconsumed bytes of the string.
+    #[inline]
+    pub fn as_slice(&self) -> &[u8] {
+        &self.string.as_bytes()[self.front..self.back]
+    }
+}
+
+impl fmt::Debug for IntoBytesIter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("IntoBytesIter")
+            .field(&self.as_slice())
+            .finish()
+    }
+}
+
+impl Iterator for IntoBytesIter {
+    type Item = u8;
+
+    #[inline]
+    fn next(&mut self) -> Option<u8> {
+        let byte = *self.as_slice().first()?;
+        self.front += 1;
+        Some(byte)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.len()
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<u8> {
+        self.front = self.front.saturating_add(n).min(self.back);
+        self.next()
+    }
+
+    #[inline]
+    fn last(mut self) -> Option<u8> {
+        self.next_back()
+    }
+}
+
+impl DoubleEndedIterator for IntoBytesIter {
+    #[inline]
+    fn next_back(&mut self) -> Option<u8> {
+        let byte = *self.as_slice().last()?;
+        self.back -= 1;
+        Some(byte)
+    }
+}
+
+impl ExactSizeIterator for IntoBytesIter {
+    #[inline]
+    fn len(&self) -> usize {
+        self.back - self.front
+    }
+}
+
+impl FusedIterator for IntoBytesIter {}
+
 /// A possible error value if allocating or resizing a [`CompactString`] failed.
 #[derive(Debug, Clone, Copy, PartialEq)]
-pub struct ReserveError(());
+pub struct ReserveError {
+    /// The capacity we tried to allocate, or `None` if computing it overflowed.
+    requested: Option<usize>,
+    /// The length of the string when the allocation failed.
+    len: usize,
+}
+
+impl ReserveError {
+    #[inline]
+    pub(crate) const fn capacity_overflow(len: usize) -> Self {
+        ReserveError {
+            requested: None,
+            len,
+        }
+    }
+
+    #[inline]
+    pub(crate) const fn alloc_failed(capacity: usize) -> Self {
+        ReserveError {
+            requested: Some(capacity),
+            len: 0,
+        }
+    }
+
+    #[inline]
+    pub(crate) const fn with_len(self, len: usize) -> Self {
+        ReserveError { len, ..self }
+    }
+
+    /// Returns the capacity, in bytes, that couldn't be allocated, or `None` if the requested
+    /// capacity overflowed a `usize`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_str::CompactString;
+    /// let mut s = CompactString::new("hello");
+    /// let err = s.try_reserve(usize::MAX).unwrap_err();
+    ///
+    /// assert_eq!(err.requested_capacity(), None);
+    /// assert_eq!(err.current_len(), 5);
+    /// ```
+    #[inline]
+    pub fn requested_capacity(&self) -> Option<usize> {
+        self.requested
+    }
+
+    /// Returns the length of the [`CompactString`] when the allocation failed, or `0` if the
+    /// allocation was for a new string.
+    #[inline]
+    pub fn current_len(&self) -> usize {
+        self.len
+    }
+
+    /// Converts a capacity overflow into a
+    /// [`TryReserveError`](alloc::collections::TryReserveError), so it can share an error type with
+    /// [`String`] and [`Vec`]. Returns `None` if an allocation failed instead.
+    ///
+    /// A [`TryReserveError`](alloc::collections::TryReserveError) can't be constructed directly,
+    /// so the overflow is reproduced by asking an empty [`Vec`] for more than `isize::MAX` bytes,
+    /// which fails before reaching the allocator. A failed allocation can't be reproduced without
+    /// allocating, so it isn't converted.
+    ///
+    /// # Examples
+    /// ```
+    /// # use compact_str::CompactString;
+    /// let mut s = CompactString::new("hello");
+    /// let err = s.try_reserve(usize::MAX).unwrap_err();
+    ///
+    /// let overflow = Vec::<u8>::new().try_reserve(usize::MAX).unwrap_err();
+    /// assert_eq!(err.into_try_reserve_error(), Some(overflow));
+    /// ```
+    #[inline]
+    pub fn into_try_reserve_error(self) -> Option<alloc::collections::TryReserveError> {
+        match self.requested {
+            Some(_) => None,
+            None => Vec::<u8>::new().try_reserve_exact(usize::MAX).err(),
+        }
+    }
+}
 
 impl fmt::Display for ReserveError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -2608,6 +5640,73 @@ impl fmt::Display for ReserveError {
 #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
 impl std::error::Error for ReserveError {}
 
+/// A possible error value if a range or index passed to a [`CompactString`] method was invalid,
+/// e.g. in [`CompactString::try_replace_range()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RangeError {
+    /// The range extends past the end of the string, or its start is after its end
+    OutOfBounds,
+    /// The start or end of the range does not lie on a [`char`] boundary
+    NotCharBoundary,
+}
+
+impl fmt::Display for RangeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RangeError::OutOfBounds => f.write_str("range is out of bounds"),
+            RangeError::NotCharBoundary => f.write_str("range does not lie on a char boundary"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl std::error::Error for RangeError {}
+
+/// A possible error value when converting a [`CompactString`] into a `[u8; N]` array, if the
+/// string is not exactly `N` bytes long.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntoArrayError {
+    string: CompactString,
+    expected: usize,
+}
+
+impl IntoArrayError {
+    /// Returns the length of the rejected string, in bytes.
+    #[inline]
+    pub fn actual_len(&self) -> usize {
+        self.string.len()
+    }
+
+    /// Returns the length of the array, in bytes.
+    #[inline]
+    pub fn expected_len(&self) -> usize {
+        self.expected
+    }
+
+    /// Returns the [`CompactString`] that could not be converted.
+    #[inline]
+    pub fn into_compact_string(self) -> CompactString {
+        self.string
+    }
+}
+
+impl fmt::Display for IntoArrayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "string is {} bytes long, but exactly {} bytes were expected",
+            self.string.len(),
+            self.expected
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl std::error::Error for IntoArrayError {}
+
 /// A possible error value if [`ToCompactString::try_to_compact_string()`] failed.
 #[derive(Debug, Clone, Copy, PartialEq)]
 #[non_exhaustive]
@@ -31,6 +31,152 @@ macro_rules! format_compact {
     }
 }
 
+/// Concatenates any number of string-like values into a `CompactString`.
+///
+/// Each argument can be any expression that implements `AsRef<str>`, and gets evaluated exactly
+/// once. The total length is computed up front so we only allocate once, and if the result is
+/// short enough it gets inlined. This avoids the overhead of the formatting machinery that
+/// `format_compact!` goes through.
+///
+/// # Examples
+/// ```
+/// use compact_str::{concat_compact, CompactString};
+///
+/// let name = CompactString::new("ferris");
+/// let greeting = concat_compact!("hello ", name, String::from("!"));
+///
+/// assert_eq!(greeting, "hello ferris!");
+/// assert!(!greeting.is_heap_allocated());
+/// ```
+#[macro_export]
+macro_rules! concat_compact {
+    () => {
+        $crate::CompactString::const_new("")
+    };
+    ($($s:expr),+ $(,)?) => {
+        // Note: temporaries in the scrutinee of a `match` live until the end of the `match`, which
+        // allows arguments like `String::from(...)` to be borrowed
+        match [$($crate::core::convert::AsRef::<str>::as_ref(&$s)),+] {
+            parts => $crate::CompactStringExt::concat_compact(&parts),
+        }
+    };
+}
+
+/// Includes a UTF-8 encoded file as a `&'static [CompactString]`, with one entry per line.
+///
+/// The file is located relative to the current file, like with [`include_str!`]. Lines are split
+/// on `\n`, a trailing `\r` is removed from each line, and a final newline doesn't create an
+/// empty entry, matching [`str::lines`].
+///
+/// The table is built entirely at compile time with [`CompactString::const_new`], so each entry is
+/// either inlined or refers to the included text, and nothing runs at startup. It's meant to be
+/// used as the initializer of a `static` or `const`.
+///
+/// Note: This macro requires Rust 1.83 or newer. On older compilers using it is a compile error,
+/// see [compiler dependent APIs](crate#compiler-dependent-apis).
+///
+/// # Examples
+/// ```
+/// use compact_str::{include_compact, CompactString};
+///
+/// // e.g. a file with one country per line, here we use this crate's README
+/// static LINES: &[CompactString] = include_compact!("../README.md");
+///
+/// assert_eq!(LINES[0], "<div align=\"center\">");
+/// assert_eq!(LINES.len(), include_str!("../README.md").lines().count());
+/// assert!(LINES.iter().all(|line| !line.is_heap_allocated()));
+/// ```
+///
+/// [`CompactString::const_new`]: crate::CompactString::const_new
+#[rustversion::since(1.83)]
+#[macro_export]
+macro_rules! include_compact {
+    ($path:expr $(,)?) => {{
+        const TEXT: &'static str = $crate::core::include_str!($path);
+        const TABLE: [$crate::CompactString; $crate::__private::count_lines(TEXT)] =
+            $crate::__private::lines_table(TEXT);
+        &TABLE
+    }};
+}
+
+/// Includes a UTF-8 encoded file as a `&'static [CompactString]`, which requires Rust 1.83 or
+/// newer.
+///
+/// Building the table needs `const fn`s that can't be written on older compilers, so this is
+/// always a compile error, instead of an error about a missing path.
+#[rustversion::before(1.83)]
+#[macro_export]
+macro_rules! include_compact {
+    ($($tt:tt)*) => {
+        $crate::core::compile_error!("`include_compact!` requires Rust 1.83 or newer")
+    };
+}
+
+/// Helpers that are referenced in macros, and aren't part of our public API.
+#[doc(hidden)]
+pub mod __private {
+    #[rustversion::since(1.83)]
+    use crate::CompactString;
+
+    /// Returns the number of lines in `text`, as split by [`str::lines`].
+    pub const fn count_lines(text: &str) -> usize {
+        let bytes = text.as_bytes();
+        let mut count = 0;
+        let mut i = 0;
+        while i < bytes.len() {
+            if bytes[i] == b'\n' {
+                count += 1;
+            }
+            i += 1;
+        }
+
+        // the last line doesn't have to end with a newline
+        match bytes {
+            [.., b'\n'] | [] => count,
+            _ => count + 1,
+        }
+    }
+
+    /// Splits `text` into its first `N` lines, see [`include_compact!`].
+    #[rustversion::since(1.83)]
+    pub const fn lines_table<const N: usize>(text: &'static str) -> [CompactString; N] {
+        const EMPTY: CompactString = CompactString::const_new("");
+
+        let mut table = [EMPTY; N];
+        let mut rest = text.as_bytes();
+        let mut i = 0;
+        while i < N {
+            let mut len = 0;
+            while len < rest.len() && rest[len] != b'\n' {
+                len += 1;
+            }
+            let (mut line, tail) = rest.split_at(len);
+            rest = match tail {
+                [b'\n', tail @ ..] => tail,
+                tail => tail,
+            };
+            if let [head @ .., b'\r'] = line {
+                line = head;
+            }
+
+            // Note: this can't fail, we only split the valid UTF-8 of `text` on ASCII characters
+            let line = match core::str::from_utf8(line) {
+                Ok(line) => line,
+                Err(_) => panic!("lines split from a str are valid UTF-8"),
+            };
+            // Note: we can't assign to `table[i]` since that would run the destructor of the
+            // previous entry at compile time, so swap it out and forget it instead
+            core::mem::forget(core::mem::replace(
+                &mut table[i],
+                CompactString::const_new(line),
+            ));
+            i += 1;
+        }
+
+        table
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -41,4 +187,76 @@ mod tests {
         assert!(!format_compact!("2").is_heap_allocated());
         assert!(!format_compact!("{}", 2).is_heap_allocated());
     }
+
+    #[test]
+    fn test_concat_compact() {
+        use alloc::string::String;
+
+        use crate::CompactString;
+
+        assert_eq!(concat_compact!(), "");
+        assert_eq!(concat_compact!("a"), "a");
+        assert_eq!(concat_compact!("a", "b",), "ab");
+
+        let compact = CompactString::new("c");
+        let c = concat_compact!("a", String::from("b"), compact, &compact);
+        assert_eq!(c, "abcc");
+        assert!(!c.is_heap_allocated());
+
+        let long = "this is a long string that will be on the heap";
+        let c = concat_compact!(long, "!", long);
+        assert_eq!(c.len(), long.len() * 2 + 1);
+        assert_eq!(c.capacity(), c.len());
+
+        // each argument is only evaluated once
+        let mut count = 0;
+        let c = concat_compact!(
+            {
+                count += 1;
+                "x"
+            },
+            "y"
+        );
+        assert_eq!(c, "xy");
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn test_count_lines() {
+        use super::__private::count_lines;
+
+        for text in [
+            "",
+            "\n",
+            "a",
+            "a\n",
+            "a\nb",
+            "a\r\nb\r\n",
+            "\n\n",
+            "a\n\nb\n",
+        ] {
+            assert_eq!(count_lines(text), text.lines().count(), "{:?}", text);
+        }
+    }
+
+    #[rustversion::since(1.83)]
+    #[test]
+    fn test_include_compact() {
+        use alloc::vec::Vec;
+
+        use super::__private::lines_table;
+        use crate::CompactString;
+
+        const TEXT: &str = "short\r\n\nthis line is too long to be stored inline\nlast";
+        const TABLE: [CompactString; 4] = lines_table(TEXT);
+        assert_eq!(TABLE, TEXT.lines().collect::<Vec<_>>()[..]);
+        assert!(TABLE.iter().all(|s| !s.is_heap_allocated()));
+        assert!(TABLE[2].as_static_str().is_some());
+
+        static README: &[CompactString] = include_compact!("../README.md");
+        assert!(README
+            .iter()
+            .map(CompactString::as_str)
+            .eq(include_str!("../README.md").lines()));
+    }
 }
@@ -6,7 +6,7 @@ use super::{Repr, MAX_SIZE};
 use crate::{ReserveError, UnwrapWithMsg};
 
 /// The minimum size we'll allocate on the heap is one usize larger than our max inline size
-const MIN_HEAP_SIZE: usize = MAX_SIZE + mem::size_of::<usize>();
+pub(crate) const MIN_HEAP_SIZE: usize = MAX_SIZE + mem::size_of::<usize>();
 
 const UNKNOWN: usize = 0;
 pub(crate) type StrBuffer = [u8; UNKNOWN];
@@ -15,6 +15,9 @@ pub(crate) type StrBuffer = [u8; UNKNOWN];
 ///
 /// Note: this is different than [`std::string::String`], which grows at a rate of 2x. It's debated
 /// which is better, for now we'll stick with a rate of 1.5x
+///
+/// Note: This formula, along with [`MIN_HEAP_SIZE`], is documented on [`crate::GrowthPolicy`], keep
+/// the two in sync.
 #[inline(always)]
 pub(crate) fn amortized_growth(cur_len: usize, additional: usize) -> usize {
     let required = cur_len.saturating_add(additional);
@@ -22,6 +25,29 @@ pub(crate) fn amortized_growth(cur_len: usize, additional: usize) -> usize {
     amortized.max(required)
 }
 
+/// Rounds `capacity` up to the size class that allocators like jemalloc and mimalloc would serve
+/// an allocation of that size from, so the bytes the allocator rounds up to become usable capacity
+/// instead of being wasted.
+///
+/// Up to 128 bytes the classes are multiples of 16, and above that there are four classes for
+/// every power of two, e.g. 160, 192, 224, 256, 320, 384, ...
+#[inline]
+pub(crate) const fn size_class(capacity: usize) -> usize {
+    let step = if capacity <= 128 {
+        16
+    } else {
+        // a quarter of the largest power of two that's smaller than `capacity`
+        let log2 = usize::BITS - 1 - (capacity - 1).leading_zeros();
+        1 << (log2 - 2)
+    };
+
+    match capacity.checked_add(step - 1) {
+        Some(rounded) => rounded & !(step - 1),
+        // we'd fail to allocate this much anyways
+        None => capacity,
+    }
+}
+
 #[repr(C)]
 pub(crate) struct HeapBuffer {
     pub(crate) ptr: ptr::NonNull<u8>,
@@ -65,7 +91,7 @@ impl HeapBuffer {
     pub(crate) fn with_additional(text: &str, additional: usize) -> Result<Self, ReserveError> {
         let len = text.len();
         let new_capacity = amortized_growth(len, additional);
-        let (cap, ptr) = allocate_ptr(new_capacity)?;
+        let (cap, ptr) = allocate_ptr(new_capacity).map_err(|err| err.with_len(len))?;
 
         // copy our string into the buffer we just allocated
         //
@@ -271,7 +297,7 @@ pub(crate) fn allocate_ptr(capacity: usize) -> Result<(Capacity, ptr::NonNull<u8
     debug_assert!(capacity > 0);
 
     #[cold]
-    fn allocate_with_capacity_on_heap(capacity: usize) -> Result<ptr::NonNull<u8>, ReserveError> {
+    fn allocate_with_capacity_on_heap(capacity: usize) -> Option<ptr::NonNull<u8>> {
         // write our capacity onto the heap
         // SAFETY: we know that the capacity is not zero
         let ptr = unsafe { heap_capacity::alloc(capacity)? };
@@ -286,7 +312,7 @@ pub(crate) fn allocate_ptr(capacity: usize) -> Result<(Capacity, ptr::NonNull<u8
         };
         let raw_ptr = ptr.as_ptr().wrapping_add(core::mem::size_of::<usize>());
         // SAFETY: We know `raw_ptr` is non-null because we just created it
-        Ok(unsafe { ptr::NonNull::new_unchecked(raw_ptr) })
+        Some(unsafe { ptr::NonNull::new_unchecked(raw_ptr) })
     }
 
     let ptr = if cap.is_heap() {
@@ -295,7 +321,8 @@ pub(crate) fn allocate_ptr(capacity: usize) -> Result<(Capacity, ptr::NonNull<u8
         unsafe { inline_capacity::alloc(capacity) }
     };
 
-    Ok((cap, ptr?))
+    let ptr = ptr.ok_or(ReserveError::alloc_failed(capacity))?;
+    Ok((cap, ptr))
 }
 
 /// Deallocates a buffer on the heap, handling when the capacity is also stored on the heap
@@ -329,7 +356,7 @@ pub(crate) fn deallocate_ptr(ptr: ptr::NonNull<u8>, cap: Capacity) {
 
 /// SAFETY: `layout` must not be zero sized
 #[inline]
-pub(crate) unsafe fn do_alloc(layout: Layout) -> Result<ptr::NonNull<u8>, ReserveError> {
+pub(crate) unsafe fn do_alloc(layout: Layout) -> Option<ptr::NonNull<u8>> {
     debug_assert!(layout.size() > 0);
 
     // SAFETY: `alloc(...)` has undefined behavior if the layout is zero-sized. We specify that
@@ -339,17 +366,16 @@ pub(crate) unsafe fn do_alloc(layout: Layout) -> Result<ptr::NonNull<u8>, Reserv
 
     // Check to make sure our pointer is non-null.
     // Implementations are encouraged to return null on memory exhaustion rather than aborting.
-    ptr::NonNull::new(raw_ptr).ok_or(ReserveError(()))
+    ptr::NonNull::new(raw_ptr)
 }
 
 mod heap_capacity {
     use core::{alloc, ptr};
 
     use super::{do_alloc, StrBuffer};
-    use crate::ReserveError;
 
     /// SAFETY: `capacity` must not be zero
-    pub(crate) unsafe fn alloc(capacity: usize) -> Result<ptr::NonNull<u8>, ReserveError> {
+    pub(crate) unsafe fn alloc(capacity: usize) -> Option<ptr::NonNull<u8>> {
         do_alloc(layout(capacity))
     }
 
@@ -384,11 +410,10 @@ mod inline_capacity {
     use core::{alloc, ptr};
 
     use super::{do_alloc, StrBuffer};
-    use crate::ReserveError;
 
     /// # SAFETY:
     /// * `capacity` must be > 0
-    pub(crate) unsafe fn alloc(capacity: usize) -> Result<ptr::NonNull<u8>, ReserveError> {
+    pub(crate) unsafe fn alloc(capacity: usize) -> Option<ptr::NonNull<u8>> {
         do_alloc(layout(capacity))
     }
 
@@ -421,10 +446,23 @@ mod inline_capacity {
 mod test {
     use test_case::test_case;
 
-    use super::{HeapBuffer, MIN_HEAP_SIZE};
+    use super::{size_class, HeapBuffer, MIN_HEAP_SIZE};
 
     const EIGHTEEN_MB: usize = 18 * 1024 * 1024;
 
+    #[test_case(0, 0; "zero")]
+    #[test_case(1, 16; "one")]
+    #[test_case(32, 32; "min heap size")]
+    #[test_case(33, 48; "small")]
+    #[test_case(128, 128; "last small class")]
+    #[test_case(129, 160; "first large class")]
+    #[test_case(257, 320; "next power of two")]
+    #[test_case(EIGHTEEN_MB + 1, 20 * 1024 * 1024; "huge")]
+    #[test_case(usize::MAX, usize::MAX; "overflow")]
+    fn test_size_class(capacity: usize, expected: usize) {
+        assert_eq!(size_class(capacity), expected);
+    }
+
     #[test]
     fn test_min_capacity() {
         let h = HeapBuffer::new("short").unwrap();
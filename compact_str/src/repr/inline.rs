@@ -70,6 +70,40 @@ impl InlineBuffer {
         InlineBuffer(buffer)
     }
 
+    /// Construct a new [`InlineBuffer`] containing the UTF-8 encoding of a single `char`, usable
+    /// in `const` contexts.
+    #[inline]
+    pub(crate) const fn new_const_char(c: char) -> Self {
+        let code = c as u32;
+        let len = c.len_utf8();
+        let mut buffer = [0u8; MAX_SIZE];
+
+        // set the length
+        buffer[MAX_SIZE - 1] = len as u8 | LENGTH_MASK;
+
+        // Note: `char::encode_utf8` isn't `const`, so we encode the char by hand
+        match len {
+            1 => buffer[0] = code as u8,
+            2 => {
+                buffer[0] = 0xC0 | (code >> 6) as u8;
+                buffer[1] = 0x80 | (code & 0x3F) as u8;
+            }
+            3 => {
+                buffer[0] = 0xE0 | (code >> 12) as u8;
+                buffer[1] = 0x80 | ((code >> 6) & 0x3F) as u8;
+                buffer[2] = 0x80 | (code & 0x3F) as u8;
+            }
+            _ => {
+                buffer[0] = 0xF0 | (code >> 18) as u8;
+                buffer[1] = 0x80 | ((code >> 12) & 0x3F) as u8;
+                buffer[2] = 0x80 | ((code >> 6) & 0x3F) as u8;
+                buffer[3] = 0x80 | (code & 0x3F) as u8;
+            }
+        }
+
+        InlineBuffer(buffer)
+    }
+
     /// Returns an empty [`InlineBuffer`]
     #[inline(always)]
     pub(crate) const fn empty() -> Self {
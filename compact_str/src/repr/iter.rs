@@ -1,27 +1,59 @@
-//! Implementations of the [`FromIterator`] trait to make building [`Repr`]s more ergonomic
+//! Implementations of the [`FromIterator`] and [`Extend`] traits to make building [`Repr`]s more
+//! ergonomic
 
 use alloc::borrow::Cow;
 use alloc::boxed::Box;
 use alloc::string::String;
 
-use super::{InlineBuffer, Repr, EMPTY, MAX_SIZE};
-use crate::{CompactString, UnwrapWithMsg};
+use super::{InlineBuffer, Repr, MAX_SIZE};
+use crate::{CompactString, ReserveError, UnwrapWithMsg};
 
 impl FromIterator<char> for Repr {
     #[inline]
     fn from_iter<T: IntoIterator<Item = char>>(iter: T) -> Self {
-        let iter = iter.into_iter();
+        let mut iter = iter.into_iter();
 
+        // Every char is at least one byte, so if the lower bound is larger than what we can store
+        // inline, go straight to the heap instead of starting inline and copying everything over
         let (lower_bound, _) = iter.size_hint();
-        let mut this = match Repr::with_capacity(lower_bound) {
-            Ok(this) => this,
-            Err(_) => EMPTY, // Ignore the error and hope that the lower_bound is incorrect.
-        };
+        if lower_bound > MAX_SIZE {
+            let mut string = String::new();
+            // Ignore the error and hope that the lower_bound is incorrect.
+            let _ = string.try_reserve(lower_bound);
+            string.extend(iter);
 
-        for c in iter {
-            this.push_str(c.encode_utf8(&mut [0; 4]));
+            // Note: if the iterator lied about its lower bound we'll still inline the result
+            return Repr::from_string(string, true).unwrap_with_msg();
         }
-        this
+
+        let mut curr_len = 0;
+        let mut inline_buf = InlineBuffer::new_const("");
+        while let Some(c) = iter.next() {
+            let char_len = c.len_utf8();
+
+            // this char doesn't fit into our inline buffer, so heap allocate the rest
+            if curr_len + char_len > MAX_SIZE {
+                let (min_remaining, _) = iter.size_hint();
+                let mut string = String::new();
+                let _ = string.try_reserve((curr_len + char_len).saturating_add(min_remaining));
+
+                // SAFETY: `inline_buf` has been filled with `char`s which are valid UTF-8
+                string
+                    .push_str(unsafe { core::str::from_utf8_unchecked(&inline_buf.0[..curr_len]) });
+                string.push(c);
+                string.extend(iter);
+
+                return Repr::from_string(string, true).unwrap_with_msg();
+            }
+
+            c.encode_utf8(&mut inline_buf.0[curr_len..]);
+            curr_len += char_len;
+        }
+
+        // SAFETY: Everything we just pushed onto the buffer is a `char` which is valid UTF-8
+        unsafe { inline_buf.set_len(curr_len) }
+
+        Repr::from_inline(inline_buf)
     }
 }
 
@@ -106,8 +138,58 @@ impl<'a> FromIterator<Cow<'a, str>> for Repr {
     }
 }
 
+/// Pushes every string from `iter` onto `repr`, the [`Extend`] counterpart of
+/// [`from_as_ref_str_iterator`].
+///
+/// Like when collecting, we can't trust the lower bound of the iterator on its own since the
+/// strings could all be empty. Instead, once a string doesn't fit into the spare capacity we
+/// reserve room for it plus one byte for each of the strings the iterator says are remaining, so
+/// a run of small strings doesn't reallocate on every push.
+fn extend_from_as_ref_str_iterator<S, I>(repr: &mut Repr, mut iter: I)
+where
+    S: AsRef<str>,
+    I: Iterator<Item = S>,
+{
+    while let Some(s) = iter.next() {
+        let str_slice = s.as_ref();
+        if str_slice.len() > repr.capacity() - repr.len() {
+            let (min_remaining, _) = iter.size_hint();
+            let additional = str_slice.len().saturating_add(min_remaining);
+            // Ignore the error and hope that the lower_bound is incorrect, `push_str` will
+            // reserve exactly what it needs.
+            let _: Result<(), ReserveError> = repr.reserve(additional);
+        }
+        repr.push_str(str_slice);
+    }
+}
+
+impl<'a> Extend<&'a str> for Repr {
+    fn extend<T: IntoIterator<Item = &'a str>>(&mut self, iter: T) {
+        extend_from_as_ref_str_iterator(self, iter.into_iter());
+    }
+}
+
+impl Extend<Box<str>> for Repr {
+    fn extend<T: IntoIterator<Item = Box<str>>>(&mut self, iter: T) {
+        extend_from_as_ref_str_iterator(self, iter.into_iter());
+    }
+}
+
+impl<'a> Extend<Cow<'a, str>> for Repr {
+    fn extend<T: IntoIterator<Item = Cow<'a, str>>>(&mut self, iter: T) {
+        extend_from_as_ref_str_iterator(self, iter.into_iter());
+    }
+}
+
+impl Extend<String> for Repr {
+    fn extend<T: IntoIterator<Item = String>>(&mut self, iter: T) {
+        extend_from_as_ref_str_iterator(self, iter.into_iter());
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use alloc::borrow::Cow;
     use alloc::string::String;
 
     use super::Repr;
@@ -158,6 +240,56 @@ mod tests {
         assert!(repr.is_heap_allocated());
     }
 
+    /// An iterator that reports a `size_hint` unrelated to how many items it yields
+    struct LyingIter<I> {
+        inner: I,
+        hint: usize,
+    }
+
+    impl<I: Iterator> Iterator for LyingIter<I> {
+        type Item = I::Item;
+
+        fn next(&mut self) -> Option<I::Item> {
+            self.inner.next()
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            (self.hint, None)
+        }
+    }
+
+    #[test]
+    fn large_hint_char_iter() {
+        let long = "a".repeat(1024);
+        let repr: Repr = long.chars().collect();
+
+        assert_eq!(repr.as_str(), long);
+        assert!(repr.is_heap_allocated());
+        assert!(repr.capacity() >= long.len());
+    }
+
+    #[test]
+    fn lying_char_iter() {
+        // claims to be long, but is short
+        let repr: Repr = LyingIter {
+            inner: "abc".chars(),
+            hint: 1 << 20,
+        }
+        .collect();
+        assert_eq!(repr.as_str(), "abc");
+        assert!(!repr.is_heap_allocated());
+
+        // claims to be empty, but is long
+        let long = "This is supposed to be a really long string 🦀";
+        let repr: Repr = LyingIter {
+            inner: long.chars(),
+            hint: 0,
+        }
+        .collect();
+        assert_eq!(repr.as_str(), long);
+        assert!(repr.is_heap_allocated());
+    }
+
     #[test]
     fn short_string_iter() {
         let strings = vec!["hello", "world"];
@@ -178,4 +310,53 @@ mod tests {
         assert_eq!(repr.as_str(), "1234567891011121314151617181920");
         assert!(repr.is_heap_allocated());
     }
+
+    #[test]
+    fn cow_string_iter() {
+        let strings = [Cow::Borrowed("hello "), Cow::Owned(String::from("world"))];
+        let repr: Repr = strings.iter().cloned().collect();
+        assert_eq!(repr.as_str(), "hello world");
+        assert!(!repr.is_heap_allocated());
+
+        let long: Repr = strings.iter().cloned().cycle().take(6).collect();
+        assert_eq!(long.as_str(), "hello worldhello worldhello world");
+        assert!(long.is_heap_allocated());
+    }
+
+    #[test]
+    fn extend_string_iter() {
+        let mut repr = Repr::new("abc").unwrap();
+        repr.extend([Cow::Borrowed("def"), Cow::Owned(String::from("ghi"))]);
+        assert_eq!(repr.as_str(), "abcdefghi");
+        assert!(!repr.is_heap_allocated());
+
+        // spilling onto the heap reserves for the strings the iterator says are remaining
+        let digits = "0123456789";
+        repr.extend(digits.split("").map(Cow::Borrowed));
+        repr.extend(digits.split("").map(String::from));
+        assert_eq!(repr.as_str(), "abcdefghi01234567890123456789");
+        assert!(repr.is_heap_allocated());
+
+        let mut repr = Repr::new("").unwrap();
+        repr.extend(["x"; 100].iter().copied());
+        assert_eq!(repr.as_str(), "x".repeat(100));
+        assert!(repr.capacity() >= 100);
+
+        // neither an empty nor a lying size hint affects the result
+        let mut repr = Repr::new("abc").unwrap();
+        repr.extend(LyingIter {
+            inner: ["", "", ""].into_iter(),
+            hint: 1 << 20,
+        });
+        assert_eq!(repr.as_str(), "abc");
+        assert!(!repr.is_heap_allocated());
+
+        let long = "This is supposed to be a really long string 🦀";
+        repr.extend(LyingIter {
+            inner: long.split(' '),
+            hint: 0,
+        });
+        assert_eq!(repr.as_str(), "abcThisissupposedtobeareallylongstring🦀");
+        assert!(repr.is_heap_allocated());
+    }
 }
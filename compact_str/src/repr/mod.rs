@@ -1,4 +1,3 @@
-use alloc::borrow::Cow;
 use alloc::boxed::Box;
 use core::str::Utf8Error;
 use core::{mem, ptr};
@@ -21,6 +20,7 @@ use alloc::string::String;
 
 use capacity::Capacity;
 use heap::HeapBuffer;
+pub(crate) use heap::{amortized_growth, size_class, MIN_HEAP_SIZE};
 use inline::InlineBuffer;
 use last_utf8_char::LastByte;
 use static_str::StaticStr;
@@ -76,6 +76,24 @@ impl Repr {
         }
     }
 
+    /// Create an inline [`Repr`], or `None` if `text` is longer than [`MAX_SIZE`]
+    #[inline]
+    pub(crate) fn new_inline(text: &str) -> Option<Self> {
+        if text.len() <= MAX_SIZE {
+            // SAFETY: We checked that the length of text is less than or equal to MAX_SIZE
+            let inline = unsafe { InlineBuffer::new(text) };
+            Some(Repr::from_inline(inline))
+        } else {
+            None
+        }
+    }
+
+    /// Create a [`Repr`] that is always heap allocated, regardless of the length of `text`
+    #[inline]
+    pub(crate) fn new_heap(text: &str) -> Result<Self, ReserveError> {
+        HeapBuffer::new(text).map(Repr::from_heap)
+    }
+
     #[inline]
     pub(crate) const fn const_new(text: &'static str) -> Self {
         if text.len() <= MAX_SIZE {
@@ -87,6 +105,11 @@ impl Repr {
         }
     }
 
+    #[inline]
+    pub(crate) const fn from_char(c: char) -> Self {
+        Repr::from_inline(InlineBuffer::new_const_char(c))
+    }
+
     /// Create a [`Repr`] with the provided `capacity`
     #[inline]
     pub(crate) fn with_capacity(capacity: usize) -> Result<Self, ReserveError> {
@@ -114,23 +137,13 @@ impl Repr {
     /// * The caller must guarantee that `buf` is valid UTF-8.
     #[inline]
     pub(crate) unsafe fn from_utf8_unchecked<B: AsRef<[u8]>>(buf: B) -> Result<Self, ReserveError> {
-        let bytes = buf.as_ref();
-        let bytes_len = bytes.len();
-
-        // Create a Repr with enough capacity for the entire buffer
-        let mut repr = Repr::with_capacity(bytes_len)?;
-
         // SAFETY: The caller is responsible for making sure the provided buffer is UTF-8. This
         // invariant is documented in the public API
-        let slice = repr.as_mut_buf();
-        // write the chunk into the Repr
-        slice[..bytes_len].copy_from_slice(bytes);
+        let text = core::str::from_utf8_unchecked(buf.as_ref());
 
-        // Set the length of the Repr
-        // SAFETY: We just wrote the entire `buf` into the Repr
-        repr.set_len(bytes_len);
-
-        Ok(repr)
+        // Going through `Repr::new` sizes the allocation and copies the bytes in one go, instead
+        // of computing a capacity and then bounds checking a write into the spare buffer
+        Self::new(text)
     }
 
     /// Create a [`Repr`] from a [`String`], in `O(1)` time. We'll attempt to inline the string
@@ -216,12 +229,52 @@ impl Repr {
         }
     }
 
+    /// Consumes the [`Repr`] returning a `Box<str>`
+    ///
+    /// If we're heap allocated with a length equal to our capacity, the existing buffer is handed
+    /// over as-is. If there is excess capacity the buffer is shrunk in place, otherwise we allocate
+    /// exactly once.
+    #[inline]
+    pub(crate) fn into_boxed_str(self) -> Box<str> {
+        if self.is_heap_allocated() {
+            // SAFETY: we just checked that the discriminant indicates we're a HeapBuffer
+            let heap_buffer = unsafe { self.into_heap() };
+
+            if !heap_buffer.cap.is_heap() {
+                // Wrap the HeapBuffer in a ManuallyDrop so the underlying buffer doesn't get freed
+                let this = mem::ManuallyDrop::new(heap_buffer);
+
+                // SAFETY: We checked above to make sure capacity is valid
+                let cap = unsafe { this.cap.as_usize() };
+
+                // SAFETY: See `Repr::into_string`, the same invariants apply here
+                let string = unsafe { String::from_raw_parts(this.ptr.as_ptr(), this.len, cap) };
+
+                // Note: when `len == cap` this does not reallocate
+                return string.into_boxed_str();
+            }
+
+            // The capacity is stored on the heap, in front of the string, so we can't hand the
+            // allocation over and need to copy
+            //
+            // SAFETY: We know pointer is valid for `length` bytes
+            let slice =
+                unsafe { core::slice::from_raw_parts(heap_buffer.ptr.as_ptr(), heap_buffer.len) };
+            // SAFETY: A `Repr` contains valid UTF-8
+            Box::from(unsafe { core::str::from_utf8_unchecked(slice) })
+        } else {
+            Box::from(self.as_str())
+        }
+    }
+
     /// Reserves at least `additional` bytes. If there is already enough capacity to store
     /// `additional` bytes this is a no-op
     #[inline]
     pub(crate) fn reserve(&mut self, additional: usize) -> Result<(), ReserveError> {
         let len = self.len();
-        let needed_capacity = len.checked_add(additional).ok_or(ReserveError(()))?;
+        let needed_capacity = len
+            .checked_add(additional)
+            .ok_or(ReserveError::capacity_overflow(len))?;
 
         if !self.is_static_str() && needed_capacity <= self.capacity() {
             // we already have enough space, no-op
@@ -312,21 +365,29 @@ impl Repr {
 
     #[inline]
     pub(crate) fn push_str(&mut self, s: &str) {
+        self.try_push_str(s).unwrap_with_msg()
+    }
+
+    #[inline]
+    pub(crate) fn try_push_str(&mut self, s: &str) -> Result<(), ReserveError> {
         // If `s` is empty, then there's no reason to reserve or push anything
         // at all.
         if s.is_empty() {
-            return;
+            return Ok(());
         }
 
         let len = self.len();
         let str_len = s.len();
 
         // Reserve at least enough space to fit `s`
-        self.reserve(str_len).unwrap_with_msg();
+        self.reserve(str_len)?;
 
         // SAFETY: `s` which we're appending to the buffer, is valid UTF-8
         let slice = unsafe { self.as_mut_buf() };
-        let push_buffer = &mut slice[len..len + str_len];
+        // SAFETY: We just reserved space for `str_len` more bytes, so `len + str_len` can't
+        // overflow and is within the capacity of our buffer. Skipping the bounds check keeps
+        // this path free of panics.
+        let push_buffer = unsafe { slice.get_unchecked_mut(len..len + str_len) };
 
         debug_assert_eq!(push_buffer.len(), s.as_bytes().len());
 
@@ -338,6 +399,8 @@ impl Repr {
         // SAFETY: We appended `s` which is valid UTF-8, and if our size became greater than
         // MAX_SIZE, our call to reserve would make us heap allocated
         unsafe { self.set_len(len + str_len) };
+
+        Ok(())
     }
 
     #[inline]
@@ -350,6 +413,16 @@ impl Repr {
         Some(ch)
     }
 
+    /// Returns `true` if our content is equal to `other`.
+    ///
+    /// Comparing lengths first only needs our metadata, so when the lengths differ, which is the
+    /// common case for a string that isn't equal, we never have to figure out where our content
+    /// is stored.
+    #[inline]
+    pub(crate) fn eq_str(&self, other: &str) -> bool {
+        self.len() == other.len() && self.as_slice() == other.as_bytes()
+    }
+
     /// Returns the string content, and only the string content, as a slice of bytes.
     #[inline]
     pub(crate) fn as_slice(&self) -> &[u8] {
@@ -426,6 +499,10 @@ impl Repr {
     /// Returns the overall capacity of the underlying buffer
     #[inline]
     pub(crate) fn capacity(&self) -> usize {
+        // Like `len()` this selects between the capacity of each variant with conditional moves
+        // instead of branching on the discriminant, which mispredicts when a workload mixes inline
+        // and heap allocated strings.
+
         #[cold]
         fn heap_capacity(this: &Repr) -> usize {
             // SAFETY: We just checked the discriminant to make sure we're heap allocated
@@ -433,13 +510,33 @@ impl Repr {
             heap_buffer.capacity()
         }
 
-        if let Some(s) = self.as_static_str() {
-            s.len()
-        } else if self.is_heap_allocated() {
-            heap_capacity(self)
-        } else {
-            MAX_SIZE
+        // SAFETY: A `HeapBuffer` and `Repr` have the same size, and every variant is fully
+        // initialized, so reading the last word as a `Capacity` is always sound. It's only
+        // meaningful when we're heap allocated though.
+        let cap = unsafe {
+            let heap = self as *const Self as *const HeapBuffer;
+            ptr::addr_of!((*heap).cap).read()
+        };
+        if self.is_heap_allocated() && cap.is_heap() {
+            // Only possible on 32-bit architectures
+            return heap_capacity(self);
         }
+
+        // Force the compiler to read the variables, so it won't put the reading in a branch.
+        // SAFETY: We checked above that the capacity isn't stored on the heap
+        let cap_heap = ensure_read(unsafe { cap.as_usize() });
+        let len_static = ensure_read(self.1);
+
+        let last_byte = self.last_byte();
+        let mut capacity = MAX_SIZE;
+        if last_byte == STATIC_STR_MASK {
+            capacity = len_static;
+        }
+        if last_byte == HEAP_MASK {
+            capacity = cap_heap;
+        }
+
+        capacity
     }
 
     #[inline(always)]
@@ -631,6 +728,32 @@ impl Repr {
         &*(self as *const _ as *const HeapBuffer)
     }
 
+    /// Returns the inline buffer along with the length of the string stored in it, or `None` if
+    /// we're heap allocated or a `&'static str`.
+    #[inline]
+    pub(crate) fn as_inline_buffer(&self) -> Option<(&[u8; MAX_SIZE], usize)> {
+        if self.last_byte() >= HEAP_MASK {
+            None
+        } else {
+            // SAFETY: We just checked the discriminant to make sure we're inline
+            Some(unsafe { self.as_inline_buffer_unchecked() })
+        }
+    }
+
+    /// Returns the inline buffer along with the length of the string stored in it.
+    ///
+    /// # SAFETY
+    /// * The caller must guarantee that the provided [`Repr`] is actually an [`InlineBuffer`] by
+    ///   checking the discriminant.
+    #[inline]
+    pub(crate) unsafe fn as_inline_buffer_unchecked(&self) -> (&[u8; MAX_SIZE], usize) {
+        debug_assert!(self.last_byte() < HEAP_MASK);
+
+        // SAFETY: An `InlineBuffer` and `Repr` have the same size and alignment
+        let inline = &*(self as *const _ as *const InlineBuffer);
+        (&inline.0, self.len())
+    }
+
     /// Reinterprets a [`Repr`] as an [`InlineBuffer`]
     ///
     /// # SAFETY
@@ -662,12 +785,14 @@ impl Repr {
     }
 }
 
-impl Clone for Repr {
+impl Repr {
+    /// Fallible version of [`Clone::clone`], returns an error instead of panicking if we fail to
+    /// allocate a new heap buffer.
     #[inline]
-    fn clone(&self) -> Self {
+    pub(crate) fn try_clone(&self) -> Result<Self, ReserveError> {
         #[inline(never)]
-        fn clone_heap(this: &Repr) -> Repr {
-            Repr::new(this.as_str()).unwrap_with_msg()
+        fn clone_heap(this: &Repr) -> Result<Repr, ReserveError> {
+            Repr::new(this.as_str())
         }
 
         // There are only two cases we need to care about: If the string is allocated on the heap
@@ -678,9 +803,16 @@ impl Clone for Repr {
         } else {
             // SAFETY: We just checked that `self` can be copied because it is an inline string or
             // a reference to a `&'static str`.
-            unsafe { core::ptr::read(self) }
+            Ok(unsafe { core::ptr::read(self) })
         }
     }
+}
+
+impl Clone for Repr {
+    #[inline]
+    fn clone(&self) -> Self {
+        self.try_clone().unwrap_with_msg()
+    }
 
     #[inline]
     fn clone_from(&mut self, source: &Self) {
@@ -744,30 +876,6 @@ impl<'a> Extend<&'a char> for Repr {
     }
 }
 
-impl<'a> Extend<&'a str> for Repr {
-    fn extend<T: IntoIterator<Item = &'a str>>(&mut self, iter: T) {
-        iter.into_iter().for_each(|s| self.push_str(s));
-    }
-}
-
-impl Extend<Box<str>> for Repr {
-    fn extend<T: IntoIterator<Item = Box<str>>>(&mut self, iter: T) {
-        iter.into_iter().for_each(move |s| self.push_str(&s));
-    }
-}
-
-impl<'a> Extend<Cow<'a, str>> for Repr {
-    fn extend<T: IntoIterator<Item = Cow<'a, str>>>(&mut self, iter: T) {
-        iter.into_iter().for_each(move |s| self.push_str(&s));
-    }
-}
-
-impl Extend<String> for Repr {
-    fn extend<T: IntoIterator<Item = String>>(&mut self, iter: T) {
-        iter.into_iter().for_each(move |s| self.push_str(&s));
-    }
-}
-
 /// Returns the supplied value, and ensures that the value is eagerly loaded into a register.
 #[inline(always)]
 fn ensure_read(value: usize) -> usize {
@@ -1031,11 +1139,31 @@ mod tests {
         assert_eq!(r.is_heap_allocated(), is_heap);
     }
 
+    #[test]
+    fn test_capacity_per_variant() {
+        assert_eq!(Repr::new("").unwrap().capacity(), MAX_SIZE);
+        assert_eq!(Repr::new("abc").unwrap().capacity(), MAX_SIZE);
+
+        let text = "a static str that is too long to be inlined";
+        let r = Repr::const_new(text);
+        assert_eq!(r.capacity(), text.len());
+
+        let r = Repr::with_capacity(100).unwrap();
+        assert!(r.is_heap_allocated());
+        assert_eq!(r.capacity(), 100);
+
+        let s = String::with_capacity(50);
+        let r = Repr::from_string(s, false).unwrap();
+        assert_eq!(r.capacity(), 50);
+    }
+
     #[test]
     fn test_reserve_overflow() {
         let mut r = Repr::new("abc").unwrap();
         let err = r.reserve(usize::MAX).unwrap_err();
-        assert_eq!(err, ReserveError(()));
+        assert_eq!(err, ReserveError::capacity_overflow(3));
+        assert_eq!(err.requested_capacity(), None);
+        assert_eq!(err.current_len(), 3);
     }
 
     #[test_case(""; "empty")]
@@ -0,0 +1,303 @@
+//! A tiny string table, for laying out many strings contiguously in a single buffer, e.g. a
+//! memory-mapped file, and viewing them without copying.
+//!
+//! The layout is stable: every entry is its length as a little-endian `u32`, followed by that many
+//! bytes of UTF-8. There is no padding or header, so tables can be concatenated.
+
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::Deref;
+use core::str::Utf8Error;
+
+use crate::CompactString;
+
+const LEN_PREFIX: usize = core::mem::size_of::<u32>();
+
+/// A borrowed view of a single string within a [`StrTable`].
+///
+/// Converting into a [`CompactString`] only copies the bytes once, and short strings get inlined.
+///
+/// # Examples
+/// ```
+/// use compact_str::{CompactStrRef, CompactString};
+///
+/// let mut buf = Vec::new();
+/// CompactStrRef::write("hello", &mut buf);
+///
+/// let (view, rest) = CompactStrRef::read(&buf).unwrap();
+/// assert_eq!(view, "hello");
+/// assert!(rest.is_empty());
+///
+/// let owned = CompactString::from(view);
+/// assert!(!owned.is_heap_allocated());
+/// ```
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CompactStrRef<'a> {
+    text: &'a str,
+}
+
+impl<'a> CompactStrRef<'a> {
+    /// Reads a single length-prefixed string from the start of `buf`, returning the view and the
+    /// remainder of the buffer.
+    pub fn read(buf: &'a [u8]) -> Result<(Self, &'a [u8]), StrTableError> {
+        if buf.len() < LEN_PREFIX {
+            return Err(StrTableError::UnexpectedEof);
+        }
+        let (len, rest) = buf.split_at(LEN_PREFIX);
+        let len = u32::from_le_bytes([len[0], len[1], len[2], len[3]]) as usize;
+
+        if rest.len() < len {
+            return Err(StrTableError::UnexpectedEof);
+        }
+        let (text, rest) = rest.split_at(len);
+        let text = core::str::from_utf8(text).map_err(StrTableError::Utf8)?;
+
+        Ok((CompactStrRef { text }, rest))
+    }
+
+    /// Appends `text` onto the end of `out`, in the layout expected by [`CompactStrRef::read`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of `text` does not fit in a `u32`.
+    pub fn write(text: &str, out: &mut Vec<u8>) {
+        let len = u32::try_from(text.len()).expect("string is too long for a StrTable");
+
+        out.reserve(LEN_PREFIX + text.len());
+        out.extend_from_slice(&len.to_le_bytes());
+        out.extend_from_slice(text.as_bytes());
+    }
+
+    /// Returns the underlying `&str`, borrowed from the table.
+    #[inline]
+    pub fn as_str(&self) -> &'a str {
+        self.text
+    }
+
+    /// Copies the string into a [`CompactString`].
+    #[inline]
+    pub fn to_compact_string(&self) -> CompactString {
+        CompactString::new(self.text)
+    }
+}
+
+impl Deref for CompactStrRef<'_> {
+    type Target = str;
+
+    #[inline]
+    fn deref(&self) -> &str {
+        self.text
+    }
+}
+
+impl PartialEq<str> for CompactStrRef<'_> {
+    fn eq(&self, other: &str) -> bool {
+        self.text == other
+    }
+}
+
+impl PartialEq<&str> for CompactStrRef<'_> {
+    fn eq(&self, other: &&str) -> bool {
+        self.text == *other
+    }
+}
+
+impl fmt::Debug for CompactStrRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.text, f)
+    }
+}
+
+impl fmt::Display for CompactStrRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.text, f)
+    }
+}
+
+impl From<CompactStrRef<'_>> for CompactString {
+    #[inline]
+    fn from(value: CompactStrRef<'_>) -> Self {
+        value.to_compact_string()
+    }
+}
+
+/// A sequence of strings laid out contiguously in a single buffer.
+///
+/// # Examples
+/// ```
+/// use compact_str::StrTable;
+///
+/// let buf = StrTable::encode(["apple", "banana", "cherry"]);
+/// let table = StrTable::new(&buf);
+///
+/// let fruits: Vec<&str> = table.iter().map(|s| s.unwrap().as_str()).collect();
+/// assert_eq!(fruits, ["apple", "banana", "cherry"]);
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct StrTable<'a> {
+    buf: &'a [u8],
+}
+
+impl<'a> StrTable<'a> {
+    /// Creates a view over an encoded table, this does not validate the contents up front.
+    #[inline]
+    pub fn new(buf: &'a [u8]) -> Self {
+        StrTable { buf }
+    }
+
+    /// Encodes the provided strings into a new buffer.
+    pub fn encode<I, S>(strings: I) -> Vec<u8>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut out = Vec::new();
+        for s in strings {
+            CompactStrRef::write(s.as_ref(), &mut out);
+        }
+        out
+    }
+
+    /// Returns an iterator over the strings in the table.
+    ///
+    /// If the table is malformed the iterator yields a single error and then stops.
+    #[inline]
+    pub fn iter(&self) -> StrTableIter<'a> {
+        StrTableIter { buf: self.buf }
+    }
+}
+
+impl<'a> IntoIterator for StrTable<'a> {
+    type Item = Result<CompactStrRef<'a>, StrTableError>;
+    type IntoIter = StrTableIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the strings in a [`StrTable`], created by [`StrTable::iter()`].
+#[derive(Clone, Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct StrTableIter<'a> {
+    buf: &'a [u8],
+}
+
+impl<'a> Iterator for StrTableIter<'a> {
+    type Item = Result<CompactStrRef<'a>, StrTableError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.buf.is_empty() {
+            return None;
+        }
+
+        match CompactStrRef::read(self.buf) {
+            Ok((s, rest)) => {
+                self.buf = rest;
+                Some(Ok(s))
+            }
+            Err(err) => {
+                self.buf = &[];
+                Some(Err(err))
+            }
+        }
+    }
+}
+
+impl core::iter::FusedIterator for StrTableIter<'_> {}
+
+/// A possible error value when reading from a [`StrTable`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum StrTableError {
+    /// The buffer ended in the middle of an entry
+    UnexpectedEof,
+    /// An entry was not valid UTF-8
+    Utf8(Utf8Error),
+}
+
+impl fmt::Display for StrTableError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StrTableError::UnexpectedEof => f.write_str("unexpected end of string table"),
+            StrTableError::Utf8(err) => err.fmt(f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl std::error::Error for StrTableError {}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::String;
+    use alloc::vec::Vec;
+
+    use test_strategy::proptest;
+
+    use super::{CompactStrRef, StrTable, StrTableError};
+    use crate::CompactString;
+
+    #[test]
+    fn test_layout() {
+        let buf = StrTable::encode(["ab", ""]);
+        assert_eq!(buf, [2, 0, 0, 0, b'a', b'b', 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let long = "this is a long string that will be on the heap";
+        let buf = StrTable::encode(["hello", "", long]);
+
+        let strings: Vec<CompactString> = StrTable::new(&buf)
+            .iter()
+            .map(|s| CompactString::from(s.unwrap()))
+            .collect();
+        assert_eq!(strings, ["hello", "", long]);
+        assert!(!strings[0].is_heap_allocated());
+        assert!(strings[2].is_heap_allocated());
+    }
+
+    #[test]
+    fn test_zero_copy() {
+        let buf = StrTable::encode(["hello"]);
+        let (view, _) = CompactStrRef::read(&buf).unwrap();
+        assert_eq!(view.as_ptr(), buf[4..].as_ptr());
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            CompactStrRef::read(&[1, 0]).unwrap_err(),
+            StrTableError::UnexpectedEof
+        );
+        assert_eq!(
+            CompactStrRef::read(&[3, 0, 0, 0, b'a']).unwrap_err(),
+            StrTableError::UnexpectedEof
+        );
+        assert!(matches!(
+            CompactStrRef::read(&[1, 0, 0, 0, 0xFF]).unwrap_err(),
+            StrTableError::Utf8(_)
+        ));
+
+        // a truncated table yields the valid entries, then a single error
+        let mut buf = StrTable::encode(["a", "b"]);
+        buf.pop();
+        let mut iter = StrTable::new(&buf).iter();
+        assert_eq!(iter.next().unwrap().unwrap(), "a");
+        assert!(iter.next().unwrap().is_err());
+        assert!(iter.next().is_none());
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[proptest]
+    fn proptest_roundtrip(strings: Vec<String>) {
+        let buf = StrTable::encode(&strings);
+        let decoded: Vec<&str> = StrTable::new(&buf)
+            .iter()
+            .map(|s| s.unwrap().as_str())
+            .collect();
+        assert_eq!(decoded, strings);
+    }
+}
@@ -10,7 +10,7 @@ use proptest::prelude::*;
 use test_case::test_case;
 use test_strategy::proptest;
 
-use crate::{format_compact, CompactString, ToCompactString};
+use crate::{format_compact, CompactString, IntoBytesIter, IntoChars, RangeError, ToCompactString};
 
 #[cfg(target_pointer_width = "64")]
 const MAX_SIZE: usize = 24;
@@ -189,6 +189,40 @@ fn proptest_extend_chars_allocated_properly(
     assert_allocated_properly(&compact);
 }
 
+#[proptest]
+#[cfg_attr(miri, ignore)]
+fn proptest_pop_n_and_truncate_chars(#[strategy(rand_unicode())] control: String, n: u8) {
+    let n = n as usize;
+    let chars: Vec<char> = control.chars().collect();
+    let keep = chars.len().saturating_sub(n);
+
+    let mut compact = CompactString::new(&control);
+    let tail = compact.pop_n(n);
+    prop_assert_eq!(&compact, &chars[..keep].iter().collect::<String>());
+    prop_assert_eq!(&tail, &chars[keep..].iter().collect::<String>());
+
+    let mut compact = CompactString::new(&control);
+    compact.truncate_chars(n);
+    prop_assert_eq!(&compact, &control.chars().take(n).collect::<String>());
+}
+
+#[test]
+fn test_pop_n_and_truncate_chars_static_str() {
+    let long = "this is a long string that will not be inlined";
+
+    let mut compact = CompactString::const_new(long);
+    assert_eq!(compact.pop_n(0), "");
+    let tail = compact.pop_n(7);
+    assert_eq!(tail, "inlined");
+    assert_eq!(compact, "this is a long string that will not be ");
+
+    let mut compact = CompactString::const_new(long);
+    compact.truncate_chars(4);
+    assert_eq!(compact, "this");
+    compact.truncate_chars(0);
+    assert_eq!(compact, "");
+}
+
 #[proptest]
 #[cfg_attr(miri, ignore)]
 fn proptest_truncate(#[strategy(rand_unicode())] mut control: String, val: u8) {
@@ -262,6 +296,34 @@ fn proptest_from_utf16_lossy_random(#[strategy(rand_u16s())] buf: Vec<u16>) {
     assert_eq!(compact, control);
 }
 
+#[proptest]
+#[cfg_attr(miri, ignore)]
+fn proptest_from_utf16_iter_random(#[strategy(rand_u16s())] buf: Vec<u16>) {
+    // `filter` hides the exact length of the iterator, so we can't rely on its size_hint
+    let units = || buf.iter().copied().filter(|_| true);
+
+    let compact = CompactString::from_utf16_iter(units());
+    match String::from_utf16(&buf) {
+        Ok(control) => assert_eq!(compact.unwrap(), control),
+        Err(_) => assert!(compact.is_err()),
+    }
+
+    let compact = CompactString::from_utf16_iter_lossy(units());
+    assert_eq!(compact, String::from_utf16_lossy(&buf));
+}
+
+#[test]
+fn test_from_utf16_iter_capacity() {
+    let long = "this is a long string that will be on the heap 🦀";
+    let compact = CompactString::from_utf16_iter(long.encode_utf16()).unwrap();
+    assert_eq!(compact, long);
+    assert!(compact.is_heap_allocated());
+
+    let short = CompactString::from_utf16_iter("short 🦀".encode_utf16()).unwrap();
+    assert_eq!(short, "short 🦀");
+    assert!(!short.is_heap_allocated());
+}
+
 #[proptest]
 #[cfg_attr(miri, ignore)]
 fn proptest_remove(#[strategy(rand_unicode_with_range(1..80))] mut control: String, val: u8) {
@@ -542,6 +604,33 @@ fn test_compact_str_is_send_and_sync() {
     is_send_and_sync::<CompactString>();
 }
 
+#[test_case("hello"; "ascii")]
+#[test_case("this is a long string that will be on the heap"; "long ascii")]
+#[test_case("quote\"s and back\\slash"; "escaped ascii")]
+#[test_case("tab\tnew\nline\0"; "control chars")]
+#[test_case("🦀 crab"; "unicode")]
+#[test_case("e\u{301}\u{200d}"; "grapheme extend")]
+#[test_case(""; "empty")]
+fn test_fmt(data: &str) {
+    let compact = CompactString::new(data);
+
+    assert_eq!(format!("{:?}", compact), format!("{:?}", data));
+    assert_eq!(format!("{:#?}", compact), format!("{:#?}", data));
+    assert_eq!(format!("{}", compact), format!("{}", data));
+    assert_eq!(format!("{:>32}", compact), format!("{:>32}", data));
+    assert_eq!(format!("{:-^9.3}", compact), format!("{:-^9.3}", data));
+    assert_eq!(format!("{:*<12}", compact), format!("{:*<12}", data));
+}
+
+#[proptest]
+#[cfg_attr(miri, ignore)]
+fn proptest_fmt(#[strategy(rand_unicode())] s: String) {
+    let compact = CompactString::new(&s);
+
+    prop_assert_eq!(format!("{:?}", compact), format!("{:?}", s));
+    prop_assert_eq!(format!("{:>32}", compact), format!("{:>32}", s));
+}
+
 #[test_case(CompactString::default(); "inline")]
 #[test_case(CompactString::const_new(""); "static_str")]
 fn test_fmt_write(mut compact: CompactString) {
@@ -1210,6 +1299,392 @@ fn test_into_string_long_static_str() {
     assert_eq!(str_len, new_str_cap);
 }
 
+#[test]
+fn test_into_boxed_str_exact_capacity_reuses_buffer() {
+    let string = String::from("this is a long string that will be on the heap");
+    assert_eq!(string.len(), string.capacity());
+    let str_addr = string.as_ptr();
+
+    let compact = CompactString::from(string);
+    let boxed = Box::<str>::from(compact);
+
+    // the allocation should get handed over to the Box<str> as-is
+    assert_eq!(str_addr, boxed.as_ptr());
+    assert_eq!(&*boxed, "this is a long string that will be on the heap");
+}
+
+#[test]
+fn test_into_boxed_str_with_excess_capacity() {
+    let mut compact = CompactString::with_capacity(128);
+    compact.push_str("abcdefghijklmnopqrstuvwxyz");
+    assert!(compact.is_heap_allocated());
+
+    let boxed = Box::<str>::from(compact);
+    assert_eq!(&*boxed, "abcdefghijklmnopqrstuvwxyz");
+}
+
+#[test]
+fn test_into_boxed_str_where_32_bit_capacity_is_on_heap() {
+    let buf = vec![b'a'; SIXTEEN_MB - 1];
+    // SAFETY: `buf` is filled with ASCII `a`s.
+    let string = unsafe { String::from_utf8_unchecked(buf) };
+    let str_addr = string.as_ptr();
+
+    let compact = CompactString::from(string);
+    let boxed = Box::<str>::from(compact);
+
+    assert_eq!(boxed.len(), SIXTEEN_MB - 1);
+    if cfg!(target_pointer_width = "64") {
+        assert_eq!(str_addr, boxed.as_ptr());
+    } else {
+        assert_eq!(&boxed.as_bytes()[0..10], b"aaaaaaaaaa");
+    }
+}
+
+#[test_case(""; "empty")]
+#[test_case("hello"; "short")]
+#[test_case("this is a long string that will be on the heap"; "long")]
+fn test_into_boxed_str_static_and_inline(data: &'static str) {
+    let boxed = Box::<str>::from(CompactString::const_new(data));
+    assert_eq!(&*boxed, data);
+
+    let boxed = Box::<str>::from(CompactString::new(data));
+    assert_eq!(&*boxed, data);
+}
+
+#[proptest]
+#[cfg_attr(miri, ignore)]
+fn proptest_char_count(#[strategy(rand_unicode())] s: String) {
+    let compact = CompactString::new(&s);
+    prop_assert_eq!(compact.char_count(), s.chars().count());
+}
+
+#[test_case(""; "empty")]
+#[test_case("hello"; "short")]
+#[test_case("this is a long string that will be on the heap"; "long")]
+fn test_into_bytes_vec(data: &'static str) {
+    assert_eq!(CompactString::new(data).into_bytes_vec(), data.as_bytes());
+    assert_eq!(
+        CompactString::const_new(data).into_bytes_vec(),
+        data.as_bytes()
+    );
+
+    // heap allocated strings hand over their buffer
+    let compact = CompactString::from_string_buffer(String::from(data));
+    let is_heap = compact.is_heap_allocated();
+    let ptr = compact.as_ptr();
+    let cap = compact.capacity();
+    let bytes = compact.into_bytes_vec();
+    if is_heap {
+        assert_eq!(bytes.as_ptr(), ptr);
+        assert_eq!(bytes.capacity(), cap);
+    }
+    assert_eq!(bytes, data.as_bytes());
+}
+
+#[test]
+fn test_try_into_static() {
+    let data = "this is a long string that will be on the heap";
+
+    let compact = CompactString::const_new(data);
+    let s = compact.try_into_static().unwrap();
+    assert_eq!(s.as_ptr(), data.as_ptr());
+
+    // short strings get inlined, so they're no longer 'static
+    let compact = CompactString::const_new("hello");
+    assert_eq!(compact.try_into_static().unwrap_err(), "hello");
+
+    let compact = CompactString::new(data);
+    assert_eq!(compact.try_into_static().unwrap_err(), data);
+
+    // mutating a static str copies it into an owned buffer
+    let mut compact = CompactString::const_new(data);
+    compact.push('!');
+    assert!(compact.try_into_static().is_err());
+}
+
+#[test_case("", "/", "a", "a"; "empty")]
+#[test_case("a", "/", "b", "a/b"; "simple")]
+#[test_case("a/", "/", "b", "a/b"; "trailing separator")]
+#[test_case("a", "/", "/b", "a/b"; "leading separator")]
+#[test_case("a/", "/", "/b", "a/b"; "both separators")]
+#[test_case("a", "/", "", "a/"; "empty item")]
+#[test_case("a", "::", "b", "a::b"; "multi byte separator")]
+#[test_case("a:", "::", "b", "a:::b"; "partial separator")]
+#[test_case("a", "", "b", "ab"; "empty separator")]
+fn test_push_joined(initial: &str, separator: &str, item: &str, expected: &str) {
+    let mut compact = CompactString::new(initial);
+    compact.push_joined(separator, item);
+    assert_eq!(compact, expected);
+}
+
+#[test]
+fn test_push_joined_heap() {
+    let mut compact = CompactString::default();
+    for segment in [
+        "this", "is/", "/a", "long", "path", "that/", "will", "be", "on", "the", "heap",
+    ] {
+        compact.push_joined("/", segment);
+    }
+    assert_eq!(compact, "this/is/a/long/path/that/will/be/on/the/heap");
+    assert!(compact.is_heap_allocated());
+}
+
+#[test_case(""; "empty")]
+#[test_case("hello"; "short")]
+#[test_case("abcdefghijklmnopqrstuvwx"; "max inline")]
+#[test_case("this is a long string that will be on the heap"; "long")]
+fn test_new_heap(data: &str) {
+    let mut compact = CompactString::new_heap(data);
+    assert_eq!(compact, data);
+    assert!(compact.is_heap_allocated());
+    assert!(compact.capacity() >= data.len());
+
+    // mutating within the capacity keeps us on the heap
+    compact.push('!');
+    assert_eq!(compact, format!("{}!", data));
+    assert!(compact.is_heap_allocated());
+
+    assert_eq!(String::from(compact), format!("{}!", data));
+}
+
+#[test]
+fn test_new_if_inline() {
+    let max = "a".repeat(crate::MAX_INLINE_SIZE);
+    for text in ["", "hello", "🦀", max.as_str()] {
+        let compact = CompactString::new_if_inline(text).unwrap();
+        assert_eq!(compact, text);
+        assert!(!compact.is_heap_allocated());
+        assert_eq!(compact.as_static_str(), None);
+    }
+
+    let too_long = "a".repeat(crate::MAX_INLINE_SIZE + 1);
+    assert_eq!(CompactString::new_if_inline(&too_long), None);
+}
+
+#[test_case(""; "empty")]
+#[test_case("\r\n"; "only newline")]
+#[test_case("a\rb\r\r\nc\n\r"; "lone carriage returns")]
+#[test_case("\u{85}\u{9f}\u{a0}\x7f\x1b\t\n"; "control chars")]
+#[test_case("this is a long string\r\nthat will be on\x00 the heap\r\n"; "long")]
+fn test_normalize_newlines_and_strip_control_chars(data: &'static str) {
+    let mut compact = CompactString::new(data);
+    let capacity = compact.capacity();
+    compact.normalize_newlines();
+    assert_eq!(compact, data.replace("\r\n", "\n"));
+    assert_eq!(compact.capacity(), capacity);
+
+    let control: String = data
+        .chars()
+        .filter(|&c| !c.is_control() || c == '\n' || c == '\t')
+        .collect();
+    let mut compact = CompactString::new(data);
+    compact.strip_control_chars();
+    assert_eq!(compact, control);
+    assert_eq!(compact.capacity(), capacity);
+
+    // untouched static strings stay static
+    let mut compact = CompactString::const_new("this is a long string that has nothing to clean");
+    compact.normalize_newlines();
+    compact.strip_control_chars();
+    assert!(compact.as_static_str().is_some());
+}
+
+#[cfg_attr(miri, ignore)]
+#[proptest]
+fn proptest_normalize_newlines_and_strip_control_chars(
+    #[strategy(r"[a\r\n\t\x00\x7f\u{80}\u{9f}\u{a0}é]{0,64}")] data: String,
+) {
+    let mut compact = CompactString::new(&data);
+    compact.normalize_newlines();
+    prop_assert_eq!(&compact, &data.replace("\r\n", "\n"));
+
+    let control: String = data
+        .chars()
+        .filter(|&c| !c.is_control() || c == '\n' || c == '\t')
+        .collect();
+    let mut compact = CompactString::new(&data);
+    compact.strip_control_chars();
+    prop_assert_eq!(&compact, &control);
+}
+
+/// The expected result of `pad_to`, built the slow way
+fn padded(data: &str, width: usize, fill: char, align: core::fmt::Alignment) -> String {
+    let padding = width.saturating_sub(data.chars().count());
+    let before = match align {
+        core::fmt::Alignment::Left => 0,
+        core::fmt::Alignment::Right => padding,
+        core::fmt::Alignment::Center => padding / 2,
+    };
+    let fill = fill.to_string();
+    [
+        fill.repeat(before),
+        data.to_string(),
+        fill.repeat(padding - before),
+    ]
+    .concat()
+}
+
+#[test_case("", 0, ' '; "empty no padding")]
+#[test_case("", 4, '-'; "empty")]
+#[test_case("abc", 2, '-'; "already wider")]
+#[test_case("abc", 8, '·'; "wide fill")]
+#[test_case("héllo", 9, '🦀'; "unicode")]
+#[test_case("a long string that will be on the heap", 48, ' '; "heap")]
+fn test_pad_to(data: &'static str, width: usize, fill: char) {
+    use core::fmt::Alignment;
+
+    for align in [Alignment::Left, Alignment::Right, Alignment::Center] {
+        let mut compact = CompactString::new(data);
+        compact.pad_to(width, fill, align);
+        assert_eq!(compact, padded(data, width, fill, align));
+    }
+
+    // matches what `format!` does
+    let mut compact = CompactString::new(data);
+    compact.pad_to(width, ' ', Alignment::Center);
+    assert_eq!(compact, format!("{:^width$}", data, width = width));
+}
+
+#[test]
+fn test_pad_to_reuses_capacity() {
+    let mut compact = CompactString::with_capacity(64);
+    compact.push_str("a long string that will be on the heap");
+    let ptr = compact.as_ptr();
+
+    compact.pad_to(50, '.', core::fmt::Alignment::Center);
+    assert_eq!(compact.chars().count(), 50);
+    assert_eq!(compact.as_ptr(), ptr);
+
+    let mut compact = CompactString::new("short");
+    compact.pad_to(MAX_SIZE, ' ', core::fmt::Alignment::Right);
+    assert!(!compact.is_heap_allocated());
+}
+
+#[cfg_attr(miri, ignore)]
+#[proptest]
+fn proptest_pad_to(
+    #[strategy(rand_unicode())] data: String,
+    #[strategy(0..80usize)] width: usize,
+    fill: char,
+) {
+    for align in [
+        core::fmt::Alignment::Left,
+        core::fmt::Alignment::Right,
+        core::fmt::Alignment::Center,
+    ] {
+        let mut compact = CompactString::new(&data);
+        compact.pad_to(width, fill, align);
+        prop_assert_eq!(&compact, &padded(&data, width, fill, align));
+    }
+}
+
+#[test]
+fn test_map_chars() {
+    let mask = |c: char| if c.is_alphanumeric() { 'x' } else { c };
+
+    let long = CompactString::new("a long string that needs to be allocated on the heap");
+    let masked = long.map_chars(mask);
+    assert_eq!(masked, long.chars().map(mask).collect::<String>());
+    assert_eq!(masked.capacity(), long.len());
+
+    let mut compact = long.clone();
+    let ptr = compact.as_ptr();
+    compact.map_chars_in_place(mask);
+    assert_eq!(compact, masked);
+    assert_eq!(compact.as_ptr(), ptr);
+
+    // a wider char midway through falls back to building a new string
+    let mut calls = 0;
+    let mut compact = CompactString::new("abcé");
+    compact.map_chars_in_place(|c| {
+        calls += 1;
+        if c == 'b' {
+            '✓'
+        } else {
+            c.to_ascii_uppercase()
+        }
+    });
+    assert_eq!(compact, "A✓Cé");
+    assert_eq!(calls, 4);
+    assert!(!compact.is_heap_allocated());
+}
+
+#[cfg_attr(miri, ignore)]
+#[proptest]
+fn proptest_map_chars(#[strategy(rand_unicode())] data: String) {
+    let f = |c: char| match c {
+        'a'..='z' => c.to_ascii_uppercase(),
+        'é' => 'e',
+        c if c.is_whitespace() => '·',
+        c => c,
+    };
+    let expected: String = data.chars().map(f).collect();
+
+    let compact = CompactString::new(&data);
+    prop_assert_eq!(&compact.map_chars(f), &expected);
+
+    let mut compact = CompactString::new(&data);
+    compact.map_chars_in_place(f);
+    prop_assert_eq!(&compact, &expected);
+}
+
+#[test_case(""; "empty")]
+#[test_case("   "; "only whitespace")]
+#[test_case("hello"; "nothing to trim")]
+#[test_case("  hello \t"; "short")]
+#[test_case("\u{3000}\u{a0}wide\u{2028}"; "unicode whitespace")]
+#[test_case("\n    this is a long string that will be on the heap    \n"; "long")]
+fn test_trim_in_place(data: &'static str) {
+    let mut compact = CompactString::new(data);
+    let capacity = compact.capacity();
+    compact.trim_in_place();
+    assert_eq!(compact, data.trim());
+    assert_eq!(compact.capacity(), capacity);
+
+    let mut compact = CompactString::new(data);
+    compact.trim_start_in_place();
+    assert_eq!(compact, data.trim_start());
+
+    let mut compact = CompactString::new(data);
+    compact.trim_end_in_place();
+    assert_eq!(compact, data.trim_end());
+
+    let mut compact = CompactString::const_new(data);
+    compact.trim_in_place();
+    assert_eq!(compact, data.trim());
+
+    assert_eq!(CompactString::new(data).trimmed(), data.trim());
+}
+
+#[proptest]
+#[cfg_attr(miri, ignore)]
+fn proptest_trim_in_place(#[strategy(rand_unicode())] s: String) {
+    let padded = format!(" \t{}\u{3000}\n", s);
+
+    let mut compact = CompactString::new(&padded);
+    compact.trim_in_place();
+    prop_assert_eq!(compact, padded.trim());
+
+    let mut compact = CompactString::new(&padded);
+    compact.trim_start_in_place();
+    prop_assert_eq!(compact, padded.trim_start());
+}
+
+#[proptest]
+#[cfg_attr(miri, ignore)]
+fn proptest_lines_compact(#[strategy(rand_unicode_collection())] lines: Vec<String>) {
+    let text = lines.join("\n");
+
+    let compact: Vec<CompactString> = CompactString::lines_compact(&text).collect();
+    let control: Vec<&str> = text.lines().collect();
+    prop_assert_eq!(&compact, &control);
+
+    let compact: Vec<CompactString> = CompactString::lines_compact(&text).rev().collect();
+    let control: Vec<&str> = text.lines().rev().collect();
+    prop_assert_eq!(compact, control);
+}
+
 #[test]
 fn test_into_string_empty_str() {
     let data = "";
@@ -1493,6 +1968,100 @@ fn test_from_utf16() {
     }
 }
 
+#[test_case("" ; "empty")]
+#[test_case("hello.txt" ; "short")]
+#[test_case(&"a".repeat(MAX_SIZE) ; "max inline")]
+#[test_case(&"a".repeat(MAX_SIZE + 1) ; "min heap")]
+#[test_case(&format!("{}🦄", "a".repeat(MAX_SIZE - 2)) ; "char straddles inline capacity")]
+#[test_case("this is a long string 🌍 that will be on the heap" ; "long")]
+fn test_from_utf16_inline_boundary(control: &str) {
+    let utf16_buf: Vec<u16> = control.encode_utf16().collect();
+
+    let compact = CompactString::from_utf16(&utf16_buf).unwrap();
+    assert_eq!(compact, control);
+    assert_eq!(compact.is_heap_allocated(), control.len() > MAX_SIZE);
+
+    let compact = CompactString::from_utf16_lossy(&utf16_buf);
+    assert_eq!(compact, control);
+    assert_eq!(compact.is_heap_allocated(), control.len() > MAX_SIZE);
+}
+
+#[test_case("" ; "empty")]
+#[test_case("hello.txt" ; "short")]
+#[test_case(&"a".repeat(MAX_SIZE) ; "max inline")]
+#[test_case(&"a".repeat(MAX_SIZE + 1) ; "min heap")]
+#[test_case(&format!("{}🦄", "a".repeat(MAX_SIZE - 2)) ; "char straddles inline capacity")]
+#[test_case("this is a long string 🌍 that will be on the heap" ; "long")]
+fn test_from_utf32(control: &str) {
+    let utf32_buf: Vec<u32> = control.chars().map(u32::from).collect();
+    let le: Vec<u8> = utf32_buf.iter().flat_map(|c| c.to_le_bytes()).collect();
+    let be: Vec<u8> = utf32_buf.iter().flat_map(|c| c.to_be_bytes()).collect();
+
+    for compact in [
+        CompactString::from_utf32(&utf32_buf).unwrap(),
+        CompactString::from_utf32_lossy(&utf32_buf),
+        CompactString::from_utf32le(&le).unwrap(),
+        CompactString::from_utf32le_lossy(&le),
+        CompactString::from_utf32be(&be).unwrap(),
+        CompactString::from_utf32be_lossy(&be),
+    ] {
+        assert_eq!(compact, control);
+        assert_eq!(compact.is_heap_allocated(), control.len() > MAX_SIZE);
+        // the output is sized up front
+        assert_eq!(
+            compact.capacity(),
+            CompactString::with_capacity(control.len()).capacity()
+        );
+    }
+}
+
+#[test]
+fn test_from_utf32_invalid() {
+    for invalid in [0xD800, 0xDFFF, 0x110000, u32::MAX] {
+        let buf = [u32::from('a'), invalid, u32::from('b')];
+        assert!(CompactString::from_utf32(buf).is_err());
+        assert_eq!(CompactString::from_utf32_lossy(buf), "a\u{FFFD}b");
+
+        let le: Vec<u8> = buf.iter().flat_map(|c| c.to_le_bytes()).collect();
+        assert!(CompactString::from_utf32le(&le).is_err());
+        assert_eq!(CompactString::from_utf32le_lossy(&le), "a\u{FFFD}b");
+
+        let be: Vec<u8> = buf.iter().flat_map(|c| c.to_be_bytes()).collect();
+        assert!(CompactString::from_utf32be(&be).is_err());
+        assert_eq!(CompactString::from_utf32be_lossy(&be), "a\u{FFFD}b");
+    }
+
+    // incomplete trailing code units
+    for extra in 1..4 {
+        let mut le = u32::from('a').to_le_bytes().to_vec();
+        le.resize(4 + extra, 0);
+        assert!(CompactString::from_utf32le(&le).is_err());
+        assert!(CompactString::from_utf32be(&le).is_err());
+        assert_eq!(CompactString::from_utf32le_lossy(&le), "a\u{FFFD}");
+    }
+
+    // the replacement character for trailing bytes is reserved up front
+    let mut le: Vec<u8> = "a"
+        .repeat(30)
+        .chars()
+        .flat_map(|c| u32::from(c).to_le_bytes())
+        .collect();
+    le.push(0);
+    let lossy = CompactString::from_utf32le_lossy(&le);
+    assert_eq!(lossy.len(), 33);
+    assert_eq!(lossy.capacity(), 33);
+}
+
+#[cfg_attr(miri, ignore)]
+#[proptest]
+fn proptest_from_utf32_roundtrip(#[strategy(rand_unicode())] control: String) {
+    let utf32_buf: Vec<u32> = control.chars().map(u32::from).collect();
+    prop_assert_eq!(CompactString::from_utf32(&utf32_buf).unwrap(), &control);
+
+    let be: Vec<u8> = utf32_buf.iter().flat_map(|c| c.to_be_bytes()).collect();
+    prop_assert_eq!(CompactString::from_utf32be_lossy(&be), &control);
+}
+
 #[test]
 fn test_reserve_shrink_roundtrip() {
     const TEXT: &str = "Hello.";
@@ -1639,6 +2208,10 @@ fn test_from_utf8_unchecked_sanity() {
 }
 
 #[test]
+#[cfg_attr(
+    all(feature = "debug-validate", debug_assertions),
+    should_panic(expected = "invalid UTF-8")
+)]
 fn test_from_utf8_unchecked_long() {
     let bytes = [255; 2048];
     let compact = unsafe { CompactString::from_utf8_unchecked(bytes) };
@@ -1648,6 +2221,10 @@ fn test_from_utf8_unchecked_long() {
 }
 
 #[test]
+#[cfg_attr(
+    all(feature = "debug-validate", debug_assertions),
+    should_panic(expected = "invalid UTF-8")
+)]
 fn test_from_utf8_unchecked_short() {
     let bytes = [255; 10];
     let compact = unsafe { CompactString::from_utf8_unchecked(bytes) };
@@ -1851,6 +2428,73 @@ fn test_into_cow() {
     assert_eq!(og, cow);
 }
 
+#[test]
+fn test_into_cow_generic() {
+    fn takes_cow<'a>(s: impl Into<Cow<'a, str>>) -> Cow<'a, str> {
+        s.into()
+    }
+
+    let compact = CompactString::new("borrowed");
+    assert!(matches!(takes_cow(&compact), Cow::Borrowed("borrowed")));
+
+    let cow: Cow<'static, str> = takes_cow(CompactString::new("owned"));
+    assert_eq!(cow, "owned");
+
+    // static strings stay borrowed
+    let cow = takes_cow(CompactString::const_new("this is a long &'static str"));
+    assert!(matches!(cow, Cow::Borrowed("this is a long &'static str")));
+}
+
+#[test]
+fn test_into_arc_shared() {
+    use alloc::sync::Arc;
+
+    let long = "i am a long string that will be allocated on the heap";
+    let compact = CompactString::new(long);
+
+    let shared = compact.clone().into_arc();
+    assert_eq!(&*shared, long);
+    assert_eq!(shared, compact);
+    assert_eq!(shared, &compact);
+    assert_eq!(compact, shared);
+    assert_eq!(&compact, &shared);
+    assert_ne!(Arc::<str>::from("other"), compact);
+
+    let clone = Arc::clone(&shared);
+    assert!(Arc::ptr_eq(&shared, &clone));
+}
+
+#[test]
+fn test_cmp_shared_str() {
+    use alloc::rc::Rc;
+    use alloc::sync::Arc;
+    use core::cmp::Ordering;
+
+    let a = CompactString::new("apple");
+    let b = CompactString::new("this is a long string that will be on the heap");
+
+    let arc_a: Arc<str> = Arc::from("apple");
+    let rc_a: Rc<str> = Rc::from("apple");
+    assert_eq!(rc_a, a);
+    assert_eq!(rc_a, &a);
+    assert_eq!(a, rc_a);
+    assert_ne!(rc_a, b);
+
+    assert_eq!(a.partial_cmp(&arc_a), Some(Ordering::Equal));
+    assert_eq!(arc_a.partial_cmp(&a), Some(Ordering::Equal));
+    assert_eq!(a.partial_cmp(&rc_a), Some(Ordering::Equal));
+    assert_eq!(rc_a.partial_cmp(&a), Some(Ordering::Equal));
+
+    let arc_b: Arc<str> = Arc::from(b.as_str());
+    let rc_b: Rc<str> = Rc::from(b.as_str());
+    assert!(a < arc_b);
+    assert!(arc_b > a);
+    assert!(a < rc_b);
+    assert!(rc_b > a);
+    assert!(b > arc_a);
+    assert!(rc_a < b);
+}
+
 #[test]
 fn test_into_arc() {
     let short = "short";
@@ -1944,6 +2588,1214 @@ fn test_into_vec_u8() {
     assert_eq!(&l, long.as_bytes());
 }
 
+#[test]
+fn test_try_from_cstr() {
+    let short = c"short";
+    let long = c"i am a long string that will be allocated on the heap";
+
+    let s = CompactString::try_from(short).unwrap();
+    assert_eq!(s, "short");
+    assert!(!s.is_heap_allocated());
+
+    let l = CompactString::try_from(long).unwrap();
+    assert_eq!(l, "i am a long string that will be allocated on the heap");
+    assert!(l.is_heap_allocated());
+
+    let invalid = c"\xF0\x9F";
+    assert!(CompactString::try_from(invalid).is_err());
+}
+
+#[test]
+fn test_try_from_non_zero_u8() {
+    fn to_non_zero(s: &[u8]) -> Vec<num::NonZeroU8> {
+        s.iter().map(|b| num::NonZeroU8::new(*b).unwrap()).collect()
+    }
+
+    let short = to_non_zero(b"short");
+    let s = CompactString::try_from(short.as_slice()).unwrap();
+    assert_eq!(s, "short");
+    let s = CompactString::try_from(short).unwrap();
+    assert_eq!(s, "short");
+    assert!(!s.is_heap_allocated());
+
+    // long vectors re-use their buffer
+    let long = to_non_zero(b"i am a long string that will be allocated on the heap");
+    let long_ptr = long.as_ptr().cast::<u8>();
+    let l = CompactString::try_from(long).unwrap();
+    assert_eq!(l, "i am a long string that will be allocated on the heap");
+    assert!(l.is_heap_allocated());
+    assert_eq!(l.as_ptr(), long_ptr);
+
+    let invalid = to_non_zero(b"\xF0\x9F");
+    assert!(CompactString::try_from(invalid.as_slice()).is_err());
+    assert!(CompactString::try_from(invalid).is_err());
+}
+
+#[test]
+fn test_try_from_bytes() {
+    let short = b"hello";
+    let long = b"this is a long string that will be on the heap";
+    let invalid = b"\xF0\x9F";
+
+    assert_eq!(CompactString::try_from(&short[..]).unwrap(), "hello");
+    assert_eq!(
+        CompactString::try_from(&invalid[..])
+            .unwrap_err()
+            .valid_up_to(),
+        0
+    );
+
+    let c = CompactString::try_from(short.to_vec()).unwrap();
+    assert_eq!(c, "hello");
+    assert!(!c.is_heap_allocated());
+
+    // long strings re-use the buffer
+    let bytes = long.to_vec();
+    let ptr = bytes.as_ptr();
+    let c = CompactString::try_from(bytes).unwrap();
+    assert_eq!(c.as_bytes(), long);
+    assert_eq!(c.as_ptr(), ptr);
+
+    // the buffer is returned on failure
+    let bytes = invalid.to_vec();
+    let ptr = bytes.as_ptr();
+    let err = CompactString::try_from(bytes).unwrap_err();
+    let bytes = err.into_bytes();
+    assert_eq!(bytes.as_ptr(), ptr);
+
+    assert_eq!(
+        CompactString::try_from(Cow::Borrowed(&long[..]))
+            .unwrap()
+            .as_bytes(),
+        long
+    );
+    assert_eq!(
+        CompactString::try_from(Cow::<[u8]>::Owned(long.to_vec()))
+            .unwrap()
+            .as_bytes(),
+        long
+    );
+    assert!(CompactString::try_from(Cow::<[u8]>::Owned(invalid.to_vec())).is_err());
+}
+
+fn try_from_bytes_generic<T: TryFrom<Vec<u8>>>(bytes: Vec<u8>) -> Option<T> {
+    T::try_from(bytes).ok()
+}
+
+#[test]
+fn test_try_from_bytes_generic() {
+    let c: CompactString = try_from_bytes_generic(b"hello".to_vec()).unwrap();
+    assert_eq!(c, "hello");
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_from_os_string() {
+    use std::ffi::{OsStr, OsString};
+
+    let long = "this is a long string that will be on the heap";
+    let os_string = OsString::from(String::from(long));
+    let compact = CompactString::try_from(os_string).unwrap();
+    assert_eq!(compact, long);
+
+    let compact = CompactString::try_from(OsString::from("short")).unwrap();
+    assert_eq!(compact, "short");
+    assert!(!compact.is_heap_allocated());
+
+    assert_eq!(CompactString::from_os_str_lossy(OsStr::new(long)), long);
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStrExt;
+
+        let invalid = OsStr::from_bytes(b"ab\xFFcd");
+        assert_eq!(CompactString::from_os_str_lossy(invalid), "ab\u{FFFD}cd");
+        assert_eq!(
+            CompactString::try_from(invalid.to_os_string()).unwrap_err(),
+            invalid
+        );
+    }
+
+    #[cfg(windows)]
+    {
+        use std::os::windows::ffi::OsStringExt;
+
+        // a lone surrogate
+        let invalid = OsString::from_wide(&[0x0061, 0xD800, 0x0062]);
+        assert_eq!(CompactString::from_os_str_lossy(&invalid), "a\u{FFFD}b");
+        assert_eq!(
+            CompactString::try_from(invalid.clone()).unwrap_err(),
+            invalid
+        );
+    }
+}
+
+#[test]
+fn test_replace_char_at() {
+    let mut s = CompactString::new("a😀c");
+    assert_eq!(s.replace_char_at(1, 'b'), '😀');
+    assert_eq!(s, "abc");
+    assert_eq!(s.replace_char_at(2, 'ℝ'), 'c');
+    assert_eq!(s, "abℝ");
+    assert_eq!(s.replace_char_at(2, 'ℤ'), 'ℝ');
+    assert_eq!(s, "abℤ");
+
+    // growing past the inline capacity moves the string to the heap
+    let mut s = CompactString::new("aaaaaaaaaaaaaaaaaaaaaaaa");
+    assert!(!s.is_heap_allocated());
+    s.replace_char_at(23, '😀');
+    assert_eq!(s, "aaaaaaaaaaaaaaaaaaaaaaa😀");
+    assert!(s.is_heap_allocated());
+}
+
+#[test]
+#[should_panic]
+fn test_replace_char_at_past_end() {
+    let mut s = CompactString::new("abc");
+    s.replace_char_at(3, 'd');
+}
+
+#[test]
+#[should_panic]
+fn test_replace_char_at_not_char_boundary() {
+    let mut s = CompactString::new("😀");
+    s.replace_char_at(1, 'd');
+}
+
+#[cfg_attr(miri, ignore)]
+#[proptest]
+fn proptest_replace_char_at(#[strategy(rand_unicode())] s: String, ch: char, idx: usize) {
+    prop_assume!(!s.is_empty());
+    let idx = s
+        .char_indices()
+        .map(|(i, _)| i)
+        .nth(idx % s.chars().count())
+        .unwrap();
+
+    let mut control = String::from(&s);
+    let old = control.remove(idx);
+    control.insert(idx, ch);
+
+    let mut compact = CompactString::new(&s);
+    prop_assert_eq!(compact.replace_char_at(idx, ch), old);
+    prop_assert_eq!(compact, control);
+}
+
+#[test]
+fn test_into_chars_outlives_borrow() {
+    fn chars_of(s: &str) -> IntoChars {
+        CompactString::new(s).into_chars()
+    }
+
+    let long = "this is a long string 🌍 that will be on the heap";
+    assert!(chars_of(long).eq(long.chars()));
+    assert!(chars_of("").next().is_none());
+
+    let mut chars = chars_of("a🦀b");
+    assert_eq!(chars.next_back(), Some('b'));
+    assert_eq!(chars.next_back(), Some('🦀'));
+    assert_eq!(chars.next(), Some('a'));
+    assert_eq!(chars.next(), None);
+    assert_eq!(chars.next_back(), None);
+}
+
+#[cfg_attr(miri, ignore)]
+#[proptest]
+fn proptest_into_chars(#[strategy(rand_unicode())] s: String, from_back: Vec<bool>) {
+    let mut control = s.chars();
+    let mut compact = CompactString::new(&s).into_chars();
+    prop_assert_eq!(compact.size_hint(), control.size_hint());
+
+    for back in from_back.iter().copied().chain(core::iter::repeat(false)) {
+        let (a, b) = if back {
+            (compact.next_back(), control.next_back())
+        } else {
+            (compact.next(), control.next())
+        };
+        prop_assert_eq!(a, b);
+        prop_assert_eq!(compact.as_str(), control.as_str());
+        if a.is_none() {
+            break;
+        }
+    }
+}
+
+#[test]
+fn test_into_bytes_iter_outlives_borrow() {
+    fn bytes_of(s: &str) -> IntoBytesIter {
+        CompactString::new(s).into_bytes_iter()
+    }
+
+    let long = "this is a long string 🌍 that will be on the heap";
+    assert!(bytes_of(long).eq(long.bytes()));
+    assert!(bytes_of(long).rev().eq(long.bytes().rev()));
+    assert!(bytes_of("").next().is_none());
+
+    let mut bytes = bytes_of("abcdef");
+    assert_eq!(bytes.nth(1), Some(b'b'));
+    assert_eq!(bytes.next_back(), Some(b'f'));
+    assert_eq!(bytes.len(), 3);
+    assert_eq!(bytes.nth(usize::MAX), None);
+    assert_eq!(bytes.next_back(), None);
+}
+
+// These tests deliberately aren't ignored under Miri, they check the pointer APIs for every kind
+// of string under both Stacked Borrows and Tree Borrows.
+#[test]
+fn test_ptr_accessors() {
+    let short = "hello";
+    let long = "this is a long string that will be on the heap";
+    let cases = [
+        CompactString::new(short),
+        CompactString::new(long),
+        CompactString::const_new(short),
+        CompactString::const_new(long),
+    ];
+
+    for mut compact in cases {
+        let expected = String::from(compact.as_str());
+
+        // `as_ptr` only needs a shared borrow, so it can be used alongside other reads
+        let s = compact.as_str();
+        let ptr = compact.as_ptr();
+        assert_eq!(ptr, s.as_ptr());
+        let bytes = unsafe { slice::from_raw_parts(ptr, compact.len()) };
+        assert_eq!(bytes, s.as_bytes());
+        assert_eq!(bytes, expected.as_bytes());
+
+        // `as_mut_ptr` is valid for writes over the whole capacity
+        let len = compact.len();
+        let ptr = compact.as_mut_ptr();
+        assert!(compact.as_static_str().is_none());
+        let cap = compact.capacity();
+        unsafe {
+            ptr.write(b'J');
+            ptr.add(len).write_bytes(b'!', cap - len);
+            compact.set_len(cap);
+        }
+        assert_eq!(compact.as_ptr(), ptr.cast_const());
+        assert_eq!(&compact[..1], "J");
+        assert_eq!(&compact[1..len], &expected[1..]);
+        assert!(compact[len..].bytes().all(|b| b == b'!'));
+    }
+}
+
+#[test]
+fn test_as_ptr_moves_with_inline_string() {
+    let heap = CompactString::new("this is a long string that will be on the heap");
+    let heap_ptr = heap.as_ptr();
+    let moved = Box::new(heap);
+    assert_eq!(moved.as_ptr(), heap_ptr);
+
+    let inline = CompactString::new("hello");
+    let moved = Box::new(inline.clone());
+    assert_ne!(moved.as_ptr(), inline.as_ptr());
+    let bytes = unsafe { slice::from_raw_parts(moved.as_ptr(), moved.len()) };
+    assert_eq!(bytes, b"hello");
+
+    let static_str: &'static str = "this is a long string that will not be inlined";
+    let moved = Box::new(CompactString::const_new(static_str));
+    assert_eq!(moved.as_ptr(), static_str.as_ptr());
+}
+
+#[cfg_attr(miri, ignore)]
+#[proptest]
+fn proptest_into_char_indices(#[strategy(rand_unicode())] s: String, from_back: Vec<bool>) {
+    let mut control = s.char_indices();
+    let mut compact = CompactString::new(&s).into_char_indices();
+
+    for back in from_back.iter().copied().chain(core::iter::repeat(false)) {
+        let (a, b) = if back {
+            (compact.next_back(), control.next_back())
+        } else {
+            prop_assert_eq!(compact.offset(), control.offset());
+            (compact.next(), control.next())
+        };
+        prop_assert_eq!(a, b);
+        prop_assert_eq!(compact.as_str(), control.as_str());
+        if a.is_none() {
+            break;
+        }
+    }
+}
+
+#[test]
+fn test_try_range_methods() {
+    use core::ops::Bound;
+
+    let mut s = CompactString::new("a🦀b");
+
+    assert_eq!(
+        s.try_replace_range(0..10, "x"),
+        Err(RangeError::OutOfBounds)
+    );
+    let reversed = (Bound::Included(3), Bound::Excluded(1));
+    assert_eq!(
+        s.try_replace_range(reversed, "x"),
+        Err(RangeError::OutOfBounds)
+    );
+    assert_eq!(
+        s.try_replace_range(..=usize::MAX, "x"),
+        Err(RangeError::OutOfBounds)
+    );
+    assert_eq!(
+        s.try_replace_range(0..2, "x"),
+        Err(RangeError::NotCharBoundary)
+    );
+    assert_eq!(s.try_drain(7..).unwrap_err(), RangeError::OutOfBounds);
+    assert_eq!(s.try_drain(2..).unwrap_err(), RangeError::NotCharBoundary);
+    assert_eq!(s.try_truncate(3), Err(RangeError::NotCharBoundary));
+    assert_eq!(s, "a🦀b");
+
+    assert_eq!(s.try_truncate(100), Ok(()));
+    assert_eq!(s.try_drain(1..5).unwrap().collect::<String>(), "🦀");
+    assert_eq!(s, "ab");
+    assert_eq!(s.try_replace_range(1.., "🌍"), Ok(()));
+    assert_eq!(s, "a🌍");
+    assert_eq!(s.try_truncate(1), Ok(()));
+    assert_eq!(s, "a");
+}
+
+#[cfg_attr(miri, ignore)]
+#[proptest]
+fn proptest_try_replace_range(
+    #[strategy(rand_unicode())] s: String,
+    #[strategy(rand_unicode())] replace_with: String,
+    start: u8,
+    end: u8,
+) {
+    let range = usize::from(start)..usize::from(end);
+    let mut compact = CompactString::new(&s);
+    let res = compact.try_replace_range(range.clone(), &replace_with);
+
+    if s.get(range.clone()).is_some() {
+        let mut control = s.clone();
+        control.replace_range(range, &replace_with);
+        prop_assert_eq!(res, Ok(()));
+        prop_assert_eq!(compact, control);
+    } else {
+        prop_assert!(res.is_err());
+        prop_assert_eq!(compact, s);
+    }
+}
+
+#[test]
+fn test_from_char_const() {
+    const CHARS: [CompactString; 4] = [
+        CompactString::from_char('a'),
+        CompactString::from_char('ß'),
+        CompactString::from_char('ℝ'),
+        CompactString::from_char('💣'),
+    ];
+    const TAB: CompactString = CompactString::from_ascii_byte(b'\t');
+
+    assert_eq!(CHARS, ["a", "ß", "ℝ", "💣"]);
+    assert_eq!(TAB, "\t");
+}
+
+#[test]
+#[should_panic(expected = "byte is not ASCII")]
+fn test_from_ascii_byte_non_ascii() {
+    let _ = CompactString::from_ascii_byte(0x80);
+}
+
+#[cfg_attr(miri, ignore)]
+#[proptest]
+fn proptest_from_char(c: char) {
+    let compact = CompactString::from_char(c);
+    let mut buf = [0; 4];
+    prop_assert_eq!(compact.as_str(), c.encode_utf8(&mut buf));
+    prop_assert_eq!(compact.len(), c.len_utf8());
+    prop_assert!(!compact.is_heap_allocated());
+}
+
+#[test]
+fn test_split_byte() {
+    let s = CompactString::new(",a,,bc,");
+    assert_eq!(
+        s.split_byte(b',').collect::<Vec<_>>(),
+        ["", "a", "", "bc", ""]
+    );
+    assert_eq!(
+        s.split_byte(b',').rev().collect::<Vec<_>>(),
+        ["", "bc", "", "a", ""]
+    );
+
+    let mut iter = s.split_byte(b',');
+    assert_eq!(iter.next(), Some(""));
+    assert_eq!(iter.next_back(), Some(""));
+    assert_eq!(iter.next_back(), Some("bc"));
+    assert_eq!(iter.next(), Some("a"));
+    assert_eq!(iter.next(), Some(""));
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next_back(), None);
+
+    let empty = CompactString::new("");
+    assert_eq!(empty.split_byte(b',').collect::<Vec<_>>(), [""]);
+}
+
+#[test]
+#[should_panic(expected = "separator byte must be ASCII")]
+fn test_split_byte_non_ascii() {
+    let s = CompactString::new("🦀");
+    let _ = s.split_byte(0xF0);
+}
+
+#[cfg_attr(miri, ignore)]
+#[proptest]
+fn proptest_find_byte(#[strategy(rand_unicode())] s: String, byte: u8) {
+    let compact = CompactString::new(&s);
+    prop_assert_eq!(compact.find_byte(byte), s.bytes().position(|b| b == byte));
+}
+
+#[cfg_attr(miri, ignore)]
+#[proptest]
+fn proptest_split_byte(#[strategy(rand_unicode())] s: String, #[strategy(0..128_u8)] byte: u8) {
+    let compact = CompactString::new(&s);
+    let control: Vec<&str> = s.split(byte as char).collect();
+    prop_assert_eq!(
+        compact.split_byte(byte).collect::<Vec<_>>(),
+        control.clone()
+    );
+
+    let control_rev: Vec<&str> = s.split(byte as char).rev().collect();
+    prop_assert_eq!(
+        compact.split_byte(byte).rev().collect::<Vec<_>>(),
+        control_rev
+    );
+}
+
+#[test]
+fn test_count_matches_find_iter() {
+    let s = CompactString::new("this is a long string, that's on the heap, with, commas");
+    assert_eq!(s.count_matches(","), 3);
+    assert_eq!(s.count_matches("is"), 2);
+    assert_eq!(s.count_matches("🦀"), 0);
+    assert_eq!(s.find_iter(", ").collect::<Vec<_>>(), [21, 41, 47]);
+
+    // non-overlapping, like `str::matches`
+    let s = CompactString::new("aaaaa");
+    assert_eq!(s.count_matches("aa"), 2);
+    assert_eq!(s.find_iter("aa").collect::<Vec<_>>(), [0, 2]);
+
+    // an empty needle matches at every char boundary
+    let s = CompactString::new("héllo");
+    assert_eq!(s.count_matches(""), 6);
+    assert_eq!(s.find_iter("").collect::<Vec<_>>(), [0, 1, 3, 4, 5, 6]);
+    assert_eq!(
+        CompactString::default().find_iter("").collect::<Vec<_>>(),
+        [0]
+    );
+}
+
+#[cfg_attr(miri, ignore)]
+#[proptest]
+fn proptest_count_matches_find_iter(
+    #[strategy(rand_unicode())] s: String,
+    #[strategy("[ab,🦀]{0,3}")] needle: String,
+) {
+    let compact = CompactString::new(&s);
+    prop_assert_eq!(
+        compact.count_matches(&needle),
+        s.matches(needle.as_str()).count()
+    );
+
+    let control: Vec<usize> = s.match_indices(needle.as_str()).map(|(i, _)| i).collect();
+    prop_assert_eq!(compact.find_iter(&needle).collect::<Vec<_>>(), control);
+}
+
+#[test]
+fn test_reserve_error_context() {
+    let mut s = CompactString::new("this is a long string that will be on the heap");
+    let err = s.try_reserve(usize::MAX).unwrap_err();
+    assert_eq!(err.requested_capacity(), None);
+    assert_eq!(err.current_len(), s.len());
+    assert_eq!(
+        err.to_string(),
+        "Cannot allocate memory to hold CompactString"
+    );
+
+    // the string is left untouched
+    assert_eq!(s, "this is a long string that will be on the heap");
+}
+
+#[cfg(feature = "unicode-case")]
+#[test]
+fn test_make_case_in_place() {
+    let mut s = CompactString::new("HeLLo WoRLD");
+    s.make_lowercase();
+    assert_eq!(s, "hello world");
+    s.make_uppercase();
+    assert_eq!(s, "HELLO WORLD");
+
+    // final sigma
+    let mut s = CompactString::new("ὈΔΥΣΣΕΎΣ ΣΑ");
+    s.make_lowercase();
+    assert_eq!(s, "ὀδυσσεύς σα");
+
+    // shrinks, 'ﬀ' is 3 bytes and "FF" is 2
+    let mut s = CompactString::new("ﬀﬀﬀ");
+    s.make_uppercase();
+    assert_eq!(s, "FFFFFF");
+
+    // grows, 'ΐ' is 2 bytes and uppercases to 6, but the result still fits inline
+    let mut s = CompactString::new("ΐaΐ");
+    s.make_uppercase();
+    assert_eq!(s, "ΐ".to_uppercase() + "A" + &"ΐ".to_uppercase());
+    assert!(!s.is_heap_allocated());
+
+    // grows past the inline capacity
+    let word = "İȿİȿİȿİȿİȿİȿ";
+    let mut s = CompactString::new(word);
+    assert!(!s.is_heap_allocated());
+    s.make_lowercase();
+    assert_eq!(s, word.to_lowercase());
+    assert!(s.is_heap_allocated());
+
+    // a static str gets converted first
+    let mut s = CompactString::const_new("a static str that is too long to be inlined, ȿ");
+    s.make_uppercase();
+    assert_eq!(s, "A STATIC STR THAT IS TOO LONG TO BE INLINED, Ȿ");
+}
+
+#[cfg(feature = "unicode-case")]
+#[cfg_attr(miri, ignore)]
+#[proptest]
+fn proptest_make_case_in_place(#[strategy(rand_unicode())] s: String) {
+    let mut compact = CompactString::new(&s);
+    compact.make_lowercase();
+    prop_assert_eq!(&compact, &s.to_lowercase());
+
+    let mut compact = CompactString::new(&s);
+    compact.make_uppercase();
+    prop_assert_eq!(&compact, &s.to_uppercase());
+}
+
+#[cfg(feature = "unicode-case")]
+#[cfg_attr(miri, ignore)]
+#[proptest]
+fn proptest_make_case_in_place_expanding(#[strategy("[ȿΐİßŉΣﬀa .]{0,80}")] s: String) {
+    // strings that start out inline only move to the heap if they no longer fit, but ones that
+    // start out on the heap stay there, even if they shrink
+    let inline = s.len() <= MAX_SIZE;
+
+    let mut compact = CompactString::new(&s);
+    compact.make_lowercase();
+    prop_assert_eq!(&compact, &s.to_lowercase());
+    if inline {
+        prop_assert_eq!(compact.is_heap_allocated(), compact.len() > MAX_SIZE);
+    }
+
+    let mut compact = CompactString::new(&s);
+    compact.make_uppercase();
+    prop_assert_eq!(&compact, &s.to_uppercase());
+    if inline {
+        prop_assert_eq!(compact.is_heap_allocated(), compact.len() > MAX_SIZE);
+    }
+}
+
+#[test]
+fn test_as_io_slices() {
+    use std::io::{IoSlice, Write};
+
+    let long = CompactString::new("this is a long string that will be on the heap");
+    let short = CompactString::new(" and short");
+    let empty = CompactString::default();
+
+    let mut slices = [IoSlice::new(&[]); 3];
+    let mut n = 0;
+    for s in [&long, &empty, &short] {
+        n += s.as_io_slices(&mut slices[n..]);
+    }
+    assert_eq!(n, 2);
+
+    // `Vec` always writes every slice
+    let mut out = Vec::new();
+    let written = out.write_vectored(&slices[..n]).unwrap();
+    assert_eq!(written, long.len() + short.len());
+    assert_eq!(
+        out,
+        b"this is a long string that will be on the heap and short"
+    );
+
+    assert_eq!(&*long.as_io_slice(), long.as_bytes());
+    assert_eq!(long.as_io_slices(&mut []), 0);
+}
+
+#[test]
+fn test_growth_policy() {
+    let policy = CompactString::growth_policy();
+    assert_eq!(policy.inline_capacity(), MAX_SIZE);
+    assert_eq!(policy.grow(0, 1), policy.min_heap_capacity());
+    assert_eq!(policy.grow(100, 1), 150);
+    assert_eq!(policy.grow(100, 80), 180);
+
+    // pushing one byte at a time follows the policy
+    let mut s = CompactString::default();
+    let mut capacities = vec![s.capacity()];
+    for _ in 0..200 {
+        let (len, cap) = (s.len(), s.capacity());
+        s.push('a');
+        if s.capacity() != cap {
+            assert_eq!(s.capacity(), policy.grow(len, 1));
+            capacities.push(s.capacity());
+        }
+    }
+
+    #[cfg(target_pointer_width = "64")]
+    assert_eq!(capacities, [24, 36, 54, 81, 121, 181, 271]);
+}
+
+#[test]
+fn test_size_class_methods() {
+    let policy = CompactString::growth_policy();
+
+    // capacities that fit inline stay inline
+    let s = CompactString::with_capacity_size_class(MAX_SIZE);
+    assert!(!s.is_heap_allocated());
+    assert_eq!(s.capacity(), MAX_SIZE);
+
+    let s = CompactString::with_capacity_size_class(150);
+    assert!(s.is_heap_allocated());
+    assert_eq!(s.capacity(), policy.size_class(150));
+
+    // reserving rounds up the amortized capacity
+    let mut s = CompactString::from("a".repeat(100));
+    s.shrink_to_fit();
+    s.reserve_size_class(1);
+    assert_eq!(s.capacity(), policy.size_class(policy.grow(100, 1)));
+
+    // enough capacity already, nothing changes
+    let cap = s.capacity();
+    s.reserve_size_class(cap - s.len());
+    assert_eq!(s.capacity(), cap);
+
+    // static strings get copied onto the heap with a rounded capacity
+    let mut s = CompactString::const_new("a &'static str that's too long to be inlined");
+    s.reserve_size_class(10);
+    assert_eq!(s.capacity(), policy.size_class(policy.grow(s.len(), 10)));
+
+    // short strings stay inline
+    let mut s = CompactString::const_new("hello");
+    s.reserve_size_class(1);
+    assert!(!s.is_heap_allocated());
+
+    // plain reserve isn't affected
+    let mut s = CompactString::from("a".repeat(100));
+    s.shrink_to_fit();
+    s.reserve(1);
+    assert_eq!(s.capacity(), policy.grow(100, 1));
+
+    assert!(s.try_reserve_size_class(usize::MAX).is_err());
+}
+
+#[test]
+fn test_try_push_try_clone() {
+    let mut s = CompactString::const_new("hello");
+    s.try_push(' ').unwrap();
+    s.try_push_str("world").unwrap();
+    assert_eq!(s, "hello world");
+    assert!(!s.is_heap_allocated());
+
+    let clone = s.try_clone().unwrap();
+    assert_eq!(clone, s);
+    assert!(!clone.is_heap_allocated());
+
+    s.try_push_str(", this is now long enough to spill onto the heap")
+        .unwrap();
+    s.try_push('🦀').unwrap();
+    s.try_push_str("").unwrap();
+    assert_eq!(
+        s,
+        "hello world, this is now long enough to spill onto the heap🦀"
+    );
+    assert!(s.is_heap_allocated());
+
+    let clone = s.try_clone().unwrap();
+    assert_eq!(clone, s);
+    assert!(clone.is_heap_allocated());
+    assert_ne!(clone.as_ptr(), s.as_ptr());
+
+    let s = CompactString::const_new("a static string that is longer than MAX_SIZE");
+    let clone = s.try_clone().unwrap();
+    assert_eq!(clone.as_static_str(), s.as_static_str());
+}
+
+#[cfg_attr(miri, ignore)]
+#[proptest]
+fn proptest_try_push_str(
+    #[strategy(rand_unicode())] a: String,
+    #[strategy(rand_unicode())] b: String,
+) {
+    let mut compact = CompactString::new(&a);
+    compact.try_push_str(&b).unwrap();
+
+    let mut control = a.clone();
+    control.push_str(&b);
+    prop_assert_eq!(&compact, &control);
+    prop_assert_eq!(compact.try_clone().unwrap(), control);
+}
+
+#[test]
+fn test_filled() {
+    let empty = CompactString::filled('x', 0);
+    assert_eq!(empty, "");
+    assert!(!empty.is_heap_allocated());
+
+    let inline = CompactString::filled('0', MAX_SIZE);
+    assert_eq!(inline, "0".repeat(MAX_SIZE));
+    assert!(!inline.is_heap_allocated());
+
+    let heap = CompactString::filled('0', MAX_SIZE + 1);
+    assert_eq!(heap, "0".repeat(MAX_SIZE + 1));
+    assert!(heap.is_heap_allocated());
+    assert!(heap.capacity() >= heap.len());
+
+    let multi = CompactString::filled('é', 3);
+    assert_eq!(multi, "ééé");
+    assert!(!multi.is_heap_allocated());
+
+    assert!(CompactString::try_filled('🦀', usize::MAX / 2).is_err());
+}
+
+#[cfg_attr(miri, ignore)]
+#[proptest]
+fn proptest_filled(ch: char, #[strategy(0..100usize)] count: usize) {
+    let compact = CompactString::filled(ch, count);
+    let control = ch.to_string().repeat(count);
+    prop_assert_eq!(&compact, &control);
+    prop_assert_eq!(compact.is_heap_allocated(), control.len() > MAX_SIZE);
+}
+
+#[test]
+fn test_extend_cow_lazily() {
+    let mut cow = Cow::Borrowed("hello");
+    cow.extend(core::iter::empty::<CompactString>());
+    cow.extend([CompactString::default(), CompactString::const_new("")]);
+    assert!(matches!(cow, Cow::Borrowed("hello")));
+
+    cow.extend([CompactString::default(), CompactString::new(" world")]);
+    assert!(matches!(&cow, Cow::Owned(s) if s == "hello world"));
+
+    cow.extend([
+        CompactString::new("!"),
+        CompactString::default(),
+        CompactString::new("this is a long string that is heap allocated"),
+    ]);
+    assert_eq!(
+        cow,
+        "hello world!this is a long string that is heap allocated"
+    );
+}
+
+#[test]
+fn test_spare_capacity_would_reallocate() {
+    let mut inline = CompactString::new("hello");
+    assert_eq!(inline.spare_capacity(), MAX_SIZE - 5);
+    assert!(!inline.would_reallocate(0));
+    assert!(!inline.would_reallocate(MAX_SIZE - 5));
+    assert!(inline.would_reallocate(MAX_SIZE - 4));
+    assert!(inline.would_reallocate(usize::MAX));
+
+    inline.push_str(&"a".repeat(MAX_SIZE - 5));
+    assert!(!inline.is_heap_allocated());
+    assert_eq!(inline.spare_capacity(), 0);
+
+    let mut heap = CompactString::with_capacity(64);
+    heap.push_str("hello");
+    assert_eq!(heap.spare_capacity(), 59);
+    assert!(!heap.would_reallocate(59));
+    assert!(heap.would_reallocate(60));
+
+    let ptr = heap.as_ptr();
+    heap.push_str(&"a".repeat(59));
+    assert_eq!(heap.as_ptr(), ptr);
+    assert_eq!(heap.spare_capacity(), 0);
+    assert!(!heap.would_reallocate(0));
+    assert!(heap.would_reallocate(1));
+
+    // a heap allocated string with a small capacity can't grow without the allocator, even if the
+    // result would fit inline
+    let small = CompactString::from_string_buffer(String::from("abc"));
+    assert!(small.is_heap_allocated());
+    assert_eq!(small.would_reallocate(1), small.spare_capacity() == 0);
+
+    let long_static = CompactString::const_new("a static string that is longer than MAX_SIZE");
+    assert_eq!(long_static.spare_capacity(), 0);
+    assert!(!long_static.would_reallocate(0));
+    assert!(long_static.would_reallocate(1));
+}
+
+#[test]
+fn test_bom_constructors() {
+    assert_eq!(CompactString::from_utf8_bom(b"").unwrap(), "");
+    assert_eq!(CompactString::from_utf8_bom(b"\xEF\xBB\xBF").unwrap(), "");
+    // only one byte order mark gets stripped
+    assert_eq!(
+        CompactString::from_utf8_bom("\u{feff}\u{feff}🦀".as_bytes()).unwrap(),
+        "\u{feff}🦀"
+    );
+    // an incomplete byte order mark is invalid UTF-8
+    assert!(CompactString::from_utf8_bom(b"\xEF\xBBhello").is_err());
+
+    let text = "this is a long string 🦀 that will be on the heap";
+    let utf16: Vec<u16> = text.encode_utf16().collect();
+    let le: Vec<u8> = [0xFF, 0xFE]
+        .into_iter()
+        .chain(utf16.iter().flat_map(|c| c.to_le_bytes()))
+        .collect();
+    let be: Vec<u8> = [0xFE, 0xFF]
+        .into_iter()
+        .chain(utf16.iter().flat_map(|c| c.to_be_bytes()))
+        .collect();
+    assert_eq!(CompactString::from_utf16_auto(&le).unwrap(), text);
+    assert_eq!(CompactString::from_utf16_auto(&be).unwrap(), text);
+    assert_eq!(CompactString::from_utf16_auto(&be[2..]).unwrap(), text);
+
+    assert_eq!(CompactString::from_utf16_auto(b"").unwrap(), "");
+    assert_eq!(CompactString::from_utf16_auto(b"\xFF\xFE").unwrap(), "");
+    assert!(CompactString::from_utf16_auto(b"\xFF\xFEh").is_err());
+    assert!(CompactString::from_utf16_auto(b"\xFE\xFF\xD8\x3D").is_err());
+}
+
+#[test]
+fn test_try_into_char_and_array() {
+    assert_eq!(char::try_from(&CompactString::new("🦀")), Ok('🦀'));
+    assert_eq!(char::try_from(CompactString::new(",")), Ok(','));
+    assert!(char::try_from(&CompactString::new("")).is_err());
+    assert!(char::try_from(&CompactString::new("ab")).is_err());
+
+    let empty: [u8; 0] = CompactString::new("").try_into().unwrap();
+    assert_eq!(empty, [0u8; 0]);
+
+    let long = "this is a long string that will be on the heap";
+    let array: [u8; 46] = CompactString::new(long).try_into().unwrap();
+    assert_eq!(&array, long.as_bytes());
+
+    let err = <[u8; 45]>::try_from(CompactString::new(long)).unwrap_err();
+    assert_eq!(err.actual_len(), 46);
+    assert_eq!(err.expected_len(), 45);
+    assert_eq!(err.into_compact_string(), long);
+}
+
+#[test]
+fn test_fmt_writer_by_value() {
+    use core::fmt::Write;
+
+    fn render<W: Write>(mut w: W, name: &str) -> core::fmt::Result {
+        write!(w, "{name:<8}|{:^7}|{:>6.2}", "mid", 1.5f32)
+    }
+
+    struct Holder {
+        buf: CompactString,
+    }
+
+    let mut holder = Holder {
+        buf: CompactString::default(),
+    };
+    render(holder.buf.as_fmt_writer(), "ab").unwrap();
+    assert_eq!(holder.buf, "ab      |  mid  |  1.50");
+
+    // `&mut CompactString` is itself a writer, and writes continue where they left off
+    render(&mut holder.buf, "this name is long").unwrap();
+    assert_eq!(
+        holder.buf,
+        "ab      |  mid  |  1.50this name is long|  mid  |  1.50"
+    );
+    assert!(holder.buf.is_heap_allocated());
+}
+
+#[test]
+fn test_string_trait_surface() {
+    fn checksum<B: AsRef<[u8]>>(bytes: B) -> u32 {
+        bytes.as_ref().iter().map(|&b| u32::from(b)).sum()
+    }
+
+    fn shout<S: AsMut<str>>(mut s: S) -> S {
+        s.as_mut().make_ascii_uppercase();
+        s
+    }
+
+    for text in ["hello", "this is a long string that will be on the heap"] {
+        let compact = CompactString::new(text);
+        assert_eq!(checksum(&compact), checksum(String::from(text)));
+        assert_eq!(checksum(compact.clone()), checksum(text));
+
+        let control = shout(String::from(text));
+        assert_eq!(shout(compact.clone()), control);
+        let mut compact = compact;
+        shout(&mut compact);
+        assert_eq!(compact, control);
+    }
+}
+
+#[test]
+fn test_as_inline_bytes() {
+    for len in 0..=MAX_SIZE {
+        let text = "a".repeat(len);
+        let compact = CompactString::new(&text);
+
+        let (buf, inline_len) = compact.as_inline_bytes().unwrap();
+        assert_eq!(inline_len, len);
+        assert_eq!(&buf[..inline_len], text.as_bytes());
+        assert_eq!(buf.as_ptr(), compact.as_ptr());
+
+        let (buf, inline_len) = unsafe { compact.as_inline_bytes_unchecked() };
+        assert_eq!(&buf[..inline_len], text.as_bytes());
+    }
+
+    let heap = CompactString::new("a".repeat(MAX_SIZE + 1));
+    assert!(heap.as_inline_bytes().is_none());
+
+    // a `&'static str` isn't heap allocated, but it isn't inline either
+    let stat = CompactString::const_new("a static string that is too long to be inlined");
+    assert!(!stat.is_heap_allocated());
+    assert!(stat.as_inline_bytes().is_none());
+}
+
+#[cfg_attr(miri, ignore)]
+#[proptest]
+fn proptest_as_inline_bytes(#[strategy(rand_unicode())] word: String) {
+    let compact = CompactString::new(&word);
+    match compact.as_inline_bytes() {
+        Some((buf, len)) => {
+            prop_assert!(word.len() <= MAX_SIZE);
+            prop_assert_eq!(&buf[..len], word.as_bytes());
+        }
+        None => prop_assert!(compact.is_heap_allocated()),
+    }
+}
+
+#[test]
+fn test_from_matrix() {
+    use alloc::rc::Rc;
+    use alloc::sync::Arc;
+
+    for text in ["hello", "this is a long string that will be on the heap"] {
+        let inline = text.len() <= MAX_SIZE;
+
+        let mut owned = String::from(text);
+        let compact = CompactString::from(owned.as_mut_str());
+        assert_eq!(compact, text);
+        assert_eq!(compact.is_heap_allocated(), !inline);
+
+        let boxed: Box<str> = Box::from(text);
+        let compact = CompactString::from(&boxed);
+        assert_eq!(compact, text);
+        assert_eq!(compact.is_heap_allocated(), !inline);
+
+        let shared = Rc::new(String::from(text));
+        let compact = CompactString::from(Rc::clone(&shared));
+        assert_eq!(compact, text);
+        assert_eq!(compact.is_heap_allocated(), !inline);
+        assert_ne!(compact.as_ptr(), shared.as_ptr());
+
+        let compact = CompactString::from(Rc::<str>::from(text));
+        assert_eq!(compact, text);
+        assert_eq!(compact.is_heap_allocated(), !inline);
+
+        let compact = CompactString::from(Arc::<str>::from(text));
+        assert_eq!(compact, text);
+        assert_eq!(compact.is_heap_allocated(), !inline);
+    }
+}
+
+#[test]
+fn test_stress_share_across_threads() {
+    use std::sync::{mpsc, Arc, Barrier};
+    use std::thread;
+
+    const THREADS: usize = 8;
+    let iterations = if cfg!(miri) { 4 } else { 2_000 };
+
+    // a mix of every representation: inline, heap allocated, and a `&'static str`
+    let shared: Arc<Vec<CompactString>> = Arc::new(vec![
+        CompactString::const_new(""),
+        CompactString::new("hello"),
+        CompactString::new("a".repeat(MAX_SIZE)),
+        CompactString::new("this is a long string that will be on the heap"),
+        CompactString::const_new("a static string that is too long to be inlined"),
+    ]);
+    let barrier = Arc::new(Barrier::new(THREADS));
+    let (tx, rx) = mpsc::channel::<CompactString>();
+
+    // drop strings on a different thread than the one that allocated them
+    let consumer = thread::spawn(move || {
+        let mut total = 0;
+        for s in rx {
+            assert!(s.starts_with(|c: char| c.is_ascii_digit()));
+            total += 1;
+        }
+        total
+    });
+
+    let workers: Vec<_> = (0..THREADS)
+        .map(|id| {
+            let shared = Arc::clone(&shared);
+            let barrier = Arc::clone(&barrier);
+            let tx = tx.clone();
+            thread::spawn(move || {
+                barrier.wait();
+                for i in 0..iterations {
+                    let original = &shared[i % shared.len()];
+                    let mut s = original.clone();
+                    assert_eq!(s, *original);
+
+                    s.insert_str(0, &format!("{id}:{i}:"));
+                    s.push_str(original);
+                    if i % 3 == 0 {
+                        s.shrink_to_fit();
+                    }
+                    if i % 2 == 0 {
+                        tx.send(s).unwrap();
+                    }
+                }
+            })
+        })
+        .collect();
+    drop(tx);
+
+    for worker in workers {
+        worker.join().unwrap();
+    }
+    assert_eq!(consumer.join().unwrap(), THREADS * iterations.div_ceil(2));
+
+    // nothing shared was modified
+    assert_eq!(shared[1], "hello");
+    assert_eq!(shared[3], "this is a long string that will be on the heap");
+
+    // a `Drain` can be handed to another thread, while the string stays borrowed
+    let mut s = CompactString::new("this is a long string that will be on the heap");
+    let drained = thread::scope(|scope| {
+        let drain = s.drain(10..);
+        scope
+            .spawn(move || drain.collect::<String>())
+            .join()
+            .unwrap()
+    });
+    assert_eq!(s, "this is a ");
+    assert_eq!(drained, "long string that will be on the heap");
+}
+
+#[test_case("" ; "empty")]
+#[test_case("hello" ; "ascii")]
+#[test_case("tab\there\n\"quoted\"" ; "control and quotes")]
+#[test_case("a\u{301}\u{200b}🦀\0" ; "combining and invisible")]
+#[test_case("this is a long string 🌍 that will be on the heap" ; "long")]
+fn test_escape_compact(control: &str) {
+    let compact = CompactString::new(control);
+
+    let cases = [
+        (
+            compact.escape_debug_compact(),
+            control.escape_debug().to_string(),
+        ),
+        (
+            compact.escape_default_compact(),
+            control.escape_default().to_string(),
+        ),
+        (
+            compact.escape_unicode_compact(),
+            control.escape_unicode().to_string(),
+        ),
+    ];
+    for (escaped, expected) in cases {
+        assert_eq!(escaped, expected);
+        assert_eq!(escaped.is_heap_allocated(), expected.len() > MAX_SIZE);
+        assert_eq!(
+            escaped.capacity(),
+            CompactString::with_capacity(expected.len()).capacity()
+        );
+    }
+}
+
+#[cfg_attr(miri, ignore)]
+#[proptest]
+fn proptest_escape_compact(#[strategy(rand_unicode())] control: String) {
+    let compact = CompactString::new(&control);
+    prop_assert_eq!(
+        compact.escape_debug_compact(),
+        control.escape_debug().to_string()
+    );
+    prop_assert_eq!(
+        compact.escape_default_compact(),
+        control.escape_default().to_string()
+    );
+}
+
+#[test]
+fn test_write_fmt_many_small_writes() {
+    use core::fmt::Write;
+
+    let starts = [
+        CompactString::default(),
+        CompactString::new("abc"),
+        CompactString::new("a".repeat(MAX_SIZE - 1)),
+        CompactString::new("this is a long string that will be on the heap"),
+        CompactString::const_new("a static string that is too long to be inlined"),
+    ];
+    for start in starts {
+        for width in [0, 1, MAX_SIZE - 4, MAX_SIZE, 100] {
+            let mut compact = start.clone();
+            let mut control = String::from(start.as_str());
+
+            write!(
+                compact,
+                "[{:>width$}|{:^width$.2}|{:<width$}]",
+                42, 1.5, "🦀"
+            )
+            .unwrap();
+            write!(
+                control,
+                "[{:>width$}|{:^width$.2}|{:<width$}]",
+                42, 1.5, "🦀"
+            )
+            .unwrap();
+            assert_eq!(compact, control);
+            assert_eq!(compact.len(), control.len());
+            assert_eq!(compact.is_heap_allocated(), control.len() > MAX_SIZE);
+        }
+    }
+}
+
+#[test]
+fn test_write_fmt_panic_keeps_written_prefix() {
+    use core::fmt::{self, Write};
+
+    struct Bomb;
+    impl fmt::Display for Bomb {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("boom")?;
+            panic!("bomb went off");
+        }
+    }
+
+    for start in ["", "this is a long string that will be on the heap"] {
+        let mut compact = CompactString::new(start);
+        let res = std::panic::catch_unwind(core::panic::AssertUnwindSafe(|| {
+            let _ = write!(compact, "{}-{}", 1, Bomb);
+        }));
+        assert!(res.is_err());
+        assert_eq!(compact, format!("{start}1-boom"));
+    }
+}
+
+#[cfg_attr(miri, ignore)]
+#[proptest]
+fn proptest_write_fmt(
+    #[strategy(rand_unicode())] start: String,
+    #[strategy(rand_unicode())] arg: String,
+    #[strategy(0..64usize)] width: usize,
+) {
+    use core::fmt::Write;
+
+    let mut compact = CompactString::new(&start);
+    let mut control = start.clone();
+    write!(compact, "{arg:>width$}{}{arg:?}", width).unwrap();
+    write!(control, "{arg:>width$}{}{arg:?}", width).unwrap();
+    prop_assert_eq!(compact, control);
+}
+
 #[test]
 fn test_from_string_buffer_inlines_on_push() {
     let mut compact = CompactString::from_string_buffer("hello".to_string());
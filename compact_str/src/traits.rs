@@ -1,3 +1,5 @@
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
 use alloc::string::String;
 use core::fmt::{self, Write};
 use core::num;
@@ -108,6 +110,17 @@ impl<T: fmt::Display> ToCompactString for T {
             &num::NonZeroU128 as s => s.into_repr()?,
             &num::NonZeroI128 as s => s.into_repr()?,
             s => {
+                #[cfg(feature = "format-scratch")]
+                {
+                    let scratch = crate::features::format_scratch::with_scratch(|scratch| {
+                        write!(scratch, "{}", s)?;
+                        Ok(CompactString::try_new(&*scratch)?)
+                    });
+                    if let Some(result) = scratch {
+                        return result;
+                    }
+                }
+
                 let mut c = CompactString::const_new("");
                 write!(c, "{}", s)?;
                 return Ok(c);
@@ -118,6 +131,219 @@ impl<T: fmt::Display> ToCompactString for T {
     }
 }
 
+/// Converts any value that implements [`fmt::Display`] into a [`CompactString`].
+///
+/// This is the same as calling [`ToCompactString::to_compact_string()`], including its fast paths
+/// for integers, floats, and strings, but as a free function it can be passed directly to
+/// iterator adapters.
+///
+/// # Panics
+///
+/// Panics in the same cases as [`ToCompactString::to_compact_string()`].
+///
+/// # Examples
+///
+/// ```
+/// use compact_str::CompactString;
+///
+/// let ids: Vec<CompactString> = [1, 22, 333].into_iter().map(compact_str::from_display).collect();
+/// assert_eq!(ids, ["1", "22", "333"]);
+///
+/// // references work too, since `&T: Display` whenever `T: Display`
+/// let words = ["hello", "world"];
+/// let words: Vec<CompactString> = words.iter().map(compact_str::from_display).collect();
+/// assert_eq!(words, ["hello", "world"]);
+/// ```
+#[inline]
+#[track_caller]
+pub fn from_display<T: fmt::Display>(value: T) -> CompactString {
+    value.to_compact_string()
+}
+
+/// A trait for formatting integers as a [`CompactString`] in a given radix.
+///
+/// Digits are written straight into a stack buffer and then into the [`CompactString`], so short
+/// results are inlined without going through the [`fmt`] machinery.
+///
+/// Negative numbers are formatted as a `-` followed by their magnitude, so the output roundtrips
+/// with `from_str_radix`. Note: this differs from `format!("{:x}", n)` which prints the two's
+/// complement representation of negative numbers.
+///
+/// # Examples
+/// ```
+/// use compact_str::ToCompactStringRadix;
+///
+/// assert_eq!(255u8.to_compact_string_radix(16), "ff");
+/// assert_eq!((-10i32).to_compact_string_radix(2), "-1010");
+/// assert_eq!(0xdead_beef_u64.to_compact_string_hex(), "deadbeef");
+/// assert_eq!(5u8.to_compact_string_binary(), "101");
+/// ```
+pub trait ToCompactStringRadix {
+    /// Formats the integer as a [`CompactString`] in the given `radix`, using lowercase letters
+    /// for digits above 9.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `radix` is not in the range `2..=36`.
+    fn to_compact_string_radix(&self, radix: u32) -> CompactString;
+
+    /// Formats the integer as lowercase hexadecimal, equivalent to `to_compact_string_radix(16)`
+    #[inline]
+    fn to_compact_string_hex(&self) -> CompactString {
+        self.to_compact_string_radix(16)
+    }
+
+    /// Formats the integer as binary, equivalent to `to_compact_string_radix(2)`
+    #[inline]
+    fn to_compact_string_binary(&self) -> CompactString {
+        self.to_compact_string_radix(2)
+    }
+}
+
+macro_rules! format_radix {
+    ($($name:ident: $m:ty),*) => {
+        $(
+            /// Formats `magnitude` in `radix`, prefixed with a `-` if `negative`
+            #[track_caller]
+            fn $name(mut magnitude: $m, negative: bool, radix: u32) -> CompactString {
+                assert!(
+                    (2..=36).contains(&radix),
+                    "radix must be in the range 2..=36, got {}",
+                    radix
+                );
+                const DIGITS: &[u8; 36] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+                // enough space for the largest magnitude in binary, plus a sign
+                let mut buf = [0u8; <$m>::BITS as usize + 1];
+                let mut pos = buf.len();
+                let radix = radix as $m;
+
+                loop {
+                    pos -= 1;
+                    buf[pos] = DIGITS[(magnitude % radix) as usize];
+                    magnitude /= radix;
+                    if magnitude == 0 {
+                        break;
+                    }
+                }
+                if negative {
+                    pos -= 1;
+                    buf[pos] = b'-';
+                }
+
+                // SAFETY: We only wrote ASCII digits and `-` into the buffer
+                let s = unsafe { core::str::from_utf8_unchecked(&buf[pos..]) };
+                CompactString::new(s)
+            }
+        )*
+    };
+}
+
+// 128-bit division is a lot slower than 64-bit division, so only 128-bit types use it
+format_radix!(format_radix_u64: u64, format_radix_u128: u128);
+
+macro_rules! impl_to_compact_string_radix {
+    ($format:ident as $m:ty; unsigned: $($t:ty),*; signed: $($s:ty),*) => {
+        $(
+            impl ToCompactStringRadix for $t {
+                #[inline]
+                #[track_caller]
+                fn to_compact_string_radix(&self, radix: u32) -> CompactString {
+                    $format(*self as $m, false, radix)
+                }
+            }
+        )*
+        $(
+            impl ToCompactStringRadix for $s {
+                #[inline]
+                #[track_caller]
+                fn to_compact_string_radix(&self, radix: u32) -> CompactString {
+                    $format(self.unsigned_abs() as $m, *self < 0, radix)
+                }
+            }
+        )*
+    };
+}
+
+impl_to_compact_string_radix!(
+    format_radix_u64 as u64;
+    unsigned: u8, u16, u32, u64, usize;
+    signed: i8, i16, i32, i64, isize
+);
+impl_to_compact_string_radix!(
+    format_radix_u128 as u128;
+    unsigned: u128;
+    signed: i128
+);
+
+/// A trait for formatting integers as a [`CompactString`] padded to a minimum width.
+///
+/// Digits are written with [`itoa`] and the padding is pushed straight into a [`CompactString`]
+/// with exactly the required capacity, so short results are inlined without going through the
+/// [`fmt`] machinery.
+///
+/// # Examples
+/// ```
+/// use compact_str::ToCompactStringPadded;
+///
+/// assert_eq!(42u32.to_compact_string_padded(6, '0'), "000042");
+/// assert_eq!((-42i32).to_compact_string_padded(6, '0'), "-00042");
+/// assert_eq!((-42i32).to_compact_string_padded(6, ' '), "   -42");
+/// assert_eq!(1234567u32.to_compact_string_padded(6, '0'), "1234567");
+/// ```
+pub trait ToCompactStringPadded {
+    /// Formats the integer in decimal as a [`CompactString`], with `fill` prepended until the
+    /// result is at least `width` [`char`]s long.
+    ///
+    /// With a `fill` of `'0'` a `-` sign comes before the padding, like `format!("{:0width$}")`.
+    /// Any other `fill` goes before the sign, like `format!("{:>width$}")`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of the result overflows `usize` or if the system is out-of-memory.
+    fn to_compact_string_padded(&self, width: usize, fill: char) -> CompactString;
+}
+
+/// Pads the decimal `digits`, including any leading `-`, to `width` chars with `fill`
+#[track_caller]
+fn format_padded(digits: &str, width: usize, fill: char) -> CompactString {
+    let pad = width.saturating_sub(digits.len());
+    let len = pad
+        .checked_mul(fill.len_utf8())
+        .and_then(|pad_len| pad_len.checked_add(digits.len()))
+        .unwrap_or(usize::MAX);
+    let mut s = CompactString::with_capacity(len);
+
+    let digits = match digits.strip_prefix('-') {
+        Some(magnitude) if fill == '0' => {
+            s.push('-');
+            magnitude
+        }
+        _ => digits,
+    };
+    for _ in 0..pad {
+        s.push(fill);
+    }
+    s.push_str(digits);
+    s
+}
+
+macro_rules! impl_to_compact_string_padded {
+    ($($t:ty),*) => {
+        $(
+            impl ToCompactStringPadded for $t {
+                #[inline]
+                #[track_caller]
+                fn to_compact_string_padded(&self, width: usize, fill: char) -> CompactString {
+                    format_padded(itoa::Buffer::new().format(*self), width, fill)
+                }
+            }
+        )*
+    };
+}
+
+impl_to_compact_string_padded!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
 /// A trait that provides convenience methods for creating a [`CompactString`] from a collection of
 /// items. It is implemented for all types that can be converted into an iterator, and that iterator
 /// yields types that can be converted into a `str`.
@@ -166,6 +392,71 @@ pub trait CompactStringExt {
     /// assert_eq!(compact, "apples, oranges, bananas");
     /// ```
     fn join_compact<S: AsRef<str>>(&self, separator: S) -> CompactString;
+
+    /// Returns an adaptor that joins all the items of a collection with a separator when it's
+    /// displayed, without building a string up front.
+    ///
+    /// Use it to stream the joined items into a formatter, or call
+    /// [`DisplayJoin::to_compact_string()`] to materialize it, which behaves the same as
+    /// [`CompactStringExt::join_compact()`].
+    ///
+    /// # Example
+    /// ```
+    /// use compact_str::CompactStringExt;
+    ///
+    /// let fruits = ["apples", "oranges", "bananas"];
+    /// let joined = fruits.display_join(", ");
+    ///
+    /// assert_eq!(format!("fruits: {}", joined), "fruits: apples, oranges, bananas");
+    /// assert_eq!(joined.to_compact_string(), "apples, oranges, bananas");
+    /// ```
+    fn display_join<S: AsRef<str>>(&self, separator: S) -> DisplayJoin<'_, Self, S> {
+        DisplayJoin {
+            items: self,
+            separator,
+        }
+    }
+}
+
+/// A [`fmt::Display`] adaptor that joins the items of a collection with a separator, created by
+/// [`CompactStringExt::display_join()`].
+///
+/// Note: the items are written as-is, formatting options like width and padding are ignored.
+#[derive(Clone, Copy, Debug)]
+pub struct DisplayJoin<'a, C: ?Sized, S> {
+    items: &'a C,
+    separator: S,
+}
+
+impl<C: ?Sized + CompactStringExt, S: AsRef<str>> DisplayJoin<'_, C, S> {
+    /// Joins the items into a [`CompactString`], allocating at most once.
+    ///
+    /// This shadows [`ToCompactString::to_compact_string()`], which would go through the
+    /// formatting machinery.
+    #[inline]
+    pub fn to_compact_string(&self) -> CompactString {
+        self.items.join_compact(self.separator.as_ref())
+    }
+}
+
+impl<I, C, S> fmt::Display for DisplayJoin<'_, C, S>
+where
+    I: AsRef<str>,
+    C: ?Sized,
+    for<'a> &'a C: IntoIterator<Item = &'a I>,
+    S: AsRef<str>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut iter = self.items.into_iter();
+        if let Some(first) = iter.next() {
+            f.write_str(first.as_ref())?;
+            for item in iter {
+                f.write_str(self.separator.as_ref())?;
+                f.write_str(item.as_ref())?;
+            }
+        }
+        Ok(())
+    }
 }
 
 impl<I, C> CompactStringExt for C
@@ -174,18 +465,32 @@ where
     for<'a> &'a C: IntoIterator<Item = &'a I>,
 {
     fn concat_compact(&self) -> CompactString {
+        // Sum the lengths of all the items up front, so we only need to allocate once
+        let len = self
+            .into_iter()
+            .fold(0usize, |len, item| len.saturating_add(item.as_ref().len()));
+
         self.into_iter()
-            .fold(CompactString::const_new(""), |mut s, item| {
+            .fold(CompactString::with_capacity(len), |mut s, item| {
                 s.push_str(item.as_ref());
                 s
             })
     }
 
     fn join_compact<S: AsRef<str>>(&self, separator: S) -> CompactString {
-        let mut compact_string = CompactString::const_new("");
+        let sep = separator.as_ref();
+
+        // Sum the lengths of all the items and separators up front, so we only need to allocate
+        // once
+        let (count, len) = self
+            .into_iter()
+            .fold((0usize, 0usize), |(count, len), item| {
+                (count + 1, len.saturating_add(item.as_ref().len()))
+            });
+        let len = len.saturating_add(sep.len().saturating_mul(count.saturating_sub(1)));
 
+        let mut compact_string = CompactString::with_capacity(len);
         let mut iter = self.into_iter().peekable();
-        let sep = separator.as_ref();
 
         while let Some(item) = iter.next() {
             compact_string.push_str(item.as_ref());
@@ -198,6 +503,166 @@ where
     }
 }
 
+/// An iterator adaptor for collecting strings or [`char`]s into a [`CompactString`] of at most
+/// `max_bytes` bytes.
+///
+/// Collecting stops as soon as the limit is hit, cutting the item that crossed it at the last
+/// [`char`] boundary that fits, so an oversized string is never built just to be truncated. The
+/// returned `bool` is `true` if anything was cut off.
+///
+/// It's implemented for iterators over [`char`], `&str`, [`String`], [`Box<str>`], [`Cow<str>`],
+/// and [`CompactString`].
+///
+/// # Examples
+/// ```
+/// use compact_str::CollectTruncated;
+///
+/// let (s, truncated) = ["hello", " ", "wörld"].into_iter().collect_compact_truncated(8);
+/// assert_eq!(s, "hello w");
+/// assert!(truncated);
+///
+/// let (s, truncated) = "short".chars().collect_compact_truncated(8);
+/// assert_eq!(s, "short");
+/// assert!(!truncated);
+/// ```
+pub trait CollectTruncated<T>: Iterator<Item = T> {
+    /// Collects the items into a [`CompactString`] of at most `max_bytes` bytes, and whether the
+    /// items were truncated to fit.
+    fn collect_compact_truncated(self, max_bytes: usize) -> (CompactString, bool);
+}
+
+impl<I: Iterator<Item = char>> CollectTruncated<char> for I {
+    fn collect_compact_truncated(self, max_bytes: usize) -> (CompactString, bool) {
+        let mut out = CompactString::default();
+        for c in self {
+            if out.len() + c.len_utf8() > max_bytes {
+                return (out, true);
+            }
+            out.push(c);
+        }
+        (out, false)
+    }
+}
+
+macro_rules! impl_collect_truncated {
+    ($($ty:ty),*) => {
+        $(
+            impl<'a, I: Iterator<Item = $ty>> CollectTruncated<$ty> for I {
+                #[inline]
+                fn collect_compact_truncated(self, max_bytes: usize) -> (CompactString, bool) {
+                    collect_truncated(self, max_bytes)
+                }
+            }
+        )*
+    };
+}
+
+impl_collect_truncated!(&'a str, String, Box<str>, Cow<'a, str>, CompactString);
+
+fn collect_truncated<I, S>(iter: I, max_bytes: usize) -> (CompactString, bool)
+where
+    I: Iterator<Item = S>,
+    S: AsRef<str>,
+{
+    let mut out = CompactString::default();
+    for item in iter {
+        let item = item.as_ref();
+        let remaining = max_bytes - out.len();
+        if item.len() <= remaining {
+            out.push_str(item);
+        } else {
+            let mut end = remaining;
+            while !item.is_char_boundary(end) {
+                end -= 1;
+            }
+            out.push_str(&item[..end]);
+            return (out, true);
+        }
+    }
+    (out, false)
+}
+
+/// A trait for values that can be cheaply viewed as a [`CompactString`], for use in function
+/// signatures that want to accept any kind of string without forcing an allocation.
+///
+/// A [`CompactString`] is borrowed as is, while any other string gets copied into a new
+/// [`CompactString`], which is free for strings short enough to be inlined. The trait is sealed,
+/// it's implemented for [`str`], [`String`], [`Box<str>`], [`Cow<str>`], [`CompactString`],
+/// `Cow<CompactString>`, and references to any of them.
+///
+/// # Examples
+/// ```
+/// use std::borrow::Cow;
+///
+/// use compact_str::{AsCompactStr, CompactString};
+///
+/// fn shout(name: impl AsCompactStr) -> CompactString {
+///     let mut name = name.to_compact().into_owned();
+///     name.make_ascii_uppercase();
+///     name
+/// }
+///
+/// assert_eq!(shout("alice"), "ALICE");
+/// assert_eq!(shout(String::from("bob")), "BOB");
+/// assert_eq!(shout(CompactString::new("carol")), "CAROL");
+///
+/// let long = CompactString::new("a long name that will need to be on the heap");
+/// assert!(matches!(long.to_compact(), Cow::Borrowed(_)));
+/// ```
+pub trait AsCompactStr: sealed::Sealed {
+    /// Returns a [`CompactString`] view of the value, borrowed if the value already is one.
+    fn to_compact(&self) -> Cow<'_, CompactString>;
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+macro_rules! impl_as_compact_str {
+    ($($ty:ty),*) => {
+        $(
+            impl sealed::Sealed for $ty {}
+
+            impl AsCompactStr for $ty {
+                #[inline]
+                fn to_compact(&self) -> Cow<'_, CompactString> {
+                    let s: &str = self.as_ref();
+                    Cow::Owned(CompactString::new(s))
+                }
+            }
+        )*
+    };
+}
+
+impl_as_compact_str!(str, String, Box<str>, Cow<'_, str>);
+
+impl sealed::Sealed for CompactString {}
+
+impl AsCompactStr for CompactString {
+    #[inline]
+    fn to_compact(&self) -> Cow<'_, CompactString> {
+        Cow::Borrowed(self)
+    }
+}
+
+impl sealed::Sealed for Cow<'_, CompactString> {}
+
+impl AsCompactStr for Cow<'_, CompactString> {
+    #[inline]
+    fn to_compact(&self) -> Cow<'_, CompactString> {
+        Cow::Borrowed(self)
+    }
+}
+
+impl<T: AsCompactStr + ?Sized> sealed::Sealed for &T {}
+
+impl<T: AsCompactStr + ?Sized> AsCompactStr for &T {
+    #[inline]
+    fn to_compact(&self) -> Cow<'_, CompactString> {
+        T::to_compact(self)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use alloc::string::{String, ToString};
@@ -207,9 +672,120 @@ mod tests {
     use proptest::prelude::*;
     use test_strategy::proptest;
 
-    use super::{CompactStringExt, ToCompactString};
+    use super::{
+        from_display, AsCompactStr, CollectTruncated, CompactStringExt, ToCompactString,
+        ToCompactStringPadded, ToCompactStringRadix,
+    };
     use crate::CompactString;
 
+    #[test]
+    fn test_from_display() {
+        let nums: Vec<CompactString> = [-1_i32, 0, 42].iter().map(from_display).collect();
+        assert_eq!(nums, ["-1", "0", "42"]);
+
+        let mixed = [
+            from_display(1.5_f64),
+            from_display('🦀'),
+            from_display(String::from("owned")),
+            from_display(format_args!("{}-{}", "a", 1)),
+        ];
+        assert_eq!(mixed, ["1.5", "🦀", "owned", "a-1"]);
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[proptest]
+    fn proptest_from_display(val: i64, s: String) {
+        prop_assert_eq!(from_display(val), val.to_string());
+        prop_assert_eq!(from_display(&s), s);
+    }
+
+    #[test]
+    fn test_as_compact_str() {
+        use alloc::borrow::Cow;
+        use alloc::boxed::Box;
+
+        fn view(s: impl AsCompactStr) -> CompactString {
+            s.to_compact().into_owned()
+        }
+
+        fn view_borrowed<T: AsCompactStr>(s: &T) -> Cow<'_, CompactString> {
+            s.to_compact()
+        }
+
+        let long = "this is a long string that will be on the heap";
+        assert_eq!(view("hello"), "hello");
+        assert_eq!(view(String::from(long)), long);
+        assert_eq!(view(Box::<str>::from(long)), long);
+        assert_eq!(view(Cow::Borrowed(long)), long);
+        let owned = String::from("nested");
+        let strings = [&owned];
+        assert_eq!(view(strings[0]), "nested");
+
+        // a CompactString, or a reference to one, is borrowed instead of copied
+        let compact = CompactString::new(long);
+        assert!(matches!(compact.to_compact(), Cow::Borrowed(c) if c.as_ptr() == compact.as_ptr()));
+        assert!(matches!(view_borrowed(&compact), Cow::Borrowed(_)));
+        let cow: Cow<'_, CompactString> = Cow::Borrowed(&compact);
+        assert!(matches!(cow.to_compact(), Cow::Borrowed(_)));
+        assert!(matches!(long.to_compact(), Cow::Owned(_)));
+    }
+
+    #[test]
+    fn test_collect_truncated() {
+        let (s, truncated) = ["ab", "cd", "ef"].into_iter().collect_compact_truncated(4);
+        assert_eq!(s, "abcd");
+        assert!(truncated);
+
+        // the limit is hit exactly, but only empty strings follow
+        let words = [String::from("ab"), String::from("cd"), String::new()];
+        let (s, truncated) = words.into_iter().collect_compact_truncated(4);
+        assert_eq!(s, "abcd");
+        assert!(!truncated);
+
+        // cut at a char boundary, 'ü' is 2 bytes
+        let (s, truncated) = ["grü", "ße"].into_iter().collect_compact_truncated(3);
+        assert_eq!(s, "gr");
+        assert!(truncated);
+
+        let (s, truncated) = "🦀🦀🦀".chars().collect_compact_truncated(9);
+        assert_eq!(s, "🦀🦀");
+        assert!(truncated);
+
+        let long = "this is a long string that will be on the heap";
+        let (s, truncated) = long
+            .split(' ')
+            .map(CompactString::from)
+            .collect_compact_truncated(0);
+        assert_eq!(s, "");
+        assert!(truncated);
+        let (s, truncated) = core::iter::once(long).collect_compact_truncated(usize::MAX);
+        assert_eq!(s, long);
+        assert!(!truncated);
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[proptest]
+    fn proptest_collect_truncated(words: Vec<String>, #[strategy(0..64usize)] max_bytes: usize) {
+        let joined: String = words.concat();
+        let (s, truncated) = words
+            .iter()
+            .map(String::as_str)
+            .collect_compact_truncated(max_bytes);
+
+        prop_assert!(s.len() <= max_bytes);
+        prop_assert!(joined.starts_with(s.as_str()));
+        prop_assert_eq!(truncated, joined.len() > max_bytes);
+        if truncated {
+            // we kept as much as fits
+            let next = joined[s.len()..].chars().next().unwrap();
+            prop_assert!(s.len() + next.len_utf8() > max_bytes);
+        }
+
+        let (chars, chars_truncated) = joined.chars().collect_compact_truncated(max_bytes);
+        prop_assert_eq!(chars, s);
+        prop_assert_eq!(chars_truncated, truncated);
+    }
+
     #[test]
     fn test_join() {
         let slice = ["hello", "world"];
@@ -240,6 +816,124 @@ mod tests {
         assert_eq!(c, "🍎🍊🍌");
     }
 
+    #[test]
+    fn test_display_join() {
+        let items = vec!["🍎", "🍊", "🍌"];
+        let joined = items.display_join(", ");
+        assert_eq!(joined.to_string(), "🍎, 🍊, 🍌");
+        assert_eq!(joined.to_compact_string(), "🍎, 🍊, 🍌");
+
+        let empty: [&str; 0] = [];
+        assert_eq!(empty.display_join(", ").to_string(), "");
+
+        // the adaptor can also be formatted through `ToCompactString`
+        let joined = ["a", "b"].display_join("-");
+        assert_eq!(ToCompactString::to_compact_string(&joined), "a-b");
+    }
+
+    #[proptest]
+    #[cfg_attr(miri, ignore)]
+    fn proptest_display_join(items: Vec<String>, separator: String) {
+        let joined = items.display_join(&separator);
+        let control = items.join(&separator);
+
+        prop_assert_eq!(joined.to_string(), control.clone());
+        prop_assert_eq!(joined.to_compact_string(), control);
+    }
+
+    #[test]
+    fn test_to_compact_string_radix() {
+        assert_eq!(0u8.to_compact_string_radix(2), "0");
+        assert_eq!(u128::MAX.to_compact_string_radix(2).len(), 128);
+        assert_eq!(i128::MIN.to_compact_string_radix(2).len(), 129);
+        assert_eq!(u64::MAX.to_compact_string_hex(), "ffffffffffffffff");
+        assert_eq!(u64::MAX.to_compact_string_radix(2).len(), 64);
+        assert_eq!(i64::MIN.to_compact_string_radix(2).len(), 65);
+        assert_eq!(i64::MIN.to_compact_string_radix(10), "-9223372036854775808");
+        assert_eq!(
+            usize::MAX.to_compact_string_radix(10),
+            usize::MAX.to_string()
+        );
+        assert_eq!(i8::MIN.to_compact_string_radix(10), "-128");
+        assert_eq!(35u32.to_compact_string_radix(36), "z");
+        assert!(u64::MAX.to_compact_string_binary().is_heap_allocated());
+        assert!(!u64::MAX.to_compact_string_hex().is_heap_allocated());
+    }
+
+    #[test]
+    #[should_panic(expected = "radix must be in the range 2..=36, got 37")]
+    fn test_to_compact_string_radix_invalid() {
+        1u8.to_compact_string_radix(37);
+    }
+
+    #[proptest]
+    #[cfg_attr(miri, ignore)]
+    fn proptest_to_compact_string_radix(val: i64, #[strategy(2u32..=36)] radix: u32) {
+        let compact = val.to_compact_string_radix(radix);
+        prop_assert_eq!(i64::from_str_radix(&compact, radix).unwrap(), val);
+
+        let hex = (val as u64).to_compact_string_hex();
+        prop_assert_eq!(hex, format!("{:x}", val as u64));
+    }
+
+    #[test]
+    fn test_to_compact_string_padded() {
+        assert_eq!(0u8.to_compact_string_padded(0, '0'), "0");
+        assert_eq!(42u64.to_compact_string_padded(6, '0'), "000042");
+        assert_eq!(i8::MIN.to_compact_string_padded(6, '0'), "-00128");
+        assert_eq!(i8::MIN.to_compact_string_padded(6, '*'), "**-128");
+        assert_eq!(7usize.to_compact_string_padded(3, '🦀'), "🦀🦀7");
+        assert_eq!(
+            u128::MAX.to_compact_string_padded(4, '0'),
+            u128::MAX.to_string()
+        );
+        assert!(!12u32.to_compact_string_padded(24, '0').is_heap_allocated());
+
+        let long = 12u32.to_compact_string_padded(100, '0');
+        assert_eq!(long.len(), 100);
+        assert_eq!(long.capacity(), 100);
+    }
+
+    #[proptest]
+    #[cfg_attr(miri, ignore)]
+    fn proptest_to_compact_string_padded(val: i64, #[strategy(0usize..32)] width: usize) {
+        prop_assert_eq!(
+            val.to_compact_string_padded(width, '0'),
+            format!("{:0width$}", val, width = width)
+        );
+        prop_assert_eq!(
+            val.to_compact_string_padded(width, ' '),
+            format!("{:>width$}", val, width = width)
+        );
+    }
+
+    #[test]
+    fn test_concat_and_join_allocate_once() {
+        let items = [
+            "this is ",
+            "a long string ",
+            "that will be ",
+            "allocated on the heap",
+        ];
+        let expected_len = items.iter().map(|s| s.len()).sum::<usize>();
+
+        let c = items.concat_compact();
+        assert_eq!(
+            c,
+            "this is a long string that will be allocated on the heap"
+        );
+        assert_eq!(c.capacity(), expected_len);
+
+        let c = items.join_compact("-");
+        assert_eq!(c.len(), expected_len + 3);
+        assert_eq!(c.capacity(), expected_len + 3);
+
+        let empty: [&str; 0] = [];
+        assert_eq!(empty.concat_compact(), "");
+        assert_eq!(empty.join_compact(", "), "");
+        assert!(!empty.join_compact(", ").is_heap_allocated());
+    }
+
     #[proptest]
     #[cfg_attr(miri, ignore)]
     fn proptest_concat(items: Vec<String>) {
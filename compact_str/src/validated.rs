@@ -0,0 +1,437 @@
+use alloc::string::String;
+use core::borrow::Borrow;
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::ops::Deref;
+use core::str::FromStr;
+
+use crate::CompactString;
+
+/// Checks that a string upholds some invariant, for use with [`Validated`].
+///
+/// # Example
+/// ```
+/// use compact_str::Validator;
+///
+/// struct Slug;
+///
+/// impl Validator for Slug {
+///     type Error = &'static str;
+///
+///     fn validate(s: &str) -> Result<(), Self::Error> {
+///         if s.is_empty() {
+///             return Err("slug must not be empty");
+///         }
+///         if !s.bytes().all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-') {
+///             return Err("slug must only contain a-z, 0-9 and '-'");
+///         }
+///         Ok(())
+///     }
+/// }
+/// ```
+pub trait Validator {
+    /// The error returned when a string is invalid.
+    type Error;
+
+    /// The maximum length in bytes of a valid string, if the invariant implies one.
+    ///
+    /// This is only used to reject input early, e.g. when deserializing a [`Validated`] the
+    /// length is checked before anything gets copied. [`Validator::validate`] is still called
+    /// and must check the length itself.
+    const MAX_LEN: usize = usize::MAX;
+
+    /// Returns an error if `s` does not uphold the invariant.
+    fn validate(s: &str) -> Result<(), Self::Error>;
+}
+
+/// A [`Validator`] that only accepts strings of at most `MAX` bytes, see
+/// [`BoundedCompactString`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MaxLen<const MAX: usize>;
+
+impl<const MAX: usize> Validator for MaxLen<MAX> {
+    type Error = TooLongError;
+
+    const MAX_LEN: usize = MAX;
+
+    #[inline]
+    fn validate(s: &str) -> Result<(), Self::Error> {
+        if s.len() > MAX {
+            Err(TooLongError {
+                len: s.len(),
+                max: MAX,
+            })
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// A [`CompactString`] that is guaranteed to be at most `MAX` bytes long.
+///
+/// The bound is part of the type, so a function taking a `BoundedCompactString<64>` never has to
+/// check, or truncate, the length itself. It's a [`Validated`] string, so it can be created with
+/// [`TryFrom`] or [`str::parse`], and with the `serde` feature enabled, deserializing one rejects
+/// strings that are too long before copying them.
+///
+/// # Example
+/// ```
+/// use compact_str::BoundedCompactString;
+///
+/// type Username = BoundedCompactString<16>;
+///
+/// let name = Username::try_from("ferris").unwrap();
+/// assert_eq!(name, "ferris");
+/// assert_eq!(name.len(), 6);
+///
+/// let err = Username::try_from("a name that is much too long").unwrap_err();
+/// assert_eq!(err.actual_len(), 28);
+/// assert_eq!(err.to_string(), "string is 28 bytes long, but at most 16 bytes are allowed");
+/// ```
+pub type BoundedCompactString<const MAX: usize> = Validated<MaxLen<MAX>>;
+
+/// A [`CompactString`] that is guaranteed to have passed the [`Validator`] `V`.
+///
+/// The only ways to create a [`Validated`] run `V::validate` first, and it can't be mutated
+/// afterwards, so the invariant holds for as long as the value exists. Like any other
+/// [`CompactString`], short strings are stored inline.
+///
+/// # Example
+/// ```
+/// use compact_str::{Validated, Validator};
+///
+/// struct Identifier;
+///
+/// impl Validator for Identifier {
+///     type Error = &'static str;
+///
+///     fn validate(s: &str) -> Result<(), Self::Error> {
+///         let mut chars = s.chars();
+///         match chars.next() {
+///             Some(c) if c.is_alphabetic() || c == '_' => (),
+///             _ => return Err("identifiers must start with a letter or '_'"),
+///         }
+///         if chars.all(|c| c.is_alphanumeric() || c == '_') {
+///             Ok(())
+///         } else {
+///             Err("identifiers must only contain letters, digits and '_'")
+///         }
+///     }
+/// }
+///
+/// let ident: Validated<Identifier> = "user_id".parse().unwrap();
+/// assert_eq!(ident, "user_id");
+///
+/// assert!(Validated::<Identifier>::try_from("1st").is_err());
+/// ```
+pub struct Validated<V: ?Sized> {
+    value: CompactString,
+    _validator: PhantomData<fn() -> V>,
+}
+
+impl<V: Validator + ?Sized> Validated<V> {
+    /// Validates `value`, returning it along with the error if it is invalid.
+    pub fn new(value: CompactString) -> Result<Self, (CompactString, V::Error)> {
+        match V::validate(&value) {
+            Ok(()) => Ok(Validated {
+                value,
+                _validator: PhantomData,
+            }),
+            Err(err) => Err((value, err)),
+        }
+    }
+}
+
+impl<V: ?Sized> Validated<V> {
+    /// Returns the validated string.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        self.value.as_str()
+    }
+
+    /// Returns a reference to the underlying [`CompactString`].
+    #[inline]
+    pub fn as_compact_str(&self) -> &CompactString {
+        &self.value
+    }
+
+    /// Consumes the [`Validated`], returning the underlying [`CompactString`].
+    #[inline]
+    pub fn into_inner(self) -> CompactString {
+        self.value
+    }
+}
+
+impl<V: Validator + ?Sized> TryFrom<&str> for Validated<V> {
+    type Error = V::Error;
+
+    #[inline]
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        // validate before copying, so invalid input never allocates
+        V::validate(value)?;
+        Ok(Validated {
+            value: CompactString::new(value),
+            _validator: PhantomData,
+        })
+    }
+}
+
+impl<V: Validator + ?Sized> TryFrom<String> for Validated<V> {
+    type Error = V::Error;
+
+    #[inline]
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        V::validate(&value)?;
+        Ok(Validated {
+            value: CompactString::from(value),
+            _validator: PhantomData,
+        })
+    }
+}
+
+impl<V: Validator + ?Sized> TryFrom<CompactString> for Validated<V> {
+    type Error = V::Error;
+
+    #[inline]
+    fn try_from(value: CompactString) -> Result<Self, Self::Error> {
+        Validated::new(value).map_err(|(_, err)| err)
+    }
+}
+
+impl<V: Validator + ?Sized> FromStr for Validated<V> {
+    type Err = V::Error;
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Validated::try_from(s)
+    }
+}
+
+impl<V: ?Sized> From<Validated<V>> for CompactString {
+    #[inline]
+    fn from(value: Validated<V>) -> Self {
+        value.value
+    }
+}
+
+impl<V: ?Sized> Deref for Validated<V> {
+    type Target = str;
+
+    #[inline]
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<V: ?Sized> AsRef<str> for Validated<V> {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<V: ?Sized> Borrow<str> for Validated<V> {
+    #[inline]
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+
+// Note: these are implemented by hand, since deriving them would require `V` to implement them too
+
+impl<V: ?Sized> Clone for Validated<V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Validated {
+            value: self.value.clone(),
+            _validator: PhantomData,
+        }
+    }
+}
+
+impl<V: ?Sized> PartialEq for Validated<V> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<V: ?Sized> Eq for Validated<V> {}
+
+impl<V: ?Sized> PartialEq<str> for Validated<V> {
+    #[inline]
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl<V: ?Sized> PartialEq<&str> for Validated<V> {
+    #[inline]
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl<V: ?Sized> PartialEq<CompactString> for Validated<V> {
+    #[inline]
+    fn eq(&self, other: &CompactString) -> bool {
+        self.value == *other
+    }
+}
+
+impl<V: ?Sized> PartialOrd for Validated<V> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<V: ?Sized> Ord for Validated<V> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.value.cmp(&other.value)
+    }
+}
+
+impl<V: ?Sized> Hash for Validated<V> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.value.hash(state)
+    }
+}
+
+impl<V: ?Sized> fmt::Debug for Validated<V> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.value, f)
+    }
+}
+
+impl<V: ?Sized> fmt::Display for Validated<V> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.value, f)
+    }
+}
+
+/// The error returned when a string is longer than a [`BoundedCompactString`] allows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TooLongError {
+    len: usize,
+    max: usize,
+}
+
+impl TooLongError {
+    /// Returns the length of the rejected string, in bytes.
+    #[inline]
+    pub fn actual_len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns the maximum length that was allowed, in bytes.
+    #[inline]
+    pub fn max_len(&self) -> usize {
+        self.max
+    }
+}
+
+impl fmt::Display for TooLongError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "string is {} bytes long, but at most {} bytes are allowed",
+            self.len, self.max
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TooLongError {}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::String;
+
+    use super::{BoundedCompactString, TooLongError, Validated, Validator};
+    use crate::CompactString;
+
+    struct NonEmptyAscii;
+
+    impl Validator for NonEmptyAscii {
+        type Error = &'static str;
+
+        fn validate(s: &str) -> Result<(), Self::Error> {
+            match (s.is_empty(), s.is_ascii()) {
+                (true, _) => Err("empty"),
+                (_, false) => Err("not ascii"),
+                _ => Ok(()),
+            }
+        }
+    }
+
+    type Token = Validated<NonEmptyAscii>;
+
+    #[test]
+    fn test_validate() {
+        let token: Token = "hello".parse().unwrap();
+        assert_eq!(token, "hello");
+        assert!(!token.as_compact_str().is_heap_allocated());
+
+        assert_eq!(Token::try_from("").unwrap_err(), "empty");
+        assert_eq!(
+            Token::try_from(String::from("🦀")).unwrap_err(),
+            "not ascii"
+        );
+
+        let (rejected, err) = Token::new(CompactString::new("héllo")).unwrap_err();
+        assert_eq!(rejected, "héllo");
+        assert_eq!(err, "not ascii");
+    }
+
+    #[test]
+    fn test_reuses_buffer() {
+        let long = String::from("this is a long string that will be on the heap");
+        let ptr = long.as_ptr();
+
+        let token = Token::try_from(long).unwrap();
+        assert_eq!(token.as_ptr(), ptr);
+        assert_eq!(CompactString::from(token).as_ptr(), ptr);
+    }
+
+    #[test]
+    fn test_traits() {
+        use alloc::collections::BTreeSet;
+
+        let a: Token = "a".parse().unwrap();
+        let b: Token = "b".parse().unwrap();
+
+        let set: BTreeSet<Token> = [b.clone(), a.clone()].into_iter().collect();
+        assert!(set.contains("a"));
+        assert_eq!(set.iter().next(), Some(&a));
+        assert_eq!(alloc::format!("{} {:?}", a, b), "a \"b\"");
+    }
+
+    #[test]
+    fn test_bounded() {
+        type Name = BoundedCompactString<8>;
+
+        let empty = Name::try_from("").unwrap();
+        assert_eq!(empty, "");
+
+        let max: Name = "12345678".parse().unwrap();
+        assert_eq!(max.as_compact_str(), &CompactString::new("12345678"));
+
+        let err = Name::try_from(String::from("123456789")).unwrap_err();
+        assert_eq!(err, TooLongError { len: 9, max: 8 });
+        assert_eq!((err.actual_len(), err.max_len()), (9, 8));
+
+        // the bound is in bytes, not chars
+        assert!(Name::try_from("🦀🦀").is_ok());
+        assert!(Name::try_from("🦀🦀🦀").is_err());
+
+        let (rejected, _) = Name::new(CompactString::new("too long to fit")).unwrap_err();
+        assert_eq!(rejected, "too long to fit");
+    }
+}
@@ -0,0 +1,68 @@
+//! Asserts that converting a `CompactString` into a `Box<str>` hands over or shrinks an existing
+//! heap buffer, and only allocates when the string isn't on the heap yet.
+
+use compact_str::CompactString;
+
+mod common;
+use common::count_allocator_calls;
+
+const LONG: &str = "a string that is too long to be stored inline";
+
+#[test]
+fn test_boxed_str_exact_capacity() {
+    let compact = CompactString::new(LONG);
+    assert!(compact.is_heap_allocated());
+    assert_eq!(compact.len(), compact.capacity());
+    let ptr = compact.as_ptr();
+
+    let (calls, boxed) = count_allocator_calls(|| Box::<str>::from(compact));
+    assert_eq!(calls, 0);
+    assert_eq!(&*boxed, LONG);
+    assert_eq!(boxed.as_ptr(), ptr);
+
+    // handing over a `String` buffer that's exactly sized works the same
+    let compact = CompactString::from(String::from(LONG));
+    let (calls, boxed) = count_allocator_calls(|| Box::<str>::from(compact));
+    assert_eq!(calls, 0);
+    assert_eq!(&*boxed, LONG);
+
+    let (calls, ()) = count_allocator_calls(|| drop(boxed));
+    assert_eq!(calls, 1);
+}
+
+#[test]
+fn test_boxed_str_excess_capacity() {
+    let mut compact = CompactString::with_capacity(LONG.len() * 2);
+    compact.push_str(LONG);
+    assert!(compact.capacity() > compact.len());
+
+    // the buffer gets shrunk in place, which is a single call to the allocator
+    let (calls, boxed) = count_allocator_calls(|| Box::<str>::from(compact));
+    assert_eq!(calls, 1);
+    assert_eq!(&*boxed, LONG);
+
+    let (calls, ()) = count_allocator_calls(|| drop(boxed));
+    assert_eq!(calls, 1);
+}
+
+#[test]
+fn test_boxed_str_inline_and_static() {
+    let inline = CompactString::new("hello");
+    let static_str = CompactString::const_new(LONG);
+    assert!(!inline.is_heap_allocated());
+    assert!(!static_str.is_heap_allocated());
+
+    for (compact, expected) in [(inline, "hello"), (static_str, LONG)] {
+        let (calls, boxed) = count_allocator_calls(|| Box::<str>::from(compact));
+        assert_eq!(calls, 1);
+        assert_eq!(&*boxed, expected);
+
+        let (calls, ()) = count_allocator_calls(|| drop(boxed));
+        assert_eq!(calls, 1);
+    }
+
+    // an empty `Box<str>` doesn't need an allocation at all
+    let (calls, boxed) = count_allocator_calls(|| Box::<str>::from(CompactString::default()));
+    assert_eq!(calls, 0);
+    assert!(boxed.is_empty());
+}
@@ -0,0 +1,48 @@
+//! A counting global allocator shared by the integration tests that assert how often the
+//! allocator is called.
+//!
+//! Every test binary that includes this module gets its own global allocator, so it doesn't affect
+//! any other tests.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+struct CountingAlloc;
+
+thread_local! {
+    // Counted per thread, so the test harness allocating on other threads doesn't interfere.
+    static ALLOCATOR_CALLS: Cell<usize> = const { Cell::new(0) };
+}
+
+fn count_call() {
+    // `try_with` because the thread local might already be destroyed when a thread exits.
+    let _ = ALLOCATOR_CALLS.try_with(|calls| calls.set(calls.get() + 1));
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        count_call();
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        count_call();
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        count_call();
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+/// Returns the number of allocations, deallocations, and reallocations made while running `f`,
+/// along with its result.
+pub fn count_allocator_calls<R>(f: impl FnOnce() -> R) -> (usize, R) {
+    let before = ALLOCATOR_CALLS.with(Cell::get);
+    let result = f();
+    (ALLOCATOR_CALLS.with(Cell::get) - before, result)
+}
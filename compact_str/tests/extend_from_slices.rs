@@ -0,0 +1,49 @@
+//! Asserts that `extend_from_slices` and `from_slices` grow a `CompactString` at most once.
+
+use compact_str::CompactString;
+
+mod common;
+use common::count_allocator_calls;
+
+const PIECES: &[&str] = &[
+    "this ", "is ", "a ", "long ", "string ", "built ", "from ", "many ", "small ", "pieces",
+];
+const JOINED: &str = "this is a long string built from many small pieces";
+
+#[test]
+fn test_from_slices_allocates_once() {
+    let (calls, compact) = count_allocator_calls(|| CompactString::from_slices(PIECES));
+    assert_eq!(calls, 1);
+    assert_eq!(compact, JOINED);
+    assert!(compact.is_heap_allocated());
+
+    let (calls, compact) = count_allocator_calls(|| CompactString::from_slices(&["a", "b", "c"]));
+    assert_eq!(calls, 0);
+    assert_eq!(compact, "abc");
+
+    let (calls, compact) = count_allocator_calls(|| CompactString::from_slices(&[]));
+    assert_eq!(calls, 0);
+    assert_eq!(compact, "");
+}
+
+#[test]
+fn test_extend_from_slices_grows_at_most_once() {
+    // inline -> heap
+    let mut compact = CompactString::new("prefix: ");
+    let calls = count_allocator_calls(|| compact.extend_from_slices(PIECES)).0;
+    assert_eq!(calls, 1);
+    assert_eq!(compact, format!("prefix: {}", JOINED));
+
+    // heap -> larger heap
+    let mut compact = CompactString::new(JOINED);
+    compact.shrink_to_fit();
+    let calls = count_allocator_calls(|| compact.extend_from_slices(PIECES)).0;
+    assert_eq!(calls, 1);
+    assert_eq!(compact, format!("{}{}", JOINED, JOINED));
+
+    // already enough capacity
+    let mut compact = CompactString::with_capacity(JOINED.len());
+    let calls = count_allocator_calls(|| compact.extend_from_slices(PIECES)).0;
+    assert_eq!(calls, 0);
+    assert_eq!(compact, JOINED);
+}
@@ -0,0 +1,51 @@
+//! Asserts that deserializing a `CompactString` with common serde formats copies the string
+//! straight into its own buffer, without going through an intermediate `String`.
+
+#![cfg(feature = "serde")]
+
+use compact_str::CompactString;
+
+mod common;
+use common::count_allocator_calls;
+
+const SHORT: &str = "ferris";
+const LONG: &str = "a string that is too long to be stored inline 🦀";
+
+#[test]
+fn test_rmp_serde_deserialize() {
+    let bytes = rmp_serde::to_vec(SHORT).unwrap();
+    let (calls, compact) =
+        count_allocator_calls(|| rmp_serde::from_slice::<CompactString>(&bytes).unwrap());
+    assert_eq!(calls, 0);
+    assert_eq!(compact, SHORT);
+
+    let bytes = rmp_serde::to_vec(LONG).unwrap();
+    let (calls, compact) =
+        count_allocator_calls(|| rmp_serde::from_slice::<CompactString>(&bytes).unwrap());
+    assert_eq!(calls, 1);
+    assert_eq!(compact, LONG);
+}
+
+#[test]
+fn test_bitcode_deserialize() {
+    // bitcode allocates for its own bookkeeping, so compare against a string that gets inlined
+    let bytes = bitcode::serialize(SHORT).unwrap();
+    let (short_calls, compact) =
+        count_allocator_calls(|| bitcode::deserialize::<CompactString>(&bytes).unwrap());
+    assert_eq!(compact, SHORT);
+
+    let bytes = bitcode::serialize(LONG).unwrap();
+    let (long_calls, compact) =
+        count_allocator_calls(|| bitcode::deserialize::<CompactString>(&bytes).unwrap());
+    assert_eq!(long_calls, short_calls + 1);
+    assert_eq!(compact, LONG);
+}
+
+#[test]
+fn test_serde_json_deserialize() {
+    let json = serde_json::to_string(LONG).unwrap();
+    let (calls, compact) =
+        count_allocator_calls(|| serde_json::from_str::<CompactString>(&json).unwrap());
+    assert_eq!(calls, 1);
+    assert_eq!(compact, LONG);
+}
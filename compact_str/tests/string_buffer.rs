@@ -0,0 +1,49 @@
+//! Asserts that converting between `String` and `CompactString` with `from_string_buffer` and
+//! `into_string_buffer` never touches the allocator.
+
+use compact_str::CompactString;
+
+mod common;
+use common::count_allocator_calls;
+
+#[test]
+fn test_string_buffer_roundtrip_is_allocation_free() {
+    let strings = [
+        String::new(),
+        String::with_capacity(16),
+        String::from("hello"),
+        String::from("a string that is too long to be stored inline"),
+    ];
+
+    for og in strings {
+        let (ptr, len, cap) = (og.as_ptr(), og.len(), og.capacity());
+
+        let (calls, string) = count_allocator_calls(|| {
+            let compact = CompactString::from_string_buffer(og);
+            compact.into_string_buffer()
+        });
+        assert_eq!(calls, 0, "round trip of {:?} used the allocator", string);
+        assert_eq!(string.as_ptr(), ptr);
+        assert_eq!(string.len(), len);
+        assert_eq!(string.capacity(), cap);
+    }
+}
+
+#[test]
+fn test_into_string_buffer_copies_inline_strings() {
+    let (calls, string) =
+        count_allocator_calls(|| CompactString::const_new("").into_string_buffer());
+    assert_eq!(calls, 0);
+    assert_eq!(string, "");
+
+    let compact = CompactString::new("hello");
+    assert!(!compact.is_heap_allocated());
+    let (calls, string) = count_allocator_calls(|| compact.into_string_buffer());
+    assert_eq!(calls, 1);
+    assert_eq!(string, "hello");
+
+    let compact = CompactString::const_new("a static string that is longer than inline");
+    let (calls, string) = count_allocator_calls(|| compact.into_string_buffer());
+    assert_eq!(calls, 1);
+    assert_eq!(string, "a static string that is longer than inline");
+}